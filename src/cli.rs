@@ -1,15 +1,36 @@
-use chrono::NaiveDate;
+use std::{collections::BTreeMap, path::PathBuf};
+
+use chrono::{Local, NaiveDate};
 use cli_table::{format::Justify, Cell, Color, Style, Table};
 use colored::Colorize;
 
 use crate::{
     error::MenteeError,
     models::{
+        agenda::{Agenda, AgendaItem, AgendaPriority},
         call::CallWithMenteeName,
-        mentee::{MenteeSummary, MenteeWithCounts, Status},
+        capacity::CapacityMonth,
+        contact::{Contact, ContactKind},
+        expense::ExpenseWithMenteeName,
+        goal::{GoalDetails, GoalWithProgress},
+        invoice::Invoice,
+        mentee::{
+            MenteeSummary, MenteeWithContacts, MenteeWithCounts, Source, Status, StatusChange,
+        },
         payment::PaymentWithMenteeName,
-        video::VideoWithMenteeName,
+        report::{
+            IncomeRow, RateRow, RatesReport, RetentionReport, SourceRow, TaxReport,
+            TurnaroundReport, TurnaroundRow,
+        },
+        statement::Statement,
+        timeline::TimelineEvent,
+        video::{VideoStatus, VideoWithMenteeName},
+    },
+    utils::{
+        template::{escape_html, render_template},
+        validation::parse_date_from_db,
     },
+    ExportFormat, InvoiceFormat, OutputFormat,
 };
 
 fn calc_net_per_call(net: &u32, calls: &u32) -> u32 {
@@ -22,7 +43,7 @@ fn calc_net_per_call(net: &u32, calls: &u32) -> u32 {
 
 fn add_ordinal_suffix(n: u32) -> String {
     let suffix = match n % 100 {
-        11 | 12 | 13 => "th", // Special case for 11, 12, 13
+        11..=13 => "th", // Special case for 11, 12, 13
         _ => match n % 10 {
             1 => "st",
             2 => "nd",
@@ -47,24 +68,153 @@ fn capitalize_first_letter_of_each_word(s: &str) -> String {
         .join(" ") // Join words with a space
 }
 
+const NOTES_PREVIEW_LENGTH: usize = 40;
+const NOTES_WRAP_WIDTH: usize = 80;
+
+/// First line of multi-line notes, shortened so tables stay one row per record
+fn notes_preview(notes: &str) -> String {
+    let mut lines = notes.lines().map(str::trim).filter(|line| !line.is_empty());
+    let first = lines
+        .next()
+        .unwrap_or("")
+        .trim_start_matches(['#', '-', '*', '>', ' ']);
+
+    if first.chars().count() > NOTES_PREVIEW_LENGTH {
+        let shortened: String = first.chars().take(NOTES_PREVIEW_LENGTH - 1).collect();
+        format!("{}…", shortened.trim_end())
+    } else if lines.next().is_some() {
+        format!("{} …", first)
+    } else {
+        first.to_string()
+    }
+}
+
+/// Greedily wraps words to `width`, prefixing the first and following lines
+fn wrap_words(text: &str, width: usize, first_prefix: &str, rest_prefix: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = first_prefix.to_string();
+    let mut current_len = first_prefix.chars().count();
+    let mut line_has_words = false;
+
+    for word in text.split_whitespace() {
+        let word_len = word.chars().count();
+
+        if line_has_words && current_len + 1 + word_len > width {
+            lines.push(current);
+            current = rest_prefix.to_string();
+            current_len = rest_prefix.chars().count();
+            line_has_words = false;
+        }
+
+        if line_has_words {
+            current.push(' ');
+            current_len += 1;
+        }
+
+        current.push_str(word);
+        current_len += word_len;
+        line_has_words = true;
+    }
+
+    lines.push(current);
+    lines
+}
+
+/// Splits a list item into its marker and content e.g. `- item` or `2. item`
+fn split_list_marker(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(content) = line.strip_prefix(bullet) {
+            return Some(("• ".to_string(), content));
+        }
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(content) = line[digits..].strip_prefix(". ") {
+            return Some((format!("{}. ", &line[..digits]), content));
+        }
+    }
+
+    None
+}
+
+/// Renders Markdown notes for the terminal: headings in bold, list items with
+/// hanging indents, code blocks untouched and everything else wrapped to `width`
+pub fn render_markdown(text: &str, width: usize) -> String {
+    let mut output: Vec<String> = Vec::new();
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            output.push(format!("    {}", line));
+        } else if trimmed.is_empty() {
+            output.push(String::new());
+        } else if trimmed.starts_with('#') {
+            let heading = trimmed.trim_start_matches('#').trim();
+            output.push(heading.bold().to_string());
+        } else if let Some((marker, content)) = split_list_marker(trimmed) {
+            let first_prefix = format!("{}{}", indent, marker);
+            let rest_prefix = " ".repeat(first_prefix.chars().count());
+            output.extend(wrap_words(content, width, &first_prefix, &rest_prefix));
+        } else {
+            output.extend(wrap_words(trimmed, width, indent, indent));
+        }
+    }
+
+    output.join("\n")
+}
+
 pub fn format_mentees(mentees: Vec<MenteeSummary>) -> Vec<Vec<String>> {
     let rows: Vec<Vec<String>> = mentees
         .into_iter()
         .map(|mentee| {
-            let remaining_calls: String;
+            let remaining_calls = if mentee.remaining_calls > 0 {
+                format!("{}", mentee.remaining_calls.to_string().green())
+            } else {
+                format!("{}", mentee.remaining_calls.to_string().red())
+            };
+
+            let last_call = match (&mentee.last_call_date, mentee.days_since_last_call) {
+                (Some(date), Some(days)) => format!(
+                    "{} ({}d ago)",
+                    format_date(date).unwrap_or_else(|_| date.clone()),
+                    days
+                ),
+                _ => "Never".to_string(),
+            };
 
-            if mentee.remaining_calls > 0 {
-                remaining_calls = format!("{}", mentee.remaining_calls.to_string().green())
+            // highlight mentees who have gone quiet for longer than their cadence
+            let last_call = if mentee.is_overdue() {
+                format!("{}", last_call.red())
             } else {
-                remaining_calls = format!("{}", mentee.remaining_calls.to_string().red())
-            }
+                last_call
+            };
+
+            let average_gap = mentee
+                .avg_days_between_calls
+                .map(|days| format!("{:.1}d", days))
+                .unwrap_or_else(|| "-".to_string());
 
             vec![
                 capitalize_first_letter_of_each_word(&mentee.name),
                 mentee.calls_per_month.to_string(),
                 remaining_calls,
+                last_call,
+                average_gap,
                 capitalize_first_letter_of_each_word(Status::as_str(&mentee.status)),
-                mentee.notes.unwrap_or("".to_string()),
+                format!(
+                    "{} / {}",
+                    mentee.calls_last_30_days, mentee.calls_last_90_days
+                ),
+                notes_preview(mentee.notes.as_deref().unwrap_or("")),
             ]
         })
         .collect();
@@ -85,19 +235,88 @@ pub fn format_videos(videos: Vec<VideoWithMenteeName>) -> Vec<Vec<String>> {
         .into_iter()
         .map(|video| {
             let formatted_date = format_date(&video.date).unwrap_or_else(|_| video.date.clone());
+            let due_date = video
+                .due_date
+                .as_deref()
+                .map(|date| format_date(date).unwrap_or_else(|_| date.to_string()))
+                .unwrap_or_default();
+            let length = if video.status == VideoStatus::Delivered {
+                video.length.to_string()
+            } else {
+                String::new()
+            };
+            let source = match (&video.source_url, &video.file_path) {
+                (Some(_), Some(_)) => "Link, File",
+                (Some(_), None) => "Link",
+                (None, Some(_)) => "File",
+                (None, None) => "",
+            };
 
             vec![
                 video.id.to_string(),
                 capitalize_first_letter_of_each_word(&video.mentee_name),
                 formatted_date,
-                video.length.to_string(),
-                video.notes,
+                video.status.label().to_string(),
+                due_date,
+                length,
+                source.to_string(),
+                notes_preview(&video.notes),
             ]
         })
         .collect();
 
     rows
 }
+
+/// How long until a review is due, e.g. "in 3 days", "today" or "2 days overdue"
+pub fn format_due_in(due_date: &str, today: NaiveDate) -> String {
+    let Ok(due_date) = parse_date_from_db(due_date) else {
+        return due_date.to_string();
+    };
+
+    let plural = |days: i64| if days == 1 { "" } else { "s" };
+
+    match (due_date - today).num_days() {
+        0 => "today".to_string(),
+        days if days > 0 => format!("in {} day{}", days, plural(days)),
+        days => format!("{} day{} overdue", -days, plural(-days)),
+    }
+}
+
+pub fn format_video_queue(videos: Vec<VideoWithMenteeName>, today: NaiveDate) -> Vec<Vec<String>> {
+    videos
+        .into_iter()
+        .map(|video| {
+            let received = video.received_date.as_deref().unwrap_or(&video.date);
+            let (due_date, due_in) = match video.due_date.as_deref() {
+                Some(due_date) => {
+                    let due_in = format_due_in(due_date, today);
+                    let overdue = parse_date_from_db(due_date).is_ok_and(|date| date < today);
+                    let due_in = if overdue {
+                        due_in.red().to_string()
+                    } else {
+                        due_in
+                    };
+                    (
+                        format_date(due_date).unwrap_or_else(|_| due_date.to_string()),
+                        due_in,
+                    )
+                }
+                None => (String::new(), String::new()),
+            };
+
+            vec![
+                video.id.to_string(),
+                capitalize_first_letter_of_each_word(&video.mentee_name),
+                video.status.label().to_string(),
+                format_date(received).unwrap_or_else(|_| received.to_string()),
+                due_date,
+                due_in,
+            ]
+        })
+        .collect()
+}
+
 pub fn format_calls(calls: Vec<CallWithMenteeName>) -> Vec<Vec<String>> {
     let rows: Vec<Vec<String>> = calls
         .into_iter()
@@ -108,8 +327,10 @@ pub fn format_calls(calls: Vec<CallWithMenteeName>) -> Vec<Vec<String>> {
                 call.id.to_string(),
                 capitalize_first_letter_of_each_word(&call.mentee_name),
                 formatted_date,
-                call.notes.unwrap_or("".to_string()),
+                notes_preview(call.notes.as_deref().unwrap_or("")),
                 if call.free_call { "Yes" } else { "No" }.to_string(),
+                call.duration
+                    .map_or("-".to_string(), |duration| format!("{} min", duration)),
             ]
         })
         .collect();
@@ -117,7 +338,10 @@ pub fn format_calls(calls: Vec<CallWithMenteeName>) -> Vec<Vec<String>> {
     rows
 }
 
-pub fn format_payments(payments: Vec<PaymentWithMenteeName>) -> Vec<Vec<String>> {
+pub fn format_payments(
+    payments: Vec<PaymentWithMenteeName>,
+    default_currency: &str,
+) -> Vec<Vec<String>> {
     let rows: Vec<Vec<String>> = payments
         .into_iter()
         .map(|payment| {
@@ -129,6 +353,10 @@ pub fn format_payments(payments: Vec<PaymentWithMenteeName>) -> Vec<Vec<String>>
                 capitalize_first_letter_of_each_word(&payment.mentee_name),
                 formatted_date,
                 payment.amount.to_string(),
+                payment.fee.to_string(),
+                payment.net().to_string(),
+                payment.currency_or(default_currency).to_string(),
+                payment.source.clone().unwrap_or_default(),
             ]
         })
         .collect();
@@ -154,7 +382,10 @@ pub fn render_mentees_table(mentees: Vec<MenteeSummary>) -> Result<(), MenteeErr
             "Name".cell().bold(true),
             "Calls / Month".cell().bold(true),
             "Remaining Calls".cell().bold(true),
+            "Last Call".cell().bold(true),
+            "Avg Gap".cell().bold(true),
             "Status".cell().bold(true),
+            "Calls 30d / 90d".cell().bold(true),
             "Notes".cell().bold(true),
         ])
         .foreground_color(Some(Color::Blue))
@@ -162,7 +393,8 @@ pub fn render_mentees_table(mentees: Vec<MenteeSummary>) -> Result<(), MenteeErr
 
     let table_display = table.display()?;
 
-    Ok(println!("{}", table_display))
+    println!("{}", table_display);
+    Ok(())
 }
 
 pub fn render_calls_table(calls: Vec<CallWithMenteeName>) -> Result<(), MenteeError> {
@@ -185,13 +417,15 @@ pub fn render_calls_table(calls: Vec<CallWithMenteeName>) -> Result<(), MenteeEr
             "Date".cell().bold(true),
             "Notes".cell().bold(true),
             "Free Call".cell().bold(true),
+            "Duration".cell().bold(true),
         ])
         .foreground_color(Some(Color::Yellow))
         .bold(true);
 
     let table_display = table.display()?;
 
-    Ok(println!("{}", table_display))
+    println!("{}", table_display);
+    Ok(())
 }
 
 pub fn render_videos_table(videos: Vec<VideoWithMenteeName>) -> Result<(), MenteeError> {
@@ -212,7 +446,10 @@ pub fn render_videos_table(videos: Vec<VideoWithMenteeName>) -> Result<(), Mente
             "Video Id".cell().bold(true),
             "Mentee".cell().bold(true),
             "Date".cell().bold(true),
+            "Status".cell().bold(true),
+            "Due".cell().bold(true),
             "Length".cell().bold(true),
+            "Source".cell().bold(true),
             "Notes".cell().bold(true),
         ])
         .foreground_color(Some(Color::Magenta))
@@ -220,11 +457,29 @@ pub fn render_videos_table(videos: Vec<VideoWithMenteeName>) -> Result<(), Mente
 
     let table_display = table.display()?;
 
-    Ok(println!("{}", table_display))
+    println!("{}", table_display);
+    Ok(())
 }
 
-pub fn render_payments_table(payments: Vec<PaymentWithMenteeName>) -> Result<(), MenteeError> {
-    let rows = format_payments(payments);
+pub fn render_video_queue(videos: Vec<VideoWithMenteeName>) -> Result<(), MenteeError> {
+    if videos.is_empty() {
+        println!("No videos waiting for review");
+        return Ok(());
+    }
+
+    render_rows(
+        &["Video Id", "Mentee", "Status", "Received", "Due", "Due In"],
+        format_video_queue(videos, Local::now().date_naive()),
+        OutputFormat::Table,
+        Color::Magenta,
+    )
+}
+
+pub fn render_payments_table(
+    payments: Vec<PaymentWithMenteeName>,
+    default_currency: &str,
+) -> Result<(), MenteeError> {
+    let rows = format_payments(payments, default_currency);
 
     let cell_rows: Vec<Vec<cli_table::CellStruct>> = rows
         .into_iter()
@@ -242,16 +497,1114 @@ pub fn render_payments_table(payments: Vec<PaymentWithMenteeName>) -> Result<(),
             "Mentee".cell().bold(true),
             "Date".cell().bold(true),
             "Amount".cell().bold(true),
+            "Fee".cell().bold(true),
+            "Net".cell().bold(true),
+            "Currency".cell().bold(true),
+            "Source".cell().bold(true),
         ])
         .foreground_color(Some(Color::Green))
         .bold(true);
 
     let table_display = table.display()?;
 
-    Ok(println!("{}", table_display))
+    println!("{}", table_display);
+    Ok(())
+}
+
+pub fn format_expenses(expenses: Vec<ExpenseWithMenteeName>) -> Vec<Vec<String>> {
+    expenses
+        .into_iter()
+        .map(|expense| {
+            vec![
+                expense.id.to_string(),
+                format_date(&expense.date).unwrap_or_else(|_| expense.date.clone()),
+                capitalize_first_letter_of_each_word(expense.category.as_str()),
+                expense.description,
+                expense.amount.to_string(),
+                expense.currency,
+                expense
+                    .mentee_name
+                    .map(|name| capitalize_first_letter_of_each_word(&name))
+                    .unwrap_or_default(),
+                if expense.receipt_path.is_some() {
+                    "Yes"
+                } else {
+                    "No"
+                }
+                .to_string(),
+            ]
+        })
+        .collect()
+}
+
+pub fn render_expenses_table(expenses: Vec<ExpenseWithMenteeName>) -> Result<(), MenteeError> {
+    render_rows(
+        &[
+            "Expense Id",
+            "Date",
+            "Category",
+            "Description",
+            "Amount",
+            "Currency",
+            "Mentee",
+            "Receipt",
+        ],
+        format_expenses(expenses),
+        OutputFormat::Table,
+        Color::Red,
+    )
+}
+
+pub fn display_call(call: CallWithMenteeName) {
+    println!("\nCall Details:");
+    println!("-----------------------");
+    println!("Call Id:          {}", call.id);
+    println!(
+        "Mentee:           {}",
+        capitalize_first_letter_of_each_word(&call.mentee_name)
+    );
+    println!(
+        "Date:             {}",
+        format_date(&call.date).unwrap_or_else(|_| call.date.clone())
+    );
+    println!(
+        "Free Call:        {}",
+        if call.free_call { "Yes" } else { "No" }
+    );
+    if let Some(duration) = call.duration {
+        println!("Duration:         {} min", duration);
+    }
+
+    println!("\nNotes:");
+    println!("-----------------------");
+    match call.notes.filter(|notes| !notes.trim().is_empty()) {
+        Some(notes) => println!("{}", render_markdown(&notes, NOTES_WRAP_WIDTH)),
+        None => println!("No notes"),
+    }
+    println!();
+}
+
+/// Formats rows as a GitHub flavoured Markdown table
+pub fn format_markdown_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let escape = |cell: &str| cell.replace('|', "\\|").replace('\n', " ");
+
+    let mut lines = vec![
+        format!("| {} |", headers.join(" | ")),
+        format!(
+            "|{}|",
+            headers.iter().map(|_| "---").collect::<Vec<_>>().join("|")
+        ),
+    ];
+
+    lines.extend(rows.iter().map(|row| {
+        format!(
+            "| {} |",
+            row.iter()
+                .map(|cell| escape(cell))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        )
+    }));
+
+    lines.join("\n")
+}
+
+pub fn format_csv(headers: &[&str], rows: &[Vec<String>]) -> Result<String, MenteeError> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    writer.write_record(headers)?;
+    for row in rows {
+        writer.write_record(row)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| MenteeError::SerializationError(err.to_string()))?;
+
+    String::from_utf8(bytes).map_err(|err| MenteeError::SerializationError(err.to_string()))
+}
+
+/// Prints rows as a terminal table, Markdown or CSV. JSON is left to the caller
+/// so the typed records can be serialised rather than their display strings.
+pub fn render_rows(
+    headers: &[&str],
+    rows: Vec<Vec<String>>,
+    format: OutputFormat,
+    color: Color,
+) -> Result<(), MenteeError> {
+    match format {
+        OutputFormat::Markdown => println!("{}", format_markdown_table(headers, &rows)),
+        OutputFormat::Csv => print!("{}", format_csv(headers, &rows)?),
+        OutputFormat::Table | OutputFormat::Json => {
+            let cell_rows: Vec<Vec<cli_table::CellStruct>> = rows
+                .into_iter()
+                .map(|row| {
+                    row.into_iter()
+                        .map(|cell| cell.cell().justify(Justify::Right))
+                        .collect()
+                })
+                .collect();
+
+            let table = cell_rows
+                .table()
+                .title(
+                    headers
+                        .iter()
+                        .map(|header| header.cell().bold(true))
+                        .collect::<Vec<_>>(),
+                )
+                .foreground_color(Some(color))
+                .bold(true);
+
+            println!("{}", table.display()?);
+        }
+    }
+
+    Ok(())
 }
 
-pub fn display_mentee(mentee: MenteeWithCounts) {
+/// Prints a heading between the tables of multi-table reports
+fn print_section_heading(title: &str, format: OutputFormat) {
+    match format {
+        OutputFormat::Markdown => println!("\n## {}\n", title),
+        OutputFormat::Csv => println!(),
+        OutputFormat::Table | OutputFormat::Json => println!("\n{}", title.bold()),
+    }
+}
+
+/// Months after the cohort month shown in the triangle, later months are cut off
+const MAX_RETENTION_MONTHS: usize = 12;
+
+pub fn format_cohorts(report: &RetentionReport) -> (Vec<String>, Vec<Vec<String>>) {
+    let months = report
+        .cohorts
+        .iter()
+        .map(|row| row.retained.len())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_RETENTION_MONTHS + 1);
+
+    let mut headers = vec!["Cohort".to_string(), "Mentees".to_string()];
+    headers.extend((0..months).map(|month| format!("M{}", month)));
+
+    let rows = report
+        .cohorts
+        .iter()
+        .map(|row| {
+            let mut cells = vec![row.cohort.clone(), row.size.to_string()];
+            cells.extend((0..months).map(|month| match row.retained.get(month) {
+                Some(count) if row.size > 0 => {
+                    format!("{} ({}%)", count, count * 100 / row.size)
+                }
+                _ => String::new(),
+            }));
+            cells
+        })
+        .collect();
+
+    (headers, rows)
+}
+
+/// Revenue in each currency, e.g. `GBP 100, USD 450`
+fn format_revenue(revenue: &BTreeMap<String, i64>) -> String {
+    if revenue.is_empty() {
+        return "-".to_string();
+    }
+
+    revenue
+        .iter()
+        .map(|(currency, amount)| format!("{} {}", currency, amount))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn render_sources(sources: Vec<SourceRow>, format: OutputFormat) -> Result<(), MenteeError> {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&sources)?);
+        return Ok(());
+    }
+
+    let rows = sources
+        .iter()
+        .map(|row| {
+            vec![
+                Source::from_str(&row.source).map_or_else(
+                    || capitalize_first_letter_of_each_word(&row.source),
+                    |source| source.label().to_string(),
+                ),
+                row.mentees.to_string(),
+                row.paying_mentees.to_string(),
+                format!("{:.0}%", row.conversion_rate()),
+                format_revenue(&row.revenue),
+                format!("{:.1}", row.average_tenure_months),
+            ]
+        })
+        .collect();
+
+    render_rows(
+        &[
+            "Source",
+            "Mentees",
+            "Paying",
+            "Conversion",
+            "Net Revenue",
+            "Average Tenure (Months)",
+        ],
+        rows,
+        format,
+        Color::Cyan,
+    )
+}
+
+/// Hourly rate in each currency, e.g. `USD 80.00/h`
+fn format_hourly_rate(hourly_rate: &BTreeMap<String, f64>) -> String {
+    if hourly_rate.is_empty() {
+        return "-".to_string();
+    }
+
+    hourly_rate
+        .iter()
+        .map(|(currency, rate)| format!("{} {:.2}/h", currency, rate))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_rate_row(row: &RateRow) -> Vec<String> {
+    vec![
+        capitalize_first_letter_of_each_word(&row.label),
+        format!("{:.1}", row.hours),
+        row.untimed_calls.to_string(),
+        format_revenue(&row.earned),
+        format_hourly_rate(&row.hourly_rate),
+    ]
+}
+
+pub fn render_rates(report: RatesReport, format: OutputFormat) -> Result<(), MenteeError> {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let mut mentee_rows: Vec<Vec<String>> = report.mentees.iter().map(format_rate_row).collect();
+    mentee_rows.push(format_rate_row(&report.overall));
+
+    print_section_heading("Hourly Rate per Mentee", format);
+    render_rows(
+        &[
+            "Mentee",
+            "Hours",
+            "Untimed Calls",
+            "Net Earned",
+            "Hourly Rate",
+        ],
+        mentee_rows,
+        format,
+        Color::Green,
+    )?;
+
+    print_section_heading("Hourly Rate per Month", format);
+    render_rows(
+        &[
+            "Month",
+            "Hours",
+            "Untimed Calls",
+            "Net Earned",
+            "Hourly Rate",
+        ],
+        report.months.iter().map(format_rate_row).collect(),
+        format,
+        Color::Blue,
+    )
+}
+
+fn format_turnaround_row(row: &TurnaroundRow) -> Vec<String> {
+    vec![
+        capitalize_first_letter_of_each_word(&row.mentee_name),
+        row.delivered.to_string(),
+        format!("{:.1}", row.average_days),
+        row.longest_days.to_string(),
+        row.on_time_rate()
+            .map_or("-".to_string(), |rate| format!("{:.0}%", rate)),
+        row.outstanding.to_string(),
+    ]
+}
+
+pub fn render_turnaround(
+    report: TurnaroundReport,
+    format: OutputFormat,
+) -> Result<(), MenteeError> {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let mut rows: Vec<Vec<String>> = report.mentees.iter().map(format_turnaround_row).collect();
+    rows.push(format_turnaround_row(&report.overall));
+
+    render_rows(
+        &[
+            "Mentee",
+            "Delivered",
+            "Average Days",
+            "Longest Days",
+            "On Time",
+            "Outstanding",
+        ],
+        rows,
+        format,
+        Color::Magenta,
+    )
+}
+
+/// What's left of a capacity, or how far over it is
+fn format_available(available: Option<f64>, decimals: usize) -> String {
+    match available {
+        Some(available) if available < 0.0 => format!("{:.*} over", decimals, -available),
+        Some(available) => format!("{:.*}", decimals, available),
+        None => "-".to_string(),
+    }
+}
+
+pub fn render_capacity(
+    months: Vec<CapacityMonth>,
+    format: OutputFormat,
+) -> Result<(), MenteeError> {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&months)?);
+        return Ok(());
+    }
+
+    let unconfigured = months
+        .first()
+        .is_some_and(|month| month.capacity_calls.is_none() && month.capacity_hours.is_none());
+
+    let rows = months
+        .iter()
+        .map(|month| {
+            vec![
+                month.month.clone(),
+                month.committed_calls.to_string(),
+                month.booked_calls.to_string(),
+                month
+                    .capacity_calls
+                    .map_or("-".to_string(), |capacity| capacity.to_string()),
+                format_available(month.available_calls().map(|calls| calls as f64), 0),
+                format!("{:.1}", month.committed_hours),
+                month
+                    .capacity_hours
+                    .map_or("-".to_string(), |capacity| capacity.to_string()),
+                format_available(month.available_hours(), 1),
+            ]
+        })
+        .collect();
+
+    render_rows(
+        &[
+            "Month",
+            "Committed Calls",
+            "Booked Calls",
+            "Call Capacity",
+            "Calls Available",
+            "Committed Hours",
+            "Hour Capacity",
+            "Hours Available",
+        ],
+        rows,
+        format,
+        Color::Yellow,
+    )?;
+
+    if unconfigured && format == OutputFormat::Table {
+        println!(
+            "Set calls_per_month or hours_per_month under [capacity] in ~/.mentees/config.toml"
+        );
+    }
+
+    Ok(())
+}
+
+pub fn format_agenda_items(items: &[AgendaItem]) -> Vec<Vec<String>> {
+    items
+        .iter()
+        .map(|item| {
+            vec![
+                item.date
+                    .as_deref()
+                    .map(|date| format_date(date).unwrap_or_else(|_| date.to_string()))
+                    .unwrap_or_default(),
+                capitalize_first_letter_of_each_word(item.kind.as_str()),
+                capitalize_first_letter_of_each_word(&item.mentee_name),
+                item.description.clone(),
+            ]
+        })
+        .collect()
+}
+
+pub fn render_agenda(agenda: Agenda, format: OutputFormat) -> Result<(), MenteeError> {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&agenda)?);
+        return Ok(());
+    }
+
+    let period = format!(
+        "{} to {}",
+        format_date(&agenda.date).unwrap_or_else(|_| agenda.date.clone()),
+        format_date(&agenda.until).unwrap_or_else(|_| agenda.until.clone())
+    );
+
+    match format {
+        OutputFormat::Markdown => println!("# Agenda\n\n{}", period),
+        OutputFormat::Table => println!("{}", format!("Agenda: {}", period).bold()),
+        OutputFormat::Csv | OutputFormat::Json => {}
+    }
+
+    if agenda.items.is_empty() {
+        if format != OutputFormat::Csv {
+            println!("\nNothing needs attention");
+        }
+        return Ok(());
+    }
+
+    for priority in [
+        AgendaPriority::Overdue,
+        AgendaPriority::Today,
+        AgendaPriority::ThisWeek,
+        AgendaPriority::Attention,
+    ] {
+        let items: Vec<AgendaItem> = agenda
+            .items
+            .iter()
+            .filter(|item| item.priority == priority)
+            .cloned()
+            .collect();
+
+        if items.is_empty() {
+            continue;
+        }
+
+        let color = match priority {
+            AgendaPriority::Overdue => Color::Red,
+            AgendaPriority::Today => Color::Green,
+            AgendaPriority::ThisWeek => Color::Blue,
+            AgendaPriority::Attention => Color::Yellow,
+        };
+
+        print_section_heading(priority.label(), format);
+        render_rows(
+            &["Date", "What", "Mentee", "Details"],
+            format_agenda_items(&items),
+            format,
+            color,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn render_retention(report: RetentionReport, format: OutputFormat) -> Result<(), MenteeError> {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let (headers, rows) = format_cohorts(&report);
+    let headers: Vec<&str> = headers.iter().map(String::as_str).collect();
+
+    print_section_heading("Cohort Retention", format);
+    render_rows(&headers, rows, format, Color::Green)?;
+
+    let churn_rows = report
+        .churn
+        .iter()
+        .map(|row| {
+            vec![
+                row.month.clone(),
+                row.active_at_start.to_string(),
+                row.churned.to_string(),
+                format!("{:.1}%", row.churn_rate()),
+            ]
+        })
+        .collect();
+
+    print_section_heading("Monthly Churn", format);
+    render_rows(
+        &["Month", "Active At Start", "Churned", "Churn"],
+        churn_rows,
+        format,
+        Color::Red,
+    )?;
+
+    print_section_heading("Summary", format);
+    render_rows(
+        &["Active Mentees", "Churned Mentees", "Average Tenure"],
+        vec![vec![
+            report.active_mentees.to_string(),
+            report.churned_mentees.to_string(),
+            format!("{:.1} months", report.average_tenure_months),
+        ]],
+        format,
+        Color::Blue,
+    )
+}
+
+fn format_statement_period(from: Option<&str>, to: Option<&str>) -> String {
+    let display = |date: &str| format_date(date).unwrap_or_else(|_| date.to_string());
+
+    match (from, to) {
+        (Some(from), Some(to)) => format!("{} to {}", display(from), display(to)),
+        (Some(from), None) => format!("From {}", display(from)),
+        (None, Some(to)) => format!("Up to {}", display(to)),
+        (None, None) => "All time".to_string(),
+    }
+}
+
+/// Ledger rows bracketed by the opening and closing balance
+pub fn format_statement(statement: &Statement) -> Vec<Vec<String>> {
+    let balance_row = |description: &str, balance: i64| {
+        vec![
+            String::new(),
+            description.to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+            balance.to_string(),
+        ]
+    };
+    let count = |calls: u32| match calls {
+        0 => String::new(),
+        calls => calls.to_string(),
+    };
+
+    let mut rows = vec![balance_row("Opening balance", statement.opening_balance)];
+
+    rows.extend(statement.entries.iter().map(|entry| {
+        vec![
+            format_date(&entry.date).unwrap_or_else(|_| entry.date.clone()),
+            entry.description.clone(),
+            entry
+                .amount
+                .map(|amount| format!("${}", amount))
+                .unwrap_or_default(),
+            count(entry.credit),
+            count(entry.debit),
+            entry.balance.to_string(),
+        ]
+    }));
+
+    rows.push(balance_row("Closing balance", statement.closing_balance));
+    rows
+}
+
+pub fn render_statement(statement: Statement, format: OutputFormat) -> Result<(), MenteeError> {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&statement)?);
+        return Ok(());
+    }
+
+    let name = capitalize_first_letter_of_each_word(&statement.mentee_name);
+    let period = format_statement_period(statement.from.as_deref(), statement.to.as_deref());
+
+    match format {
+        OutputFormat::Markdown => println!("# Statement for {}\n\n{}\n", name, period),
+        OutputFormat::Table => println!("{}\n{}", format!("Statement for {}", name).bold(), period),
+        OutputFormat::Csv | OutputFormat::Json => {}
+    }
+
+    render_rows(
+        &[
+            "Date",
+            "Description",
+            "Amount",
+            "Calls In",
+            "Calls Out",
+            "Balance",
+        ],
+        format_statement(&statement),
+        format,
+        Color::Blue,
+    )
+}
+
+/// Income rows, with expense and profit columns unless it's the per source breakdown
+fn format_income_rows(rows: &[IncomeRow], with_expenses: bool) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| {
+            let mut cells = vec![
+                row.label.clone(),
+                row.currency.clone(),
+                row.payments.to_string(),
+                row.gross.to_string(),
+                row.fees.to_string(),
+                row.net.to_string(),
+            ];
+            if with_expenses {
+                cells.push(row.expenses.to_string());
+                cells.push(row.profit.to_string());
+            }
+            cells
+        })
+        .collect()
+}
+
+pub fn render_tax_report(report: TaxReport, format: OutputFormat) -> Result<(), MenteeError> {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let period = format!(
+        "Fiscal year {}: {} to {}",
+        report.year,
+        format_date(&report.start).unwrap_or_else(|_| report.start.clone()),
+        format_date(&report.end).unwrap_or_else(|_| report.end.clone())
+    );
+
+    match format {
+        OutputFormat::Markdown => println!("# Income Summary\n\n{}", period),
+        OutputFormat::Table => println!("{}", period.bold()),
+        OutputFormat::Csv | OutputFormat::Json => {}
+    }
+
+    let income = ["Currency", "Payments", "Gross", "Fees", "Net"];
+    let profit = ["Expenses", "Profit"];
+
+    print_section_heading("By Month", format);
+    render_rows(
+        &[&["Month"], &income[..], &profit[..]].concat(),
+        format_income_rows(&report.months, true),
+        format,
+        Color::Green,
+    )?;
+
+    print_section_heading("By Source", format);
+    render_rows(
+        &[&["Source"], &income[..]].concat(),
+        format_income_rows(&report.sources, false),
+        format,
+        Color::Blue,
+    )?;
+
+    print_section_heading("Expenses By Category", format);
+    render_rows(
+        &["Category", "Currency", "Expenses", "Amount"],
+        report
+            .expense_categories
+            .iter()
+            .map(|row| {
+                vec![
+                    capitalize_first_letter_of_each_word(&row.category),
+                    row.currency.clone(),
+                    row.count.to_string(),
+                    row.amount.to_string(),
+                ]
+            })
+            .collect(),
+        format,
+        Color::Red,
+    )?;
+
+    print_section_heading("Totals", format);
+    render_rows(
+        &[&[""], &income[..], &profit[..]].concat(),
+        format_income_rows(&report.totals, true),
+        format,
+        Color::Yellow,
+    )
+}
+
+pub fn format_timeline(events: &[TimelineEvent]) -> Vec<Vec<String>> {
+    events
+        .iter()
+        .map(|event| {
+            let change = match event.balance_change {
+                0 => String::new(),
+                change if change > 0 => format!("+{}", change),
+                change => change.to_string(),
+            };
+
+            vec![
+                format_date(&event.date).unwrap_or_else(|_| event.date.clone()),
+                capitalize_first_letter_of_each_word(event.kind.as_str()),
+                event.description.clone(),
+                change,
+                event.balance.to_string(),
+            ]
+        })
+        .collect()
+}
+
+pub fn render_timeline(
+    events: Vec<TimelineEvent>,
+    format: OutputFormat,
+) -> Result<(), MenteeError> {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&events)?);
+        return Ok(());
+    }
+
+    render_rows(
+        &["Date", "Event", "Details", "Calls", "Balance"],
+        format_timeline(&events),
+        format,
+        Color::Blue,
+    )
+}
+
+pub fn render_contacts_table(contacts: Vec<Contact>) -> Result<(), MenteeError> {
+    let cell_rows: Vec<Vec<cli_table::CellStruct>> = contacts
+        .into_iter()
+        .map(|contact| {
+            vec![
+                contact.id.to_string().cell().justify(Justify::Right),
+                contact.kind.label().cell().justify(Justify::Right),
+                contact.value.cell().justify(Justify::Right),
+            ]
+        })
+        .collect();
+
+    let table = cell_rows
+        .table()
+        .title(vec![
+            "Contact Id".cell().bold(true),
+            "Kind".cell().bold(true),
+            "Value".cell().bold(true),
+        ])
+        .foreground_color(Some(Color::Cyan))
+        .bold(true);
+
+    let table_display = table.display()?;
+
+    println!("{}", table_display);
+    Ok(())
+}
+
+pub fn format_goals(goals: Vec<GoalWithProgress>) -> Vec<Vec<String>> {
+    goals
+        .into_iter()
+        .map(|goal| {
+            let target_date = goal
+                .target_date
+                .as_deref()
+                .map(|date| format_date(date).unwrap_or_else(|_| date.to_string()))
+                .unwrap_or_default();
+
+            vec![
+                goal.id.to_string(),
+                capitalize_first_letter_of_each_word(&goal.mentee_name),
+                goal.title.clone(),
+                target_date,
+                format!("{}/{}", goal.milestones_done, goal.milestones_total),
+                format!("{}%", goal.completion_percentage()),
+                goal.linked_calls.to_string(),
+                capitalize_first_letter_of_each_word(goal.status.as_str()),
+            ]
+        })
+        .collect()
+}
+
+pub fn render_goals_table(goals: Vec<GoalWithProgress>) -> Result<(), MenteeError> {
+    let rows = format_goals(goals);
+
+    let cell_rows: Vec<Vec<cli_table::CellStruct>> = rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| cell.cell().justify(Justify::Right))
+                .collect()
+        })
+        .collect();
+
+    let table = cell_rows
+        .table()
+        .title(vec![
+            "Goal Id".cell().bold(true),
+            "Mentee".cell().bold(true),
+            "Goal".cell().bold(true),
+            "Target Date".cell().bold(true),
+            "Milestones".cell().bold(true),
+            "Complete".cell().bold(true),
+            "Calls".cell().bold(true),
+            "Status".cell().bold(true),
+        ])
+        .foreground_color(Some(Color::Cyan))
+        .bold(true);
+
+    let table_display = table.display()?;
+
+    println!("{}", table_display);
+    Ok(())
+}
+
+pub fn display_goal(details: GoalDetails) {
+    let goal = details.goal;
+
+    println!("\nGoal Details:");
+    println!("-----------------------");
+    println!("Goal:             {}", goal.title);
+    println!(
+        "Mentee:           {}",
+        capitalize_first_letter_of_each_word(&goal.mentee_name)
+    );
+    println!(
+        "Status:           {}",
+        capitalize_first_letter_of_each_word(goal.status.as_str())
+    );
+    if let Some(date) = goal.target_date.as_deref() {
+        println!(
+            "Target Date:      {}",
+            format_date(date).unwrap_or_else(|_| date.to_string())
+        );
+    }
+    println!("Complete:         {}%", goal.completion_percentage());
+
+    println!("\nMilestones:");
+    println!("-----------------------");
+    if details.milestones.is_empty() {
+        println!("None yet, add one with `goals milestone add {}`", goal.id);
+    }
+    for milestone in details.milestones {
+        let check = if milestone.done {
+            "[x]".green()
+        } else {
+            "[ ]".normal()
+        };
+        println!("{} {} (#{})", check, milestone.title, milestone.id);
+    }
+
+    println!("\nCalls That Advanced It:");
+    println!("-----------------------");
+    if details.linked_calls.is_empty() {
+        println!("None linked");
+    }
+    for call in details.linked_calls {
+        println!(
+            "{} (#{})",
+            format_date(&call.date).unwrap_or_else(|_| call.date.clone()),
+            call.call_id
+        );
+    }
+    println!();
+}
+
+/// Joins every contact value of the given kind, used for the CSV export columns
+fn join_contacts(contacts: &[Contact], kind: ContactKind) -> String {
+    contacts
+        .iter()
+        .filter(|contact| contact.kind == kind)
+        .map(|contact| contact.value.as_str())
+        .collect::<Vec<&str>>()
+        .join("; ")
+}
+
+pub fn format_export_csv(records: &[MenteeWithContacts]) -> Result<String, MenteeError> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    let mut header = vec![
+        "name",
+        "calls",
+        "gross",
+        "net",
+        "status",
+        "payment_day",
+        "notes",
+        "source",
+        "referrer",
+    ];
+    header.extend(ContactKind::variants().iter().map(|kind| kind.as_str()));
+    writer.write_record(&header)?;
+
+    for record in records {
+        let mentee = &record.mentee;
+        let mut row = vec![
+            mentee.name.clone(),
+            mentee.calls.to_string(),
+            mentee.gross.to_string(),
+            mentee.net.to_string(),
+            mentee.status.as_str().to_string(),
+            mentee.payment_day.to_string(),
+            mentee.notes.clone().unwrap_or_default(),
+            mentee
+                .source
+                .map(|source| source.as_str().to_string())
+                .unwrap_or_default(),
+            mentee.referrer.clone().unwrap_or_default(),
+        ];
+        row.extend(
+            ContactKind::variants()
+                .into_iter()
+                .map(|kind| join_contacts(&record.contacts, kind)),
+        );
+        writer.write_record(&row)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| MenteeError::SerializationError(err.to_string()))?;
+
+    String::from_utf8(bytes).map_err(|err| MenteeError::SerializationError(err.to_string()))
+}
+
+pub fn render_export(
+    records: Vec<MenteeWithContacts>,
+    format: ExportFormat,
+) -> Result<(), MenteeError> {
+    let output = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&records)?,
+        ExportFormat::Csv => format_export_csv(&records)?,
+    };
+
+    println!("{}", output.trim_end());
+    Ok(())
+}
+
+pub fn format_invoice_amount(amount: u32, currency: &str) -> String {
+    format!("{}.00 {}", amount, currency)
+}
+
+pub fn format_invoices(invoices: Vec<Invoice>, prefix: &str) -> Vec<Vec<String>> {
+    invoices
+        .into_iter()
+        .map(|invoice| {
+            vec![
+                invoice.display_number(prefix),
+                capitalize_first_letter_of_each_word(&invoice.mentee_name),
+                format_date(&invoice.issue_date).unwrap_or_else(|_| invoice.issue_date.clone()),
+                format_date(&invoice.payment_date).unwrap_or_else(|_| invoice.payment_date.clone()),
+                format_invoice_amount(invoice.amount, &invoice.currency),
+                invoice
+                    .payment_id
+                    .map_or_else(|| "Deleted".to_string(), |id| id.to_string()),
+            ]
+        })
+        .collect()
+}
+
+pub fn render_invoices_table(invoices: Vec<Invoice>, prefix: &str) -> Result<(), MenteeError> {
+    render_rows(
+        &[
+            "Invoice",
+            "Mentee",
+            "Issued",
+            "Paid",
+            "Amount",
+            "Payment Id",
+        ],
+        format_invoices(invoices, prefix),
+        OutputFormat::Table,
+        Color::Green,
+    )
+}
+
+/// Fills an invoice template. Values are escaped for HTML templates and the
+/// issuer address keeps its line breaks in both formats.
+pub fn format_invoice_document(
+    invoice: &Invoice,
+    template: &str,
+    prefix: &str,
+    format: InvoiceFormat,
+) -> String {
+    let escape = |value: &str| match format {
+        InvoiceFormat::Html => escape_html(value),
+        InvoiceFormat::Text => value.to_string(),
+    };
+    let line_break = match format {
+        InvoiceFormat::Html => "<br>\n",
+        InvoiceFormat::Text => "\n",
+    };
+
+    let address = invoice
+        .issuer_address
+        .lines()
+        .map(|line| escape(line.trim()))
+        .collect::<Vec<_>>()
+        .join(line_break);
+    let tax_id = invoice
+        .issuer_tax_id
+        .as_deref()
+        .map(|tax_id| escape(&format!("Tax ID: {}", tax_id)))
+        .unwrap_or_default();
+    let month = parse_date_from_db(&invoice.payment_date)
+        .map(|date| date.format("%B %Y").to_string())
+        .unwrap_or_else(|_| invoice.payment_date.clone());
+    let display_date = |date: &str| format_date(date).unwrap_or_else(|_| date.to_string());
+
+    let values = [
+        ("number", escape(&invoice.display_number(prefix))),
+        ("issuer_name", escape(&invoice.issuer_name)),
+        ("issuer_address", address),
+        ("issuer_email", escape(&invoice.issuer_email)),
+        ("issuer_tax_id", tax_id),
+        (
+            "mentee_name",
+            escape(&capitalize_first_letter_of_each_word(&invoice.mentee_name)),
+        ),
+        ("issue_date", display_date(&invoice.issue_date)),
+        ("payment_date", display_date(&invoice.payment_date)),
+        ("description", escape(&format!("Mentoring, {}", month))),
+        (
+            "amount",
+            escape(&format_invoice_amount(invoice.amount, &invoice.currency)),
+        ),
+        ("currency", escape(&invoice.currency)),
+    ];
+
+    render_template(template, &values)
+}
+
+/// Prints the invoice, or writes it to `out` when given
+pub fn render_invoice(
+    invoice: Invoice,
+    template: &str,
+    prefix: &str,
+    format: InvoiceFormat,
+    out: Option<PathBuf>,
+) -> Result<(), MenteeError> {
+    let document = format_invoice_document(&invoice, template, prefix, format);
+
+    match out {
+        Some(path) => {
+            std::fs::write(&path, document)?;
+            println!(
+                "{}",
+                format!(
+                    "Wrote invoice {} to {}",
+                    invoice.display_number(prefix),
+                    path.display()
+                )
+                .green()
+            );
+        }
+        None => println!("{}", document.trim_end()),
+    }
+
+    Ok(())
+}
+
+fn format_status_change(change: &StatusChange) -> String {
+    let date = format_date(&change.date).unwrap_or_else(|_| change.date.clone());
+    let to_status = capitalize_first_letter_of_each_word(change.to_status.as_str());
+    let transition = match &change.from_status {
+        Some(from_status) => format!(
+            "{} -> {}",
+            capitalize_first_letter_of_each_word(from_status.as_str()),
+            to_status
+        ),
+        None => format!("Added as {}", to_status),
+    };
+
+    match change.reason.as_deref().filter(|reason| !reason.is_empty()) {
+        Some(reason) => format!("{:<18}{} ({})", date, transition, reason),
+        None => format!("{:<18}{}", date, transition),
+    }
+}
+
+pub fn display_mentee(
+    mentee: MenteeWithCounts,
+    contacts: Vec<Contact>,
+    open_goals: Vec<GoalWithProgress>,
+    status_history: Vec<StatusChange>,
+    rate: RateRow,
+) {
     println!("\nMentee Details:");
     println!("-----------------------");
     println!(
@@ -263,6 +1616,16 @@ pub fn display_mentee(mentee: MenteeWithCounts) {
         "Payment Day:      {}",
         add_ordinal_suffix(mentee.mentee.payment_day)
     );
+    if let Some(source) = mentee.mentee.source {
+        match mentee.mentee.referrer.as_deref() {
+            Some(referrer) => println!(
+                "Source:           {} by {}",
+                source.label(),
+                capitalize_first_letter_of_each_word(referrer)
+            ),
+            None => println!("Source:           {}", source.label()),
+        }
+    }
 
     println!("\nPayment Details:");
     println!("-----------------------");
@@ -272,6 +1635,11 @@ pub fn display_mentee(mentee: MenteeWithCounts) {
     let net_per_call = calc_net_per_call(&mentee.mentee.net, &mentee.mentee.calls);
     println!("Net / Call:       ${:.2}", net_per_call);
     println!("Total Payments:   {}", mentee.payment_count);
+    println!("Hours Spent:      {:.1}", rate.hours);
+    println!(
+        "Hourly Rate:      {}",
+        format_hourly_rate(&rate.hourly_rate)
+    );
 
     println!("\nCall Details:");
     println!("-----------------------");
@@ -286,16 +1654,63 @@ pub fn display_mentee(mentee: MenteeWithCounts) {
     };
     println!("Remaining Calls:  {}", remaining_calls_colored);
 
-    println!(
-        "Notes:            {}",
-        mentee.mentee.notes.unwrap_or("".to_string())
-    );
+    if let Some(notes) = mentee.mentee.notes.filter(|notes| !notes.trim().is_empty()) {
+        println!("\nNotes:");
+        println!("-----------------------");
+        println!("{}", render_markdown(&notes, NOTES_WRAP_WIDTH));
+    }
+
+    if !open_goals.is_empty() {
+        println!("\nOpen Goals:");
+        println!("-----------------------");
+        for goal in open_goals {
+            let target_date = goal
+                .target_date
+                .as_deref()
+                .map(|date| {
+                    format!(
+                        ", due {}",
+                        format_date(date).unwrap_or_else(|_| date.to_string())
+                    )
+                })
+                .unwrap_or_default();
+            println!(
+                "{:>4}%  {} (#{}{})",
+                goal.completion_percentage(),
+                goal.title,
+                goal.id,
+                target_date
+            );
+        }
+    }
+
+    if !status_history.is_empty() {
+        println!("\nStatus History:");
+        println!("-----------------------");
+        for change in status_history {
+            println!("{}", format_status_change(&change));
+        }
+    }
+
+    if !contacts.is_empty() {
+        println!("\nContact Details:");
+        println!("-----------------------");
+        for contact in contacts {
+            println!(
+                "{:<18}{} (#{})",
+                format!("{}:", contact.kind.label()),
+                contact.value,
+                contact.id
+            );
+        }
+    }
     println!();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::statement::StatementEntry;
 
     #[test]
     fn test_format_mentees() {
@@ -305,6 +1720,11 @@ mod tests {
             remaining_calls: 0,
             status: Status::Warm,
             notes: Some("CET timezone".to_string()),
+            last_call_date: Some("2026-03-01".to_string()),
+            days_since_last_call: Some(10),
+            avg_days_between_calls: Some(14.25),
+            calls_last_30_days: 1,
+            calls_last_90_days: 5,
         }];
 
         let rows = format_mentees(mentees);
@@ -313,10 +1733,155 @@ mod tests {
         assert_eq!(rows[0][0], "John Doe");
         assert_eq!(rows[0][1], "2");
         assert_eq!(rows[0][2], "0");
+        assert_eq!(rows[0][3], "01 Mar 2026 (10d ago)");
+        assert_eq!(rows[0][4], "14.2d");
         assert_eq!(rows[0][5], "Warm");
+        assert_eq!(rows[0][6], "1 / 5");
         assert_eq!(rows[0][7], "CET timezone");
     }
 
+    #[test]
+    fn export_csv_has_a_column_per_contact_kind() {
+        let records = vec![MenteeWithContacts {
+            mentee: crate::models::mentee::Mentee {
+                id: 1,
+                name: "john doe".to_string(),
+                calls: 2,
+                gross: 200,
+                net: 160,
+                status: Status::Hot,
+                payment_day: 5,
+                notes: Some("likes, commas".to_string()),
+                source: Some(Source::Referral),
+                referrer: Some("amy".to_string()),
+            },
+            contacts: vec![
+                Contact {
+                    id: 1,
+                    mentee_id: 1,
+                    kind: ContactKind::Email,
+                    value: "john@example.com".to_string(),
+                },
+                Contact {
+                    id: 2,
+                    mentee_id: 1,
+                    kind: ContactKind::Email,
+                    value: "jd@work.com".to_string(),
+                },
+            ],
+        }];
+
+        let csv = format_export_csv(&records).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "name,calls,gross,net,status,payment_day,notes,source,referrer,email,phone,slack,discord,linkedin,github,website"
+        );
+        assert_eq!(
+            lines[1],
+            "john doe,2,200,160,hot,5,\"likes, commas\",referral,amy,john@example.com; jd@work.com,,,,,,"
+        );
+    }
+
+    #[test]
+    fn preview_uses_first_line_of_notes() {
+        assert_eq!(notes_preview("## Agenda\n- CV review"), "Agenda …");
+        assert_eq!(notes_preview("CET timezone"), "CET timezone");
+        assert_eq!(notes_preview(""), "");
+        assert_eq!(
+            notes_preview("Talked through the system design interview in a lot of detail"),
+            "Talked through the system design interv…"
+        );
+    }
+
+    #[test]
+    fn markdown_wraps_paragraphs_and_list_items() {
+        let notes = "Discussed the portfolio site and next steps\n\n- Rewrite the hero section copy\n12. Ship it";
+        let rendered = render_markdown(notes, 24);
+
+        assert_eq!(
+            rendered,
+            "Discussed the portfolio\nsite and next steps\n\n• Rewrite the hero\n  section copy\n12. Ship it"
+        );
+    }
+
+    #[test]
+    fn markdown_keeps_code_blocks_unwrapped() {
+        let notes = "```\nlet answer = compute_the_answer_to_everything();\n```";
+        let rendered = render_markdown(notes, 10);
+
+        assert_eq!(
+            rendered,
+            "    let answer = compute_the_answer_to_everything();"
+        );
+    }
+
+    #[test]
+    fn markdown_table_escapes_pipes() {
+        let table = format_markdown_table(
+            &["Date", "Notes"],
+            &[vec!["01 Jan 2026".to_string(), "a | b".to_string()]],
+        );
+
+        assert_eq!(
+            table,
+            "| Date | Notes |\n|---|---|\n| 01 Jan 2026 | a \\| b |"
+        );
+    }
+
+    #[test]
+    fn status_change_shows_transition_and_reason() {
+        let mut change = StatusChange {
+            id: 1,
+            mentee_id: 1,
+            date: "2026-03-14".to_string(),
+            from_status: Some(Status::Hot),
+            to_status: Status::Cold,
+            reason: Some("Paused for exams".to_string()),
+        };
+
+        assert_eq!(
+            format_status_change(&change),
+            "14 Mar 2026       Hot -> Cold (Paused for exams)"
+        );
+
+        change.from_status = None;
+        change.reason = None;
+        assert_eq!(
+            format_status_change(&change),
+            "14 Mar 2026       Added as Cold"
+        );
+    }
+
+    #[test]
+    fn cohort_cells_show_count_and_percentage() {
+        let report = RetentionReport {
+            cohorts: vec![
+                crate::models::report::CohortRow {
+                    cohort: "2026-01".to_string(),
+                    size: 4,
+                    retained: vec![4, 3],
+                },
+                crate::models::report::CohortRow {
+                    cohort: "2026-02".to_string(),
+                    size: 1,
+                    retained: vec![1],
+                },
+            ],
+            churn: vec![],
+            average_tenure_months: 0.0,
+            active_mentees: 0,
+            churned_mentees: 0,
+        };
+
+        let (headers, rows) = format_cohorts(&report);
+
+        assert_eq!(headers, vec!["Cohort", "Mentees", "M0", "M1"]);
+        assert_eq!(rows[0], vec!["2026-01", "4", "4 (100%)", "3 (75%)"]);
+        assert_eq!(rows[1], vec!["2026-02", "1", "1 (100%)", ""]);
+    }
+
     #[test]
     fn net_for_zero_calls() {
         let net_amount = 200;
@@ -361,4 +1926,105 @@ mod tests {
         let result = capitalize_first_letter_of_each_word("dan page");
         assert_eq!(result, "Dan Page")
     }
+
+    fn sample_invoice() -> Invoice {
+        Invoice {
+            id: 1,
+            number: 12,
+            payment_id: Some(4),
+            mentee_name: "dan page".to_string(),
+            issue_date: "2026-03-02".to_string(),
+            payment_date: "2026-03-01".to_string(),
+            amount: 400,
+            currency: "USD".to_string(),
+            issuer_name: "Tom & Co".to_string(),
+            issuer_address: "1 High Street\nLondon".to_string(),
+            issuer_email: "tom@example.com".to_string(),
+            issuer_tax_id: None,
+        }
+    }
+
+    #[test]
+    fn test_format_invoice_document_text() {
+        let document = format_invoice_document(
+            &sample_invoice(),
+            "{{number}} {{mentee_name}}\n{{issuer_address}}\n{{description}}: {{amount}}",
+            "INV-",
+            InvoiceFormat::Text,
+        );
+
+        assert_eq!(
+            document,
+            "INV-0012 Dan Page\n1 High Street\nLondon\nMentoring, March 2026: 400.00 USD"
+        );
+    }
+
+    #[test]
+    fn test_format_invoice_document_html_escapes_values() {
+        let document = format_invoice_document(
+            &sample_invoice(),
+            "{{issuer_name}}|{{issuer_address}}|{{issuer_tax_id}}",
+            "INV-",
+            InvoiceFormat::Html,
+        );
+
+        assert_eq!(document, "Tom &amp; Co|1 High Street<br>\nLondon|");
+    }
+
+    #[test]
+    fn test_format_statement_brackets_entries_with_balances() {
+        let statement = Statement {
+            mentee_name: "dan".to_string(),
+            from: Some("2026-02-01".to_string()),
+            to: None,
+            opening_balance: 1,
+            entries: vec![
+                StatementEntry {
+                    date: "2026-02-01".to_string(),
+                    description: "Payment".to_string(),
+                    amount: Some(200),
+                    credit: 2,
+                    debit: 0,
+                    balance: 3,
+                },
+                StatementEntry {
+                    date: "2026-02-03".to_string(),
+                    description: "Call".to_string(),
+                    amount: None,
+                    credit: 0,
+                    debit: 1,
+                    balance: 2,
+                },
+            ],
+            closing_balance: 2,
+            total_paid: 200,
+            calls_credited: 2,
+            calls_used: 1,
+        };
+
+        let rows = format_statement(&statement);
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], vec!["", "Opening balance", "", "", "", "1"]);
+        assert_eq!(
+            rows[1],
+            vec!["01 Feb 2026", "Payment", "$200", "2", "", "3"]
+        );
+        assert_eq!(rows[2], vec!["03 Feb 2026", "Call", "", "", "1", "2"]);
+        assert_eq!(rows[3], vec!["", "Closing balance", "", "", "", "2"]);
+        assert_eq!(
+            format_statement_period(statement.from.as_deref(), statement.to.as_deref()),
+            "From 01 Feb 2026"
+        );
+    }
+
+    #[test]
+    fn test_format_due_in() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+
+        assert_eq!(format_due_in("2026-03-10", today), "today");
+        assert_eq!(format_due_in("2026-03-11", today), "in 1 day");
+        assert_eq!(format_due_in("2026-03-13", today), "in 3 days");
+        assert_eq!(format_due_in("2026-03-08", today), "2 days overdue");
+    }
 }
@@ -0,0 +1,173 @@
+use std::io::Write;
+
+use clap::ValueEnum;
+use clap_complete::{
+    env::{Bash, EnvCompleter, Fish, Zsh},
+    CompletionCandidate,
+};
+
+use crate::{
+    config::Config,
+    error::MenteeError,
+    services::{ExpenseService, GoalService},
+    Store,
+};
+
+/// Environment variable the shell sets when it asks the binary for completions
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+const BIN: &str = "mentees";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Writes the script that registers completions with the shell. The script
+/// calls back into `mentees` on every tab, so names and ids are always current.
+pub fn write_registration(shell: CompletionShell, out: &mut dyn Write) -> Result<(), MenteeError> {
+    let completer: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+    };
+
+    completer.write_registration(COMPLETE_VAR, BIN, BIN, BIN, out)?;
+
+    Ok(())
+}
+
+/// The database the CLI uses, or nothing when it hasn't been created yet.
+/// Completing should never create one.
+fn open_store() -> Option<Store> {
+    let config = Config::new().ok()?;
+
+    if config.db_path.exists() {
+        Store::open(&config.db_path).ok()
+    } else {
+        None
+    }
+}
+
+/// Bash inserts candidates as they are, so spaces in names need escaping there.
+/// Zsh and fish escape candidates themselves.
+fn escape_for_shell(value: &str) -> String {
+    if std::env::var_os("_CLAP_COMPLETE_COMP_TYPE").is_some() {
+        value.replace(' ', "\\ ")
+    } else {
+        value.to_string()
+    }
+}
+
+fn candidate(value: impl ToString, help: String) -> CompletionCandidate {
+    CompletionCandidate::new(value.to_string()).help(Some(help.into()))
+}
+
+pub fn mentee_names() -> Vec<CompletionCandidate> {
+    let Some(store) = open_store() else {
+        return Vec::new();
+    };
+
+    let mentees = store.mentees().get_mentees(true).unwrap_or_default();
+
+    mentees
+        .into_iter()
+        .map(|mentee| {
+            candidate(
+                escape_for_shell(&mentee.name),
+                mentee.status.as_str().to_string(),
+            )
+        })
+        .collect()
+}
+
+pub fn call_ids() -> Vec<CompletionCandidate> {
+    let Some(store) = open_store() else {
+        return Vec::new();
+    };
+
+    let calls = store.calls().get_all_calls(None).unwrap_or_default();
+
+    calls
+        .into_iter()
+        .map(|call| candidate(call.id, format!("{} on {}", call.mentee_name, call.date)))
+        .collect()
+}
+
+pub fn video_ids() -> Vec<CompletionCandidate> {
+    let Some(store) = open_store() else {
+        return Vec::new();
+    };
+
+    let videos = store.videos().get_all_videos(None).unwrap_or_default();
+
+    videos
+        .into_iter()
+        .map(|video| {
+            candidate(
+                video.id,
+                format!(
+                    "{} on {}, {}",
+                    video.mentee_name,
+                    video.date,
+                    video.status.label()
+                ),
+            )
+        })
+        .collect()
+}
+
+pub fn payment_ids() -> Vec<CompletionCandidate> {
+    let Some(store) = open_store() else {
+        return Vec::new();
+    };
+
+    let payments = store.payments().get_all_payments(None).unwrap_or_default();
+
+    payments
+        .into_iter()
+        .map(|payment| {
+            candidate(
+                payment.id,
+                format!("{} on {}", payment.mentee_name, payment.date),
+            )
+        })
+        .collect()
+}
+
+pub fn goal_ids() -> Vec<CompletionCandidate> {
+    let Some(store) = open_store() else {
+        return Vec::new();
+    };
+
+    let goals = GoalService::new(store.connection())
+        .get_goals(None, true)
+        .unwrap_or_default();
+
+    goals
+        .into_iter()
+        .map(|goal| candidate(goal.id, format!("{}: {}", goal.mentee_name, goal.title)))
+        .collect()
+}
+
+pub fn expense_ids() -> Vec<CompletionCandidate> {
+    let Some(store) = open_store() else {
+        return Vec::new();
+    };
+
+    let expenses = ExpenseService::new(store.connection(), store.config())
+        .get_all_expenses(None)
+        .unwrap_or_default();
+
+    expenses
+        .into_iter()
+        .map(|expense| {
+            candidate(
+                expense.id,
+                format!("{} on {}", expense.description, expense.date),
+            )
+        })
+        .collect()
+}
@@ -1,11 +1,222 @@
 use std::path::PathBuf;
 
+use chrono::NaiveDate;
 use dirs::home_dir;
+use serde::Deserialize;
 
 use crate::error::MenteeError;
 
+const CONFIG_FILE: &str = "config.toml";
+
+/// Your details as they appear on invoices, read from the `[issuer]` section.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct IssuerConfig {
+    pub name: String,
+    pub address: String,
+    pub email: String,
+    pub tax_id: Option<String>,
+}
+
+/// Read from the `[payments]` section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PaymentsConfig {
+    /// Suggested for new payments and assumed for payments recorded without one
+    pub currency: String,
+}
+
+impl Default for PaymentsConfig {
+    fn default() -> Self {
+        Self {
+            currency: "USD".to_string(),
+        }
+    }
+}
+
+/// Read from the `[tax]` section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TaxConfig {
+    /// First day of the fiscal year as `MM-DD`, e.g. `04-06` for the UK
+    pub year_start: String,
+}
+
+impl Default for TaxConfig {
+    fn default() -> Self {
+        Self {
+            year_start: "01-01".to_string(),
+        }
+    }
+}
+
+impl TaxConfig {
+    /// The month and day the fiscal year starts on
+    pub fn year_start(&self) -> Result<(u32, u32), MenteeError> {
+        let invalid = || {
+            MenteeError::ConfigError(format!(
+                "tax year_start '{}' is not a date in the format MM-DD",
+                self.year_start
+            ))
+        };
+
+        let (month, day) = self.year_start.split_once('-').ok_or_else(invalid)?;
+        let month: u32 = month.parse().map_err(|_| invalid())?;
+        let day: u32 = day.parse().map_err(|_| invalid())?;
+
+        // Checked against a non-leap year so the start date exists every year
+        NaiveDate::from_ymd_opt(2001, month, day).ok_or_else(invalid)?;
+
+        Ok((month, day))
+    }
+}
+
+/// Read from the `[calls]` section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CallsConfig {
+    /// Minutes counted for calls logged without a duration
+    pub default_minutes: u32,
+}
+
+impl Default for CallsConfig {
+    fn default() -> Self {
+        Self {
+            default_minutes: 60,
+        }
+    }
+}
+
+/// Read from the `[capacity]` section. A limit that's left out isn't checked.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CapacityConfig {
+    pub calls_per_month: Option<u32>,
+    pub hours_per_month: Option<u32>,
+}
+
+impl CapacityConfig {
+    /// Why committing to `calls` a month, each taking `minutes_per_call`, is
+    /// more than can be handled, or `None` when it fits
+    pub fn exceeded_by(&self, calls: u32, minutes_per_call: u32) -> Option<String> {
+        let mut reasons = Vec::new();
+
+        if let Some(capacity) = self.calls_per_month.filter(|capacity| calls > *capacity) {
+            reasons.push(format!(
+                "{} calls a month is more than the capacity of {}",
+                calls, capacity
+            ));
+        }
+
+        let hours = calls as f64 * minutes_per_call as f64 / 60.0;
+        if let Some(capacity) = self
+            .hours_per_month
+            .filter(|capacity| hours > *capacity as f64)
+        {
+            reasons.push(format!(
+                "{:.1} hours a month is more than the capacity of {}",
+                hours, capacity
+            ));
+        }
+
+        if reasons.is_empty() {
+            None
+        } else {
+            Some(reasons.join(" and "))
+        }
+    }
+}
+
+/// Read from the `[videos]` section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct VideosConfig {
+    /// Days after a video is received that its review is suggested to be due
+    pub due_in_days: u32,
+}
+
+impl Default for VideosConfig {
+    fn default() -> Self {
+        Self { due_in_days: 7 }
+    }
+}
+
+/// Commands run when records change, read from the `[hooks]` section. Each
+/// receives the event as JSON on stdin.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Seconds a hook may run before it's stopped
+    pub timeout_secs: u64,
+    pub on_mentee_added: Option<String>,
+    pub on_mentee_status_changed: Option<String>,
+    pub on_call_added: Option<String>,
+    pub on_payment_added: Option<String>,
+    pub on_video_requested: Option<String>,
+    pub on_video_delivered: Option<String>,
+    pub on_expense_added: Option<String>,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 10,
+            on_mentee_added: None,
+            on_mentee_status_changed: None,
+            on_call_added: None,
+            on_payment_added: None,
+            on_video_requested: None,
+            on_video_delivered: None,
+            on_expense_added: None,
+        }
+    }
+}
+
+/// Settings for `mentees invoice`, read from the `[invoice]` section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct InvoiceConfig {
+    pub number_prefix: String,
+    /// Replaces the built in HTML template when set
+    pub html_template: Option<PathBuf>,
+    /// Replaces the built in plain text template when set
+    pub text_template: Option<PathBuf>,
+}
+
+impl Default for InvoiceConfig {
+    fn default() -> Self {
+        Self {
+            number_prefix: "INV-".to_string(),
+            html_template: None,
+            text_template: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ConfigFile {
+    issuer: IssuerConfig,
+    invoice: InvoiceConfig,
+    payments: PaymentsConfig,
+    tax: TaxConfig,
+    calls: CallsConfig,
+    capacity: CapacityConfig,
+    videos: VideosConfig,
+    hooks: HooksConfig,
+}
+
 pub struct Config {
     pub db_path: PathBuf,
+    pub config_path: PathBuf,
+    pub issuer: IssuerConfig,
+    pub invoice: InvoiceConfig,
+    pub payments: PaymentsConfig,
+    pub tax: TaxConfig,
+    pub calls: CallsConfig,
+    pub capacity: CapacityConfig,
+    pub videos: VideosConfig,
+    pub hooks: HooksConfig,
 }
 
 impl Config {
@@ -14,6 +225,8 @@ impl Config {
         db_path.push(".mentees"); // Directory to store db
         std::fs::create_dir_all(&db_path)?; // Ensure directory exists
 
+        let config_path = db_path.join(CONFIG_FILE);
+
         if cfg!(debug_assertions) {
             // Dev database path
             db_path.push("mentees_dev.db");
@@ -22,6 +235,128 @@ impl Config {
             db_path.push("mentees.db");
         }
 
-        Ok(Self { db_path })
+        let file = Self::read_config_file(&config_path)?;
+
+        Ok(Self {
+            db_path,
+            config_path,
+            issuer: file.issuer,
+            invoice: file.invoice,
+            payments: file.payments,
+            tax: file.tax,
+            calls: file.calls,
+            capacity: file.capacity,
+            videos: file.videos,
+            hooks: file.hooks,
+        })
+    }
+
+    /// Settings for the database at `db_path`, everything else left at its default
+    pub fn for_database(db_path: PathBuf) -> Self {
+        let file = ConfigFile::default();
+
+        Self {
+            config_path: db_path.with_file_name(CONFIG_FILE),
+            db_path,
+            issuer: file.issuer,
+            invoice: file.invoice,
+            payments: file.payments,
+            tax: file.tax,
+            calls: file.calls,
+            capacity: file.capacity,
+            videos: file.videos,
+            hooks: file.hooks,
+        }
+    }
+
+    /// A missing config file is fine, every setting has a default.
+    fn read_config_file(path: &PathBuf) -> Result<ConfigFile, MenteeError> {
+        if !path.exists() {
+            return Ok(ConfigFile::default());
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+
+        let file: ConfigFile = toml::from_str(&contents)
+            .map_err(|err| MenteeError::ConfigError(format!("{}: {}", path.display(), err)))?;
+
+        file.tax.year_start()?;
+
+        Ok(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file_defaults_missing_sections() {
+        let file: ConfigFile = toml::from_str(
+            r#"
+            [issuer]
+            name = "Dan Page"
+            email = "dan@example.com"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(file.issuer.name, "Dan Page");
+        assert_eq!(file.issuer.address, "");
+        assert_eq!(file.issuer.tax_id, None);
+        assert_eq!(file.invoice.number_prefix, "INV-");
+        assert_eq!(file.payments.currency, "USD");
+        assert_eq!(file.tax.year_start().unwrap(), (1, 1));
+        assert_eq!(file.calls.default_minutes, 60);
+        assert_eq!(file.capacity.calls_per_month, None);
+        assert_eq!(file.videos.due_in_days, 7);
+        assert_eq!(file.hooks.timeout_secs, 10);
+        assert_eq!(file.hooks.on_call_added, None);
+    }
+
+    #[test]
+    fn test_capacity_exceeded_by() {
+        let capacity = CapacityConfig {
+            calls_per_month: Some(20),
+            hours_per_month: Some(15),
+        };
+
+        assert_eq!(capacity.exceeded_by(15, 60), None);
+        assert_eq!(
+            capacity.exceeded_by(16, 60).unwrap(),
+            "16.0 hours a month is more than the capacity of 15"
+        );
+        assert_eq!(
+            capacity.exceeded_by(21, 30).unwrap(),
+            "21 calls a month is more than the capacity of 20"
+        );
+        assert!(CapacityConfig::default().exceeded_by(500, 60).is_none());
+    }
+
+    #[test]
+    fn test_tax_year_start() {
+        let year_start = |value: &str| {
+            TaxConfig {
+                year_start: value.to_string(),
+            }
+            .year_start()
+        };
+
+        assert_eq!(year_start("04-06").unwrap(), (4, 6));
+        assert!(year_start("02-29").is_err());
+        assert!(year_start("13-01").is_err());
+        assert!(year_start("april").is_err());
+    }
+
+    #[test]
+    fn test_unknown_hook_is_rejected() {
+        let result: Result<ConfigFile, _> = toml::from_str(
+            r#"
+            [hooks]
+            on_call_add = "echo typo"
+            "#,
+        );
+
+        assert!(result.is_err());
     }
 }
@@ -2,3 +2,10 @@ pub const MENTEES_TABLE: &str = "mentees";
 pub const CALLS_TABLE: &str = "calls";
 pub const PAYMENTS_TABLE: &str = "payments";
 pub const VIDEOS_TABLE: &str = "videos";
+pub const CONTACTS_TABLE: &str = "contacts";
+pub const GOALS_TABLE: &str = "goals";
+pub const MILESTONES_TABLE: &str = "milestones";
+pub const CALL_GOALS_TABLE: &str = "call_goals";
+pub const STATUS_CHANGES_TABLE: &str = "status_changes";
+pub const INVOICES_TABLE: &str = "invoices";
+pub const EXPENSES_TABLE: &str = "expenses";
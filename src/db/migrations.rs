@@ -19,6 +19,92 @@ pub fn migrate_add_free_call_column(conn: &Connection) -> rusqlite::Result<()> {
     Ok(())
 }
 
+/// Adds each column that the table doesn't have yet
+fn add_missing_columns(
+    conn: &Connection,
+    table: &str,
+    new_columns: &[(&str, &str)],
+) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({});", table))?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(Result::ok)
+        .collect();
+
+    for (name, definition) in new_columns {
+        if !columns.iter().any(|col_name| col_name == name) {
+            conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN {} {};", table, name, definition),
+                [],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn migrate_add_call_duration_column(conn: &Connection) -> rusqlite::Result<()> {
+    add_missing_columns(conn, constants::CALLS_TABLE, &[("duration", "INTEGER")])
+}
+
+pub fn migrate_add_payment_details_columns(conn: &Connection) -> rusqlite::Result<()> {
+    add_missing_columns(
+        conn,
+        constants::PAYMENTS_TABLE,
+        &[
+            ("currency", "TEXT"),
+            ("fee", "INTEGER NOT NULL DEFAULT 0"),
+            ("source", "TEXT"),
+        ],
+    )
+}
+
+/// Videos logged before the workflow existed were all delivered
+pub fn migrate_add_video_workflow_columns(conn: &Connection) -> rusqlite::Result<()> {
+    add_missing_columns(
+        conn,
+        constants::VIDEOS_TABLE,
+        &[
+            (
+                "status",
+                "TEXT NOT NULL DEFAULT 'delivered' CHECK(status IN ('requested', 'in_progress', 'delivered'))",
+            ),
+            ("received_date", "TEXT"),
+            ("due_date", "TEXT"),
+        ],
+    )
+}
+
+pub fn migrate_add_video_source_columns(conn: &Connection) -> rusqlite::Result<()> {
+    add_missing_columns(
+        conn,
+        constants::VIDEOS_TABLE,
+        &[
+            ("source_url", "TEXT"),
+            ("file_path", "TEXT"),
+            ("file_size", "INTEGER"),
+            ("file_sha256", "TEXT"),
+        ],
+    )
+}
+
+pub fn migrate_add_mentee_source_columns(conn: &Connection) -> rusqlite::Result<()> {
+    add_missing_columns(
+        conn,
+        constants::MENTEES_TABLE,
+        &[
+            (
+                "source",
+                "TEXT CHECK(source IN ('mentorcruise', 'referral', 'website', 'other'))",
+            ),
+            (
+                "referrer_id",
+                "INTEGER REFERENCES mentees (id) ON DELETE SET NULL",
+            ),
+        ],
+    )
+}
+
 pub fn run_migrations(conn: &Connection) -> rusqlite::Result<()> {
     let mentees_sql = format!(
         "CREATE TABLE IF NOT EXISTS {} (
@@ -35,6 +121,8 @@ pub fn run_migrations(conn: &Connection) -> rusqlite::Result<()> {
 
     conn.execute(&mentees_sql, [])?;
 
+    migrate_add_mentee_source_columns(conn)?;
+
     let calls_sql = format!(
         "CREATE TABLE IF NOT EXISTS {} (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -49,6 +137,7 @@ pub fn run_migrations(conn: &Connection) -> rusqlite::Result<()> {
     conn.execute(&calls_sql, [])?;
 
     migrate_add_free_call_column(conn)?;
+    migrate_add_call_duration_column(conn)?;
 
     let videos_sql = format!(
         "CREATE TABLE IF NOT EXISTS {} (
@@ -64,6 +153,9 @@ pub fn run_migrations(conn: &Connection) -> rusqlite::Result<()> {
 
     conn.execute(&videos_sql, [])?;
 
+    migrate_add_video_workflow_columns(conn)?;
+    migrate_add_video_source_columns(conn)?;
+
     let payments_sql = format!(
         "CREATE TABLE IF NOT EXISTS {} (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -77,5 +169,115 @@ pub fn run_migrations(conn: &Connection) -> rusqlite::Result<()> {
 
     conn.execute(&payments_sql, [])?;
 
+    migrate_add_payment_details_columns(conn)?;
+
+    let contacts_sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            mentee_id INTEGER NOT NULL,
+            kind TEXT NOT NULL CHECK(kind IN ('email', 'phone', 'slack', 'discord', 'linkedin', 'github', 'website')),
+            value TEXT NOT NULL,
+            UNIQUE (mentee_id, kind, value),
+            FOREIGN KEY (mentee_id) REFERENCES {} (id) ON DELETE CASCADE)",
+        constants::CONTACTS_TABLE,
+        constants::MENTEES_TABLE
+    );
+
+    conn.execute(&contacts_sql, [])?;
+
+    let goals_sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            mentee_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            target_date TEXT,
+            status TEXT NOT NULL DEFAULT 'open' CHECK(status IN ('open', 'achieved', 'abandoned')),
+            FOREIGN KEY (mentee_id) REFERENCES {} (id) ON DELETE CASCADE)",
+        constants::GOALS_TABLE,
+        constants::MENTEES_TABLE
+    );
+
+    conn.execute(&goals_sql, [])?;
+
+    let milestones_sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            goal_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            done INTEGER NOT NULL DEFAULT 0,
+            FOREIGN KEY (goal_id) REFERENCES {} (id) ON DELETE CASCADE)",
+        constants::MILESTONES_TABLE,
+        constants::GOALS_TABLE
+    );
+
+    conn.execute(&milestones_sql, [])?;
+
+    let call_goals_sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            call_id INTEGER NOT NULL,
+            goal_id INTEGER NOT NULL,
+            PRIMARY KEY (call_id, goal_id),
+            FOREIGN KEY (call_id) REFERENCES {} (id) ON DELETE CASCADE,
+            FOREIGN KEY (goal_id) REFERENCES {} (id) ON DELETE CASCADE)",
+        constants::CALL_GOALS_TABLE,
+        constants::CALLS_TABLE,
+        constants::GOALS_TABLE
+    );
+
+    conn.execute(&call_goals_sql, [])?;
+
+    let status_changes_sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            mentee_id INTEGER NOT NULL,
+            date TEXT NOT NULL,
+            from_status TEXT CHECK(from_status IN ('archived', 'cold', 'warm', 'hot')),
+            to_status TEXT NOT NULL CHECK(to_status IN ('archived', 'cold', 'warm', 'hot')),
+            reason TEXT,
+            FOREIGN KEY (mentee_id) REFERENCES {} (id) ON DELETE CASCADE)",
+        constants::STATUS_CHANGES_TABLE,
+        constants::MENTEES_TABLE
+    );
+
+    conn.execute(&status_changes_sql, [])?;
+
+    let invoices_sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            number INTEGER NOT NULL UNIQUE,
+            payment_id INTEGER UNIQUE,
+            mentee_name TEXT NOT NULL,
+            issue_date TEXT NOT NULL,
+            payment_date TEXT NOT NULL,
+            amount INTEGER NOT NULL,
+            currency TEXT NOT NULL,
+            issuer_name TEXT NOT NULL,
+            issuer_address TEXT NOT NULL,
+            issuer_email TEXT NOT NULL,
+            issuer_tax_id TEXT,
+            FOREIGN KEY (payment_id) REFERENCES {} (id) ON DELETE SET NULL)",
+        constants::INVOICES_TABLE,
+        constants::PAYMENTS_TABLE
+    );
+
+    conn.execute(&invoices_sql, [])?;
+
+    let expenses_sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            date TEXT NOT NULL,
+            category TEXT NOT NULL CHECK(category IN ('software', 'subscription', 'course', 'equipment', 'marketing', 'travel', 'other')),
+            description TEXT NOT NULL,
+            amount INTEGER NOT NULL,
+            currency TEXT NOT NULL,
+            mentee_id INTEGER,
+            receipt_path TEXT,
+            FOREIGN KEY (mentee_id) REFERENCES {} (id) ON DELETE SET NULL)",
+        constants::EXPENSES_TABLE,
+        constants::MENTEES_TABLE
+    );
+
+    conn.execute(&expenses_sql, [])?;
+
     Ok(())
 }
@@ -1,4 +1,3 @@
-use rusqlite;
 use std::fmt;
 use std::io;
 
@@ -11,6 +10,8 @@ pub enum MenteeError {
     InvalidInput(String),
     UniqueViolation(String),
     ValidationError(String),
+    SerializationError(String),
+    ConfigError(String),
     HomeDirNotFound,
 }
 
@@ -26,11 +27,15 @@ impl fmt::Display for MenteeError {
             MenteeError::UniqueViolation(name) => {
                 write!(f, "Mentee with name '{}' already exists.", name)
             }
+            MenteeError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
+            MenteeError::ConfigError(msg) => write!(f, "Config error: {}", msg),
             MenteeError::HomeDirNotFound => write!(f, "Home directory not found"),
         }
     }
 }
 
+impl std::error::Error for MenteeError {}
+
 impl From<rusqlite::Error> for MenteeError {
     fn from(err: rusqlite::Error) -> MenteeError {
         MenteeError::DatabaseError(err)
@@ -49,6 +54,18 @@ impl From<inquire::InquireError> for MenteeError {
     }
 }
 
+impl From<serde_json::Error> for MenteeError {
+    fn from(err: serde_json::Error) -> MenteeError {
+        MenteeError::SerializationError(err.to_string())
+    }
+}
+
+impl From<csv::Error> for MenteeError {
+    fn from(err: csv::Error) -> MenteeError {
+        MenteeError::SerializationError(err.to_string())
+    }
+}
+
 impl From<&str> for MenteeError {
     fn from(message: &str) -> MenteeError {
         MenteeError::ValidationError(message.to_string())
@@ -126,6 +143,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialization_error_from_serde_json() {
+        let json_err = serde_json::from_str::<u32>("nope").unwrap_err();
+        let mentee_error: MenteeError = json_err.into();
+        assert!(matches!(mentee_error, MenteeError::SerializationError(_)));
+    }
+
     #[test]
     fn test_database_error_from_rusqlite() {
         let db_err = RusqliteError::InvalidQuery;
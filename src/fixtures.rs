@@ -0,0 +1,48 @@
+//! Records for unit tests. What a test doesn't care about is left empty, and
+//! tests set the fields they're about with struct update syntax.
+use crate::models::{
+    call::CallWithMenteeName,
+    mentee::{MenteeSummary, Status},
+    payment::PaymentWithMenteeName,
+};
+
+/// A call with dan, without notes or a duration
+pub(crate) fn call(id: u32, date: &str, free_call: bool) -> CallWithMenteeName {
+    CallWithMenteeName {
+        id,
+        mentee_name: "dan".to_string(),
+        date: date.to_string(),
+        notes: None,
+        free_call,
+        duration: None,
+    }
+}
+
+/// A payment of 200 from dan in the default currency, without a fee
+pub(crate) fn payment(id: u32, date: &str) -> PaymentWithMenteeName {
+    PaymentWithMenteeName {
+        id,
+        mentee_name: "dan".to_string(),
+        date: date.to_string(),
+        amount: 200,
+        currency: None,
+        fee: 0,
+        source: None,
+    }
+}
+
+/// A hot mentee on two calls a month who hasn't had a call yet
+pub(crate) fn summary(name: &str) -> MenteeSummary {
+    MenteeSummary {
+        name: name.to_string(),
+        calls_per_month: 2,
+        remaining_calls: 0,
+        status: Status::Hot,
+        notes: None,
+        last_call_date: None,
+        days_since_last_call: None,
+        avg_days_between_calls: None,
+        calls_last_30_days: 0,
+        calls_last_90_days: 0,
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    io::{self, Write},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::Local;
+use colored::Colorize;
+use serde_json::{json, Value};
+
+use crate::config::HooksConfig;
+
+/// How often a running hook is checked for having finished
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    MenteeAdded,
+    MenteeStatusChanged,
+    CallAdded,
+    PaymentAdded,
+    VideoRequested,
+    VideoDelivered,
+    ExpenseAdded,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::MenteeAdded => "mentee_added",
+            HookEvent::MenteeStatusChanged => "mentee_status_changed",
+            HookEvent::CallAdded => "call_added",
+            HookEvent::PaymentAdded => "payment_added",
+            HookEvent::VideoRequested => "video_requested",
+            HookEvent::VideoDelivered => "video_delivered",
+            HookEvent::ExpenseAdded => "expense_added",
+        }
+    }
+}
+
+impl HooksConfig {
+    /// The command configured for an event, if any
+    pub fn command(&self, event: HookEvent) -> Option<&str> {
+        let command = match event {
+            HookEvent::MenteeAdded => &self.on_mentee_added,
+            HookEvent::MenteeStatusChanged => &self.on_mentee_status_changed,
+            HookEvent::CallAdded => &self.on_call_added,
+            HookEvent::PaymentAdded => &self.on_payment_added,
+            HookEvent::VideoRequested => &self.on_video_requested,
+            HookEvent::VideoDelivered => &self.on_video_delivered,
+            HookEvent::ExpenseAdded => &self.on_expense_added,
+        };
+
+        command
+            .as_deref()
+            .filter(|command| !command.trim().is_empty())
+    }
+}
+
+/// Stops a hook that ran out of time, along with anything it started
+fn kill_hook(child: &mut Child) {
+    // the hook leads its own process group, so this reaches its children too
+    #[cfg(unix)]
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", child.id())])
+        .stderr(Stdio::null())
+        .status();
+    #[cfg(windows)]
+    let _ = Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &child.id().to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Runs `command` through the shell with `payload` on stdin, stopping it after `timeout`
+fn run_hook(
+    command: &str,
+    payload: &str,
+    env: &[(&str, &str)],
+    timeout: Duration,
+) -> Result<(), String> {
+    let mut shell = if cfg!(target_os = "windows") {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut shell, 0);
+
+    // hook output would get mixed into ours, errors are still shown
+    let mut child = shell
+        .arg(command)
+        .envs(env.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|err| format!("could not start: {}", err))?;
+
+    let started = Instant::now();
+
+    // written on its own thread, a hook that never reads a large event would
+    // otherwise block us before the timeout could stop it
+    let writer = child.stdin.take().map(|mut stdin| {
+        let payload = payload.to_string();
+        thread::spawn(move || stdin.write_all(payload.as_bytes()))
+    });
+
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                kill_hook(&mut child);
+                return Err(format!("timed out after {}s", timeout.as_secs_f32()));
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(err) => return Err(err.to_string()),
+        }
+    };
+
+    // a hook doesn't have to read the event, and anything it left running
+    // may still hold the pipe open, so only a finished write is checked
+    if let Some(writer) = writer.filter(|writer| writer.is_finished()) {
+        match writer.join() {
+            Ok(Err(err)) if err.kind() != io::ErrorKind::BrokenPipe => {
+                return Err(format!("could not write the event: {}", err))
+            }
+            _ => {}
+        }
+    }
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("exited with {}", status))
+    }
+}
+
+/// Runs the user's hook commands. The change has already been saved when a hook
+/// runs, so failures are reported without failing the command.
+pub struct Hooks<'a> {
+    config: &'a HooksConfig,
+}
+
+impl<'a> Hooks<'a> {
+    pub fn new(config: &'a HooksConfig) -> Self {
+        Self { config }
+    }
+
+    /// Fires `event` for a mentee with `data` describing the record
+    pub fn fire(&self, event: HookEvent, mentee_name: &str, data: Value) {
+        let Some(command) = self.config.command(event) else {
+            return;
+        };
+
+        let payload = json!({
+            "event": event.as_str(),
+            "occurred_at": Local::now().to_rfc3339(),
+            "mentee": mentee_name,
+            "data": data,
+        });
+
+        let env = [
+            ("MENTEES_EVENT", event.as_str()),
+            ("MENTEES_MENTEE", mentee_name),
+        ];
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+
+        if let Err(err) = run_hook(command, &payload.to_string(), &env, timeout) {
+            eprintln!(
+                "{}",
+                format!("Hook on_{} failed: {}", event.as_str(), err).yellow()
+            );
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hook_reads_event_and_environment() {
+        let result = run_hook(
+            r#"test "$MENTEES_EVENT" = call_added && grep -q '"mentee":"amy"'"#,
+            r#"{"mentee":"amy"}"#,
+            &[("MENTEES_EVENT", "call_added")],
+            Duration::from_secs(5),
+        );
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn hook_failures_are_reported() {
+        let failed = run_hook("exit 3", "{}", &[], Duration::from_secs(5));
+        assert!(failed.unwrap_err().contains("exit status: 3"));
+
+        let timed_out = run_hook("sleep 5", "{}", &[], Duration::from_millis(100));
+        assert!(timed_out.unwrap_err().starts_with("timed out"));
+    }
+
+    #[test]
+    fn hook_that_ignores_a_large_event_still_times_out() {
+        let payload = "x".repeat(1024 * 1024);
+        let started = Instant::now();
+
+        let timed_out = run_hook("sleep 5", &payload, &[], Duration::from_millis(100));
+
+        assert!(timed_out.unwrap_err().starts_with("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn timing_out_stops_what_the_hook_started() {
+        let pid_file = std::env::temp_dir().join(format!("mentees-hook-{}", std::process::id()));
+        let command = format!("sleep 30 & echo $! > '{}'; wait", pid_file.display());
+
+        let timed_out = run_hook(&command, "{}", &[], Duration::from_millis(200));
+        assert!(timed_out.unwrap_err().starts_with("timed out"));
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        let _ = std::fs::remove_file(&pid_file);
+        thread::sleep(Duration::from_millis(100));
+
+        // gone, or a zombie waiting to be reaped
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid.trim()));
+        assert!(stat.is_err() || stat.unwrap().contains(") Z "));
+    }
+}
@@ -1,29 +1,84 @@
+//! Mentees keeps track of mentees, their calls, video reviews and payments in
+//! a local SQLite database. Besides the `mentees` CLI, the crate can be used as
+//! a library by opening a [`Store`]:
+//!
+//! ```no_run
+//! use mentees::{models::call::CallInput, Store};
+//!
+//! let store = Store::open("mentees.db")?;
+//!
+//! let call = store.calls().create_call(
+//!     "dan page",
+//!     CallInput {
+//!         date: "2026-03-02".to_string(),
+//!         notes: Some("Mock interview".to_string()),
+//!         free_call: false,
+//!         duration: Some(60),
+//!         goal_ids: vec![],
+//!     },
+//! )?;
+//! println!("Added call {}", call.id);
+//! # Ok::<(), mentees::MenteeError>(())
+//! ```
+
 mod cli;
+mod completions;
 mod config;
 mod constants;
 mod db;
 mod error;
-mod models;
+#[cfg(test)]
+mod fixtures;
+mod hooks;
+pub mod models;
 mod repositories;
+mod server;
 mod services;
+mod store;
 mod utils;
 
-use clap::{Parser, Subcommand, ValueEnum};
+pub use config::Config;
+pub use error::MenteeError;
+pub use repositories::{
+    CallRepository, InMemoryRepository, MenteeRepository, PaymentRepository, VideoRepository,
+};
+pub use services::{CallService, MenteeService, PaymentService, VideoService};
+pub use store::Store;
+
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDate};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::{ArgValueCandidates, CompleteEnv};
 use cli::{
-    display_mentee, render_calls_table, render_mentees_table, render_payments_table,
-    render_videos_table,
+    display_call, display_goal, display_mentee, render_agenda, render_calls_table, render_capacity,
+    render_contacts_table, render_expenses_table, render_export, render_goals_table,
+    render_invoice, render_invoices_table, render_mentees_table, render_payments_table,
+    render_rates, render_retention, render_sources, render_statement, render_tax_report,
+    render_timeline, render_turnaround, render_video_queue, render_videos_table,
 };
-use config::Config;
-use db::connection;
-use db::migrations;
-use error::MenteeError;
-use models::mentee::Status;
+use completions::CompletionShell;
+use models::contact::ContactKind;
+use models::list::ListOptions;
+use models::mentee::{Source, Status};
+use models::video::VideoStatus;
 use rusqlite::Result;
-use services::CallService;
-use services::MenteeService;
-use services::PaymentService;
-use services::VideoService;
+use services::AgendaService;
+use services::CapacityService;
+use services::ContactService;
+use services::ExpenseService;
+use services::ExportService;
+use services::GoalService;
+use services::InvoiceService;
+use services::ReportService;
+use services::StatementService;
+use services::TimelineService;
+use services::{import_service::ImportKind, ImportService};
+use services::{seed_service::MAX_SEED_MENTEES, SeedService};
+use utils::validation::clap_validate_date;
 use utils::validation::clap_validate_day;
+use utils::validation::clap_validate_mapping;
+use utils::validation::clap_validate_month;
 use utils::validation::clap_validate_name;
 
 /// CLI to manage state of mentees
@@ -47,17 +102,99 @@ enum Commands {
         /// Include archived mentees which are hidden by default
         #[arg(long, default_value_t = false)]
         all: bool,
+
+        /// Show mentees with the status they had on this date (YYYY-MM-DD)
+        #[arg(long, value_parser = clap_validate_date)]
+        as_of: Option<NaiveDate>,
+
+        /// Only show mentees with this status, archived ones included
+        #[arg(long, value_enum)]
+        status: Option<Status>,
+
+        #[command(flatten)]
+        list: ListArgs<MenteeSort>,
     },
     /// View more details of a mentee
-    View { name: String },
+    View {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+    },
     /// Adds a new mentee
-    Add,
+    Add {
+        /// Refuse to add them when their calls would go over capacity
+        #[arg(long, default_value_t = false)]
+        strict: bool,
+    },
     /// Updates an existing mentee
     Update(UpdateMentee),
     /// Deletes a mentee
-    Delete { name: String },
+    Delete {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+    },
     /// Count or Sum a specified column
-    Count { column: Option<CountOptions> },
+    Count {
+        column: Option<CountOptions>,
+
+        /// Count the mentees active on this date (YYYY-MM-DD), or the expenses in its month
+        #[arg(long, value_parser = clap_validate_date)]
+        as_of: Option<NaiveDate>,
+    },
+    /// Calls a month committed to against the configured capacity
+    Capacity {
+        /// How many months to show, starting with this one
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+        months: u32,
+
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Calls, payments and video reviews due this week, and anything overdue
+    Agenda {
+        /// Day the agenda starts on (YYYY-MM-DD), defaults to today
+        #[arg(long, value_parser = clap_validate_date)]
+        date: Option<NaiveDate>,
+
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Show calls, videos, payments and status changes of a mentee in date order
+    Timeline {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+
+        /// Only show events on or after this date (YYYY-MM-DD)
+        #[arg(long, value_parser = clap_validate_date)]
+        since: Option<NaiveDate>,
+
+        /// Only show events on or before this date (YYYY-MM-DD)
+        #[arg(long, value_parser = clap_validate_date)]
+        until: Option<NaiveDate>,
+
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Statement of payments, calls and the call balance of a mentee
+    Statement {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+
+        /// Start of the statement (YYYY-MM-DD), earlier records make up the opening balance
+        #[arg(long, value_parser = clap_validate_date)]
+        from: Option<NaiveDate>,
+
+        /// End of the statement (YYYY-MM-DD)
+        #[arg(long, value_parser = clap_validate_date)]
+        to: Option<NaiveDate>,
+
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Reports across all mentees
+    Report {
+        #[command(subcommand)]
+        report: ReportKind,
+    },
     /// Manage calls
     Calls {
         #[command(subcommand)]
@@ -73,48 +210,459 @@ enum Commands {
         #[command(subcommand)]
         action: PaymentActions,
     },
+    /// Manage business expenses
+    Expenses {
+        #[command(subcommand)]
+        action: ExpenseActions,
+    },
+    /// Manage contact details
+    Contact {
+        #[command(subcommand)]
+        action: ContactActions,
+    },
+    /// Manage goals, lists open goals of all active mentees by default
+    Goals {
+        #[command(subcommand)]
+        action: Option<GoalActions>,
+    },
+    /// Create and view invoices for payments
+    Invoice {
+        #[command(subcommand)]
+        action: InvoiceActions,
+    },
+    /// Serve mentees, calls, videos and payments as a JSON API on localhost
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+
+        /// Require `Authorization: Bearer <token>` on every request
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// Export mentees and their contact details
+    Export {
+        /// Format to write to stdout
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+
+        /// Include archived mentees which are hidden by default
+        #[arg(long, default_value_t = false)]
+        all: bool,
+    },
+    /// Fill a database with generated mentees, calls, videos and payments for demos
+    Seed {
+        /// Number of mentees to generate
+        #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..=MAX_SEED_MENTEES as i64))]
+        mentees: u32,
+
+        /// Months of history to generate, ending this month
+        #[arg(long, default_value_t = 12, value_parser = clap::value_parser!(u32).range(1..=120))]
+        months: u32,
+
+        /// The same seed and --until date always generate the same data
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+
+        /// Last day of the generated history (YYYY-MM-DD), defaults to today
+        #[arg(long, value_parser = clap_validate_date)]
+        until: Option<NaiveDate>,
+
+        /// Database to fill, defaults to the one in ~/.mentees
+        #[arg(long)]
+        db: Option<PathBuf>,
+
+        /// Delete everything already in the database instead of refusing
+        #[arg(long, default_value_t = false)]
+        force: bool,
+    },
+    /// Print the script that sets up tab completion, e.g. `source <(mentees completions bash)`
+    Completions {
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ReportKind {
+    /// Cohorts by month of first payment, monthly churn and average tenure
+    Retention {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Mentees, conversion to paying, lifetime revenue and average tenure per acquisition source
+    Sources {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Net earned per hour of calls and video reviews, per mentee from best to worst and per month
+    Rates {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Days taken to review videos per mentee and how often they were on time
+    Turnaround {
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
+    /// Gross, fees and net income per month, currency and payment source
+    Tax {
+        /// The fiscal year, named after the calendar year it starts in
+        #[arg(long)]
+        year: i32,
+
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        output: OutputFormat,
+    },
 }
 
 // TODO: deduplicate
 #[derive(Subcommand, Debug, Clone)]
 enum CallActions {
     /// List all calls
-    List { name: Option<String> },
+    List {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: Option<String>,
+
+        /// Only show calls of mentees with this status
+        #[arg(long, value_enum)]
+        status: Option<Status>,
+
+        #[command(flatten)]
+        list: ListArgs<CallSort>,
+    },
+    /// Show a call with its full notes
+    Show {
+        #[arg(add = ArgValueCandidates::new(completions::call_ids))]
+        call_id: u32,
+    },
     /// Add a call
-    Add { name: String },
+    Add {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+    },
+    /// Add calls from a CSV file, all of them or none if a row is invalid
+    Import {
+        file: PathBuf,
+
+        /// Read a field from a differently named column, e.g. `--map date=When`
+        #[arg(long = "map", value_name = "FIELD=COLUMN", value_parser = clap_validate_mapping)]
+        mappings: Vec<(String, String)>,
+
+        /// Check every row and report errors without adding anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
     /// Delete a call
-    Delete { call_id: u32 },
+    Delete {
+        #[arg(add = ArgValueCandidates::new(completions::call_ids))]
+        call_id: u32,
+    },
     /// Update a call log
-    Update { call_id: u32 },
+    Update {
+        #[arg(add = ArgValueCandidates::new(completions::call_ids))]
+        call_id: u32,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
 enum PaymentActions {
     /// List all payments
-    List { name: Option<String> },
+    List {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: Option<String>,
+
+        /// Only show payments of mentees with this status
+        #[arg(long, value_enum)]
+        status: Option<Status>,
+
+        #[command(flatten)]
+        list: ListArgs<PaymentSort>,
+    },
     /// Add a payment
-    Add { name: String },
+    Add {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+    },
+    /// Add payments from a CSV file, all of them or none if a row is invalid
+    Import {
+        file: PathBuf,
+
+        /// Read a field from a differently named column, e.g. `--map date=When`
+        #[arg(long = "map", value_name = "FIELD=COLUMN", value_parser = clap_validate_mapping)]
+        mappings: Vec<(String, String)>,
+
+        /// Check every row and report errors without adding anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
     /// Delete a payment
-    Delete { payment_id: u32 },
+    Delete {
+        #[arg(add = ArgValueCandidates::new(completions::payment_ids))]
+        payment_id: u32,
+    },
     /// Update a payment
-    Update { payment_id: u32 },
+    Update {
+        #[arg(add = ArgValueCandidates::new(completions::payment_ids))]
+        payment_id: u32,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
 enum VideoActions {
     /// List all analysis videos
-    List { name: Option<String> },
-    /// Add a video analysis
-    Add { name: String },
+    List {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: Option<String>,
+
+        /// Only show videos at this stage of the review
+        #[arg(long, value_enum)]
+        status: Option<VideoStatus>,
+
+        #[command(flatten)]
+        list: ListArgs<VideoSort>,
+    },
+    /// Show videos waiting for review, soonest due first
+    Queue,
+    /// Log a video analysis that has already been delivered
+    Add {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+    },
+    /// Record a video received for review
+    Request {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+    },
+    /// Mark a requested video as in progress
+    Start {
+        #[arg(add = ArgValueCandidates::new(completions::video_ids))]
+        video_id: u32,
+    },
+    /// Mark a video review as delivered
+    Deliver {
+        #[arg(add = ArgValueCandidates::new(completions::video_ids))]
+        video_id: u32,
+    },
+    /// Open a video's local copy or link
+    Open {
+        #[arg(add = ArgValueCandidates::new(completions::video_ids))]
+        video_id: u32,
+
+        /// Print the link and file path instead of opening them
+        #[arg(long, default_value_t = false)]
+        print: bool,
+    },
+    /// Add videos from a CSV file, all of them or none if a row is invalid
+    Import {
+        file: PathBuf,
+
+        /// Read a field from a differently named column, e.g. `--map date=When`
+        #[arg(long = "map", value_name = "FIELD=COLUMN", value_parser = clap_validate_mapping)]
+        mappings: Vec<(String, String)>,
+
+        /// Check every row and report errors without adding anything
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
     /// Delete a video log
-    Delete { video_id: u32 },
+    Delete {
+        #[arg(add = ArgValueCandidates::new(completions::video_ids))]
+        video_id: u32,
+    },
     /// Update a video log
-    Update { video_id: u32 },
+    Update {
+        #[arg(add = ArgValueCandidates::new(completions::video_ids))]
+        video_id: u32,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ExpenseActions {
+    /// List all expenses, or those for one mentee
+    List {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: Option<String>,
+
+        #[command(flatten)]
+        list: ListArgs<ExpenseSort>,
+    },
+    /// Add an expense
+    Add,
+    /// Delete an expense
+    Delete {
+        #[arg(add = ArgValueCandidates::new(completions::expense_ids))]
+        expense_id: u32,
+    },
+    /// Update an expense
+    Update {
+        #[arg(add = ArgValueCandidates::new(completions::expense_ids))]
+        expense_id: u32,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ContactActions {
+    /// List a mentee's contact details
+    List {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+    },
+    /// Add a contact method e.g. `contact add "dan page" email dan@example.com`
+    Add {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+        kind: ContactKind,
+        value: String,
+    },
+    /// Remove a contact method
+    #[command(alias = "delete")]
+    Rm { contact_id: u32 },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum GoalActions {
+    /// List goals, optionally for a single mentee
+    List {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: Option<String>,
+
+        /// Include achieved and abandoned goals
+        #[arg(long, default_value_t = false)]
+        all: bool,
+    },
+    /// Show a goal with its milestones and linked calls
+    Show {
+        #[arg(add = ArgValueCandidates::new(completions::goal_ids))]
+        goal_id: u32,
+    },
+    /// Add a goal
+    Add {
+        #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
+        name: String,
+    },
+    /// Update a goal's title, target date or status
+    Update {
+        #[arg(add = ArgValueCandidates::new(completions::goal_ids))]
+        goal_id: u32,
+    },
+    /// Delete a goal
+    Delete {
+        #[arg(add = ArgValueCandidates::new(completions::goal_ids))]
+        goal_id: u32,
+    },
+    /// Manage the milestones of a goal
+    Milestone {
+        #[command(subcommand)]
+        action: MilestoneActions,
+    },
+    /// Link a call to a goal it advanced
+    Link {
+        #[arg(add = ArgValueCandidates::new(completions::call_ids))]
+        call_id: u32,
+        #[arg(add = ArgValueCandidates::new(completions::goal_ids))]
+        goal_id: u32,
+    },
+    /// Remove the link between a call and a goal
+    Unlink {
+        #[arg(add = ArgValueCandidates::new(completions::call_ids))]
+        call_id: u32,
+        #[arg(add = ArgValueCandidates::new(completions::goal_ids))]
+        goal_id: u32,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum InvoiceActions {
+    /// List all invoices
+    List,
+    /// Invoice a payment, or every payment in a month without an invoice
+    Create {
+        #[arg(
+            required_unless_present = "month",
+            conflicts_with = "month",
+            add = ArgValueCandidates::new(completions::payment_ids)
+        )]
+        payment_id: Option<u32>,
+
+        /// Invoice every payment made in this month (YYYY-MM)
+        #[arg(long, value_parser = clap_validate_month)]
+        month: Option<String>,
+    },
+    /// Print an invoice e.g. `invoice show INV-0007`
+    Show {
+        number: String,
+
+        #[arg(long, value_enum, default_value_t = InvoiceFormat::Text)]
+        format: InvoiceFormat,
+
+        /// Write the invoice to this file instead of printing it
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum MilestoneActions {
+    /// Add a milestone to a goal
+    Add {
+        #[arg(add = ArgValueCandidates::new(completions::goal_ids))]
+        goal_id: u32,
+        title: String,
+    },
+    /// Mark a milestone as done
+    Done { milestone_id: u32 },
+    /// Mark a milestone as not done
+    Undo { milestone_id: u32 },
+    /// Delete a milestone
+    Delete { milestone_id: u32 },
+}
+
+/// Date range, order and paging, the same on every list command
+#[derive(Args, Debug, Clone)]
+struct ListArgs<S: ValueEnum + Default + Clone + Send + Sync + 'static> {
+    /// Only show those dated on or after this date (YYYY-MM-DD)
+    #[arg(long, value_parser = clap_validate_date)]
+    since: Option<NaiveDate>,
+
+    /// Only show those dated on or before this date (YYYY-MM-DD)
+    #[arg(long, value_parser = clap_validate_date)]
+    until: Option<NaiveDate>,
+
+    /// Column to sort by, dates by default and status for mentees
+    #[arg(long, value_enum)]
+    sort: Option<S>,
+
+    /// Sort in descending order
+    #[arg(long, default_value_t = false)]
+    desc: bool,
+
+    /// Show at most this many
+    #[arg(long)]
+    limit: Option<u32>,
+
+    /// Skip this many first, e.g. to show the next page with --limit
+    #[arg(long, default_value_t = 0)]
+    offset: u32,
+}
+
+impl<S: ValueEnum + Default + Clone + Send + Sync + 'static> From<ListArgs<S>> for ListOptions<S> {
+    fn from(args: ListArgs<S>) -> Self {
+        Self {
+            since: args.since,
+            until: args.until,
+            sort: args.sort.unwrap_or_default(),
+            desc: args.desc,
+            limit: args.limit,
+            offset: args.offset,
+        }
+    }
 }
 
 #[derive(Parser, Clone, Debug)]
 pub struct UpdateMentee {
     /// The current name of the mentee (Required)
+    #[arg(add = ArgValueCandidates::new(completions::mentee_names))]
     pub name: String,
 
     /// Optionally update the name
@@ -129,6 +677,14 @@ pub struct UpdateMentee {
     #[arg(long)]
     pub status: Option<Status>,
 
+    /// Why the status changed, kept in the status history
+    #[arg(long, requires = "status")]
+    pub reason: Option<String>,
+
+    /// When the status changed (YYYY-MM-DD), defaults to today
+    #[arg(long, requires = "status", value_parser = clap_validate_date)]
+    pub status_date: Option<NaiveDate>,
+
     /// Optionally update the day the mentee pays
     #[arg(long, value_parser = clap_validate_day)]
     pub payment_day: Option<i32>,
@@ -144,6 +700,22 @@ pub struct UpdateMentee {
     /// Optionally update the notes
     #[arg(long)]
     pub notes: Option<String>,
+
+    /// Edit the notes in $VISUAL or $EDITOR, pre-filled with the current notes
+    #[arg(long, default_value_t = false)]
+    pub edit_notes: bool,
+
+    /// Optionally update where they found us
+    #[arg(long, value_enum)]
+    pub source: Option<Source>,
+
+    /// Optionally update the mentee who referred them
+    #[arg(long, add = ArgValueCandidates::new(completions::mentee_names))]
+    pub referrer: Option<String>,
+
+    /// Refuse more calls, or a return from archived, that would go over capacity
+    #[arg(long, default_value_t = false)]
+    pub strict: bool,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -153,37 +725,139 @@ pub enum CountOptions {
     Gross,
     Net,
     NetPerCall,
+    /// Expenses in the default currency in the current month, or the month of --as-of
+    Expenses,
+    /// Net less the month's expenses in the default currency
+    Profit,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum MenteeSort {
+    #[default]
+    Status,
+    Name,
+    Remaining,
+    LastCall,
+    DaysSince,
+    Cadence,
+    Calls30,
+    Calls90,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum CallSort {
+    #[default]
+    Date,
+    Mentee,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum PaymentSort {
+    #[default]
+    Date,
+    Mentee,
+    Amount,
+    Net,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum VideoSort {
+    #[default]
+    Date,
+    Mentee,
+    Status,
+    Due,
+    Length,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExpenseSort {
+    #[default]
+    Date,
+    Category,
+    Amount,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Markdown,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum InvoiceFormat {
+    Text,
+    Html,
 }
 
 pub fn run() -> Result<(), MenteeError> {
-    let config = Config::new()?;
-    let conn =
-        connection::establish_connection(&config).expect("Failed to connect to the database");
+    // When the shell asks for completions this answers and exits
+    CompleteEnv::with_factory(Cli::command)
+        .var(completions::COMPLETE_VAR)
+        .complete();
 
-    migrations::run_migrations(&conn).expect("Failed to run database migrations");
+    let store = Store::open_default()?;
+    let conn = store.connection();
+    let config = store.config();
 
-    let call_service = CallService::new(&conn);
-    let mentee_service = MenteeService::new(&conn);
-    let payment_service = PaymentService::new(&conn);
-    let video_service = VideoService::new(&conn);
+    let agenda_service = AgendaService::new(conn);
+    let capacity_service = CapacityService::new(conn, config);
+    let call_service = CallService::new(conn, config);
+    let contact_service = ContactService::new(conn);
+    let expense_service = ExpenseService::new(conn, config);
+    let export_service = ExportService::new(conn);
+    let goal_service = GoalService::new(conn);
+    let import_service = ImportService::new(conn, config);
+    let invoice_service = InvoiceService::new(conn, config);
+    let mentee_service = MenteeService::new(conn, config);
+    let payment_service = PaymentService::new(conn, config);
+    let report_service = ReportService::new(conn, config);
+    let statement_service = StatementService::new(conn);
+    let timeline_service = TimelineService::new(conn);
+    let video_service = VideoService::new(conn, config);
 
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::List { all } => {
+        Commands::List {
+            all,
+            as_of,
+            status,
+            list,
+        } => {
             if let Err(err) = mentee_service
-                .get_mentees_summaries(all)
+                .get_mentees_summaries(all, as_of, status, &list.into())
                 .and_then(render_mentees_table)
             {
                 eprintln!("{err}");
             }
         }
-        Commands::View { name } => match mentee_service.get_mentee_with_counts(name) {
-            Ok(mentee) => display_mentee(mentee),
+        Commands::View { name } => match mentee_service
+            .get_mentee_with_counts(name.clone())
+            .and_then(|mentee| {
+                let contacts = contact_service.get_contacts(name.clone())?;
+                let goals = goal_service.get_goals(Some(name.clone()), false)?;
+                let history = mentee_service.get_status_history(name)?;
+                let rate = report_service
+                    .get_rates(Some(mentee.mentee.id as i64))?
+                    .overall;
+                Ok((mentee, contacts, goals, history, rate))
+            }) {
+            Ok((mentee, contacts, goals, history, rate)) => {
+                display_mentee(mentee, contacts, goals, history, rate)
+            }
             Err(err) => eprintln!("{err}"),
         },
-        Commands::Add => match mentee_service.add_mentee() {
-            Ok(name) => println!("Added Mentee: {}", name),
+        Commands::Add { strict } => match mentee_service.add_mentee(strict) {
+            Ok(message) => println!("{}", message),
             Err(err) => eprintln!("{err}"),
         },
         Commands::Update(update_args) => match mentee_service.update_mentee(update_args) {
@@ -194,19 +868,81 @@ pub fn run() -> Result<(), MenteeError> {
             Ok(deleted) => println!("Deleted Mentee: {}", deleted),
             Err(err) => eprintln!("{err}"),
         },
-        Commands::Count { column } => match mentee_service.get_mentee_count(column) {
+        Commands::Count { column, as_of } => match mentee_service.get_mentee_count(column, as_of) {
             Ok(result) => println!("{result}"),
             Err(err) => eprintln!("{err}"),
         },
+        Commands::Capacity { months, output } => match capacity_service.get_capacity(months) {
+            Ok(capacity) => render_capacity(capacity, output)?,
+            Err(err) => eprintln!("{err}"),
+        },
+        Commands::Agenda { date, output } => match agenda_service.get_agenda(date) {
+            Ok(agenda) => render_agenda(agenda, output)?,
+            Err(err) => eprintln!("{err}"),
+        },
+        Commands::Timeline {
+            name,
+            since,
+            until,
+            output,
+        } => match timeline_service.get_timeline(name, since, until) {
+            Ok(events) => render_timeline(events, output)?,
+            Err(err) => eprintln!("{err}"),
+        },
+        Commands::Statement {
+            name,
+            from,
+            to,
+            output,
+        } => match statement_service.get_statement(name, from, to) {
+            Ok(statement) => render_statement(statement, output)?,
+            Err(err) => eprintln!("{err}"),
+        },
+        Commands::Report { report } => match report {
+            ReportKind::Retention { output } => match report_service.get_retention() {
+                Ok(retention) => render_retention(retention, output)?,
+                Err(err) => eprintln!("{err}"),
+            },
+            ReportKind::Sources { output } => match report_service.get_sources() {
+                Ok(sources) => render_sources(sources, output)?,
+                Err(err) => eprintln!("{err}"),
+            },
+            ReportKind::Rates { output } => match report_service.get_rates(None) {
+                Ok(report) => render_rates(report, output)?,
+                Err(err) => eprintln!("{err}"),
+            },
+            ReportKind::Turnaround { output } => match report_service.get_turnaround() {
+                Ok(report) => render_turnaround(report, output)?,
+                Err(err) => eprintln!("{err}"),
+            },
+            ReportKind::Tax { year, output } => match report_service.get_tax_report(year) {
+                Ok(report) => render_tax_report(report, output)?,
+                Err(err) => eprintln!("{err}"),
+            },
+        },
         Commands::Calls { action } => match action {
-            CallActions::List { name } => match call_service.get_all_calls(name) {
-                Ok(calls) => render_calls_table(calls)?,
-                Err(err) => eprintln!("{}", err),
+            CallActions::List { name, status, list } => {
+                match call_service.list_calls(name, status, &list.into()) {
+                    Ok(calls) => render_calls_table(calls)?,
+                    Err(err) => eprintln!("{}", err),
+                }
+            }
+            CallActions::Show { call_id } => match call_service.get_call(call_id) {
+                Ok(call) => display_call(call),
+                Err(err) => eprintln!("{err}"),
             },
             CallActions::Add { name } => match call_service.add_call(name) {
                 Ok(success) => println!("{success}"),
                 Err(err) => eprintln!("{err}"),
             },
+            CallActions::Import {
+                file,
+                mappings,
+                dry_run,
+            } => match import_service.import(ImportKind::Calls, &file, &mappings, dry_run) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
             CallActions::Update { call_id } => match call_service.update_call(call_id) {
                 Ok(success) => println!("{success}"),
                 Err(err) => eprintln!("{err}"),
@@ -219,14 +955,46 @@ pub fn run() -> Result<(), MenteeError> {
             }
         },
         Commands::Videos { action } => match action {
-            VideoActions::List { name } => match video_service.get_all_videos(name) {
-                Ok(videos) => render_videos_table(videos)?,
-                Err(err) => eprintln!("{}", err),
+            VideoActions::List { name, status, list } => {
+                match video_service.list_videos(name, status, &list.into()) {
+                    Ok(videos) => render_videos_table(videos)?,
+                    Err(err) => eprintln!("{}", err),
+                }
+            }
+            VideoActions::Queue => match video_service.get_queue() {
+                Ok(videos) => render_video_queue(videos)?,
+                Err(err) => eprintln!("{err}"),
             },
             VideoActions::Add { name } => match video_service.add_video(name) {
                 Ok(success) => println!("{success}"),
                 Err(err) => eprintln!("{err}"),
             },
+            VideoActions::Request { name } => match video_service.request_video(name) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
+            VideoActions::Import {
+                file,
+                mappings,
+                dry_run,
+            } => match import_service.import(ImportKind::Videos, &file, &mappings, dry_run) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
+            VideoActions::Start { video_id } => match video_service.start_video(video_id) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
+            VideoActions::Deliver { video_id } => match video_service.deliver_video(video_id) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
+            VideoActions::Open { video_id, print } => {
+                match video_service.open_video(video_id, print) {
+                    Ok(success) => println!("{success}"),
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
             VideoActions::Update { video_id } => match video_service.update_video(video_id) {
                 Ok(success) => println!("{success}"),
                 Err(err) => eprintln!("{err}"),
@@ -237,14 +1005,26 @@ pub fn run() -> Result<(), MenteeError> {
             },
         },
         Commands::Payments { action } => match action {
-            PaymentActions::List { name } => match payment_service.get_all_payments(name) {
-                Ok(payments) => render_payments_table(payments)?,
-                Err(err) => eprintln!("{}", err),
-            },
+            PaymentActions::List { name, status, list } => {
+                match payment_service.list_payments(name, status, &list.into()) {
+                    Ok(payments) => {
+                        render_payments_table(payments, payment_service.default_currency())?
+                    }
+                    Err(err) => eprintln!("{}", err),
+                }
+            }
             PaymentActions::Add { name } => match payment_service.add_payment(name) {
                 Ok(success) => println!("{success}"),
                 Err(err) => eprintln!("{err}"),
             },
+            PaymentActions::Import {
+                file,
+                mappings,
+                dry_run,
+            } => match import_service.import(ImportKind::Payments, &file, &mappings, dry_run) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
             PaymentActions::Update { payment_id } => {
                 match payment_service.update_payment(payment_id) {
                     Ok(success) => println!("{success}"),
@@ -258,6 +1038,173 @@ pub fn run() -> Result<(), MenteeError> {
                 }
             }
         },
+        Commands::Expenses { action } => match action {
+            ExpenseActions::List { name, list } => {
+                match expense_service.list_expenses(name, &list.into()) {
+                    Ok(expenses) => render_expenses_table(expenses)?,
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
+            ExpenseActions::Add => match expense_service.add_expense() {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
+            ExpenseActions::Update { expense_id } => {
+                match expense_service.update_expense(expense_id) {
+                    Ok(success) => println!("{success}"),
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
+            ExpenseActions::Delete { expense_id } => {
+                match expense_service.delete_expense(expense_id) {
+                    Ok(success) => println!("{success}"),
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
+        },
+        Commands::Contact { action } => match action {
+            ContactActions::List { name } => match contact_service.get_contacts(name) {
+                Ok(contacts) => render_contacts_table(contacts)?,
+                Err(err) => eprintln!("{err}"),
+            },
+            ContactActions::Add { name, kind, value } => {
+                match contact_service.add_contact(name, kind, value) {
+                    Ok(success) => println!("{success}"),
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
+            ContactActions::Rm { contact_id } => match contact_service.delete_contact(contact_id) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
+        },
+        Commands::Goals { action } => match action.unwrap_or(GoalActions::List {
+            name: None,
+            all: false,
+        }) {
+            GoalActions::List { name, all } => match goal_service.get_goals(name, all) {
+                Ok(goals) => render_goals_table(goals)?,
+                Err(err) => eprintln!("{err}"),
+            },
+            GoalActions::Show { goal_id } => match goal_service.get_goal_details(goal_id) {
+                Ok(details) => display_goal(details),
+                Err(err) => eprintln!("{err}"),
+            },
+            GoalActions::Add { name } => match goal_service.add_goal(name) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
+            GoalActions::Update { goal_id } => match goal_service.update_goal(goal_id) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
+            GoalActions::Delete { goal_id } => match goal_service.delete_goal(goal_id) {
+                Ok(success) => println!("{success}"),
+                Err(err) => eprintln!("{err}"),
+            },
+            GoalActions::Milestone { action } => {
+                let result = match action {
+                    MilestoneActions::Add { goal_id, title } => {
+                        goal_service.add_milestone(goal_id, title)
+                    }
+                    MilestoneActions::Done { milestone_id } => {
+                        goal_service.set_milestone_done(milestone_id, true)
+                    }
+                    MilestoneActions::Undo { milestone_id } => {
+                        goal_service.set_milestone_done(milestone_id, false)
+                    }
+                    MilestoneActions::Delete { milestone_id } => {
+                        goal_service.delete_milestone(milestone_id)
+                    }
+                };
+
+                match result {
+                    Ok(success) => println!("{success}"),
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
+            GoalActions::Link { call_id, goal_id } => {
+                match goal_service.link_call(call_id, goal_id) {
+                    Ok(success) => println!("{success}"),
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
+            GoalActions::Unlink { call_id, goal_id } => {
+                match goal_service.unlink_call(call_id, goal_id) {
+                    Ok(success) => println!("{success}"),
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
+        },
+        Commands::Invoice { action } => match action {
+            InvoiceActions::List => match invoice_service.get_invoices() {
+                Ok(invoices) => render_invoices_table(invoices, invoice_service.number_prefix())?,
+                Err(err) => eprintln!("{err}"),
+            },
+            InvoiceActions::Create { payment_id, month } => {
+                let result = match (payment_id, month) {
+                    (Some(payment_id), _) => invoice_service.create_invoice(payment_id),
+                    (None, Some(month)) => invoice_service.create_month_invoices(month),
+                    (None, None) => unreachable!("clap requires a payment id or --month"),
+                };
+
+                match result {
+                    Ok(success) => println!("{success}"),
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
+            InvoiceActions::Show {
+                number,
+                format,
+                out,
+            } => match invoice_service
+                .get_invoice(&number)
+                .and_then(|invoice| Ok((invoice, invoice_service.get_template(format)?)))
+            {
+                Ok((invoice, template)) => render_invoice(
+                    invoice,
+                    &template,
+                    invoice_service.number_prefix(),
+                    format,
+                    out,
+                )?,
+                Err(err) => eprintln!("{err}"),
+            },
+        },
+        Commands::Serve { port, token } => {
+            if let Err(err) = server::serve(conn, config, port, token) {
+                eprintln!("{err}");
+            }
+        }
+        Commands::Export { format, all } => match export_service.export_mentees(all) {
+            Ok(records) => render_export(records, format)?,
+            Err(err) => eprintln!("{err}"),
+        },
+        Commands::Seed {
+            mentees,
+            months,
+            seed,
+            until,
+            db,
+            force,
+        } => {
+            let until = until.unwrap_or_else(|| Local::now().date_naive());
+            let result = match db {
+                Some(path) => Store::open(path).and_then(|target| {
+                    SeedService::new(target.connection(), target.config())
+                        .seed(mentees, months, seed, until, force)
+                }),
+                None => SeedService::new(conn, config).seed(mentees, months, seed, until, force),
+            };
+
+            match result {
+                Ok(message) => println!("{message}"),
+                Err(err) => eprintln!("{err}"),
+            }
+        }
+        Commands::Completions { shell } => {
+            completions::write_registration(shell, &mut std::io::stdout())?
+        }
     };
 
     Ok(())
@@ -0,0 +1,68 @@
+use serde::Serialize;
+
+/// Sections of the agenda, declared in the order they're listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgendaPriority {
+    Overdue,
+    Today,
+    ThisWeek,
+    /// Not tied to a date, such as a mentee owing calls
+    Attention,
+}
+
+impl AgendaPriority {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgendaPriority::Overdue => "Overdue",
+            AgendaPriority::Today => "Today",
+            AgendaPriority::ThisWeek => "This Week",
+            AgendaPriority::Attention => "Needs Attention",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgendaItemKind {
+    Call,
+    Payment,
+    Video,
+    Balance,
+}
+
+impl AgendaItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgendaItemKind::Call => "call",
+            AgendaItemKind::Payment => "payment",
+            AgendaItemKind::Video => "video",
+            AgendaItemKind::Balance => "balance",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AgendaItem {
+    pub priority: AgendaPriority,
+    pub date: Option<String>,
+    pub kind: AgendaItemKind,
+    pub mentee_name: String,
+    pub description: String,
+}
+
+/// Everything needing attention in the week starting on `date`, most urgent first
+#[derive(Debug, Clone, Serialize)]
+pub struct Agenda {
+    pub date: String,
+    pub until: String,
+    pub items: Vec<AgendaItem>,
+}
+
+/// When an active mentee pays each month and when they last did
+#[derive(Debug, Clone)]
+pub struct PaymentSchedule {
+    pub mentee_name: String,
+    pub payment_day: u32,
+    pub last_payment_date: Option<String>,
+}
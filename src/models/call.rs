@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, Clone)]
 pub struct Call {
     pub id: u32,
@@ -5,13 +7,34 @@ pub struct Call {
     pub date: String,
     pub notes: Option<String>,
     pub free_call: bool,
+    /// Minutes the call took, calls logged before durations were have none
+    pub duration: Option<u32>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CallWithMenteeName {
     pub id: u32,
     pub mentee_name: String,
     pub date: String,
     pub notes: Option<String>,
     pub free_call: bool,
+    /// Minutes the call took, calls logged before durations were have none
+    pub duration: Option<u32>,
+}
+
+/// The fields of a call when it's recorded without prompting
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CallInput {
+    pub date: String,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub free_call: bool,
+    /// Minutes the call took
+    #[serde(default)]
+    pub duration: Option<u32>,
+    /// Open goals of the mentee the call advanced, only linked when the call is added
+    #[serde(default)]
+    pub goal_ids: Vec<u32>,
 }
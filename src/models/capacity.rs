@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+/// Calls committed to and booked in a month against what can be handled.
+/// Capacities are `None` when they aren't configured.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CapacityMonth {
+    /// YYYY-MM
+    pub month: String,
+    /// Calls a month of every mentee who isn't archived
+    pub committed_calls: u32,
+    /// Calls held or scheduled in the month
+    pub booked_calls: u32,
+    pub committed_hours: f64,
+    pub capacity_calls: Option<u32>,
+    pub capacity_hours: Option<u32>,
+}
+
+impl CapacityMonth {
+    /// Calls that can still be committed to, negative when overcommitted
+    pub fn available_calls(&self) -> Option<i64> {
+        self.capacity_calls
+            .map(|capacity| capacity as i64 - self.committed_calls as i64)
+    }
+
+    /// Hours that can still be committed to, negative when overcommitted
+    pub fn available_hours(&self) -> Option<f64> {
+        self.capacity_hours
+            .map(|capacity| capacity as f64 - self.committed_hours)
+    }
+}
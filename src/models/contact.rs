@@ -0,0 +1,91 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContactKind {
+    Email,
+    Phone,
+    Slack,
+    Discord,
+    Linkedin,
+    Github,
+    Website,
+}
+
+impl ContactKind {
+    // convert enum variant to a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContactKind::Email => "email",
+            ContactKind::Phone => "phone",
+            ContactKind::Slack => "slack",
+            ContactKind::Discord => "discord",
+            ContactKind::Linkedin => "linkedin",
+            ContactKind::Github => "github",
+            ContactKind::Website => "website",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<ContactKind> {
+        match s {
+            "email" => Some(ContactKind::Email),
+            "phone" => Some(ContactKind::Phone),
+            "slack" => Some(ContactKind::Slack),
+            "discord" => Some(ContactKind::Discord),
+            "linkedin" => Some(ContactKind::Linkedin),
+            "github" => Some(ContactKind::Github),
+            "website" => Some(ContactKind::Website),
+            _ => None,
+        }
+    }
+
+    pub fn variants() -> Vec<ContactKind> {
+        vec![
+            ContactKind::Email,
+            ContactKind::Phone,
+            ContactKind::Slack,
+            ContactKind::Discord,
+            ContactKind::Linkedin,
+            ContactKind::Github,
+            ContactKind::Website,
+        ]
+    }
+
+    /// Human friendly label used when displaying a mentee
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContactKind::Email => "Email",
+            ContactKind::Phone => "Phone",
+            ContactKind::Slack => "Slack",
+            ContactKind::Discord => "Discord",
+            ContactKind::Linkedin => "LinkedIn",
+            ContactKind::Github => "GitHub",
+            ContactKind::Website => "Website",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Contact {
+    pub id: u32,
+    #[serde(skip)]
+    pub mentee_id: i64,
+    pub kind: ContactKind,
+    pub value: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contact_kind_round_trips_through_str() {
+        for kind in ContactKind::variants() {
+            assert_eq!(ContactKind::from_str(kind.as_str()), Some(kind));
+        }
+
+        assert_eq!(ContactKind::from_str("fax"), None);
+    }
+}
@@ -0,0 +1,93 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpenseCategory {
+    Software,
+    Subscription,
+    Course,
+    Equipment,
+    Marketing,
+    Travel,
+    Other,
+}
+
+impl ExpenseCategory {
+    // convert enum variant to a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpenseCategory::Software => "software",
+            ExpenseCategory::Subscription => "subscription",
+            ExpenseCategory::Course => "course",
+            ExpenseCategory::Equipment => "equipment",
+            ExpenseCategory::Marketing => "marketing",
+            ExpenseCategory::Travel => "travel",
+            ExpenseCategory::Other => "other",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<ExpenseCategory> {
+        match s {
+            "software" => Some(ExpenseCategory::Software),
+            "subscription" => Some(ExpenseCategory::Subscription),
+            "course" => Some(ExpenseCategory::Course),
+            "equipment" => Some(ExpenseCategory::Equipment),
+            "marketing" => Some(ExpenseCategory::Marketing),
+            "travel" => Some(ExpenseCategory::Travel),
+            "other" => Some(ExpenseCategory::Other),
+            _ => None,
+        }
+    }
+
+    pub fn variants() -> Vec<&'static str> {
+        vec![
+            ExpenseCategory::Software.as_str(),
+            ExpenseCategory::Subscription.as_str(),
+            ExpenseCategory::Course.as_str(),
+            ExpenseCategory::Equipment.as_str(),
+            ExpenseCategory::Marketing.as_str(),
+            ExpenseCategory::Travel.as_str(),
+            ExpenseCategory::Other.as_str(),
+        ]
+    }
+}
+
+pub struct Expense {
+    pub id: u32,
+    pub date: String,
+    pub category: ExpenseCategory,
+    pub description: String,
+    pub amount: u32,
+    pub currency: String,
+    /// Set when the cost was for a particular mentee
+    pub mentee_id: Option<u32>,
+    pub receipt_path: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExpenseWithMenteeName {
+    pub id: u32,
+    pub date: String,
+    pub category: ExpenseCategory,
+    pub description: String,
+    pub amount: u32,
+    pub currency: String,
+    pub mentee_name: Option<String>,
+    pub receipt_path: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_round_trip() {
+        for name in ExpenseCategory::variants() {
+            let category = ExpenseCategory::from_str(name).unwrap();
+            assert_eq!(category.as_str(), name);
+        }
+        assert_eq!(ExpenseCategory::from_str("lunch"), None);
+    }
+}
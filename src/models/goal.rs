@@ -0,0 +1,138 @@
+use clap::ValueEnum;
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+pub enum GoalStatus {
+    Open,
+    Achieved,
+    Abandoned,
+}
+
+impl GoalStatus {
+    // convert enum variant to a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GoalStatus::Open => "open",
+            GoalStatus::Achieved => "achieved",
+            GoalStatus::Abandoned => "abandoned",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<GoalStatus> {
+        match s {
+            "open" => Some(GoalStatus::Open),
+            "achieved" => Some(GoalStatus::Achieved),
+            "abandoned" => Some(GoalStatus::Abandoned),
+            _ => None,
+        }
+    }
+
+    pub fn variants() -> Vec<&'static str> {
+        vec![
+            GoalStatus::Open.as_str(),
+            GoalStatus::Achieved.as_str(),
+            GoalStatus::Abandoned.as_str(),
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Goal {
+    pub id: u32,
+    pub mentee_id: i64,
+    pub title: String,
+    pub target_date: Option<String>,
+    pub status: GoalStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct Milestone {
+    pub id: u32,
+    #[allow(dead_code)]
+    pub goal_id: u32,
+    pub title: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct GoalWithProgress {
+    pub id: u32,
+    pub mentee_name: String,
+    pub title: String,
+    pub target_date: Option<String>,
+    pub status: GoalStatus,
+    pub milestones_done: u32,
+    pub milestones_total: u32,
+    pub linked_calls: u32,
+}
+
+impl GoalWithProgress {
+    /// Percentage of milestones completed, an achieved goal is always complete
+    pub fn completion_percentage(&self) -> u32 {
+        if self.status == GoalStatus::Achieved {
+            100
+        } else {
+            (self.milestones_done * 100)
+                .checked_div(self.milestones_total)
+                .unwrap_or(0)
+        }
+    }
+}
+
+/// A call that advanced a goal
+#[derive(Debug, Clone)]
+pub struct LinkedCall {
+    pub call_id: u32,
+    pub date: String,
+}
+
+/// A goal with everything `goals show` displays
+#[derive(Debug, Clone)]
+pub struct GoalDetails {
+    pub goal: GoalWithProgress,
+    pub milestones: Vec<Milestone>,
+    pub linked_calls: Vec<LinkedCall>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(status: GoalStatus, done: u32, total: u32) -> GoalWithProgress {
+        GoalWithProgress {
+            id: 1,
+            mentee_name: "dan".to_string(),
+            title: "Land a job".to_string(),
+            target_date: None,
+            status,
+            milestones_done: done,
+            milestones_total: total,
+            linked_calls: 0,
+        }
+    }
+
+    #[test]
+    fn completion_from_milestones() {
+        assert_eq!(goal(GoalStatus::Open, 1, 3).completion_percentage(), 33);
+        assert_eq!(goal(GoalStatus::Open, 0, 0).completion_percentage(), 0);
+    }
+
+    #[test]
+    fn achieved_goal_is_complete() {
+        assert_eq!(
+            goal(GoalStatus::Achieved, 0, 4).completion_percentage(),
+            100
+        );
+    }
+
+    #[test]
+    fn test_goal_status_from_str() {
+        assert_eq!(GoalStatus::from_str("open"), Some(GoalStatus::Open));
+        assert_eq!(GoalStatus::from_str("achieved"), Some(GoalStatus::Achieved));
+        assert_eq!(
+            GoalStatus::from_str("abandoned"),
+            Some(GoalStatus::Abandoned)
+        );
+        assert_eq!(GoalStatus::from_str("done"), None);
+    }
+}
@@ -0,0 +1,51 @@
+/// An issued invoice. The mentee and issuer details are copied in when it's
+/// created so the invoice reads the same after a rename or a config change.
+#[derive(Debug, Clone)]
+pub struct Invoice {
+    #[allow(dead_code)]
+    pub id: u32,
+    pub number: u32,
+    pub payment_id: Option<u32>,
+    pub mentee_name: String,
+    pub issue_date: String,
+    pub payment_date: String,
+    pub amount: u32,
+    pub currency: String,
+    pub issuer_name: String,
+    pub issuer_address: String,
+    pub issuer_email: String,
+    pub issuer_tax_id: Option<String>,
+}
+
+impl Invoice {
+    /// The invoice number as printed, e.g. `INV-0007`
+    pub fn display_number(&self, prefix: &str) -> String {
+        format!("{}{:04}", prefix, self.number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_number_pads_to_four_digits() {
+        let invoice = Invoice {
+            id: 1,
+            number: 7,
+            payment_id: Some(3),
+            mentee_name: "dan page".to_string(),
+            issue_date: "2026-03-01".to_string(),
+            payment_date: "2026-03-01".to_string(),
+            amount: 400,
+            currency: "USD".to_string(),
+            issuer_name: "Mentor".to_string(),
+            issuer_address: String::new(),
+            issuer_email: String::new(),
+            issuer_tax_id: None,
+        };
+
+        assert_eq!(invoice.display_number("INV-"), "INV-0007");
+        assert_eq!(invoice.display_number(""), "0007");
+    }
+}
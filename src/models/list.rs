@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+
+/// Narrows, orders and pages the records of a list command. `S` is the column
+/// the records can be sorted by, the dates are inclusive.
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions<S> {
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub sort: S,
+    pub desc: bool,
+    /// Everything after `offset` when not set
+    pub limit: Option<u32>,
+    pub offset: u32,
+}
+
+impl<S> ListOptions<S> {
+    /// Whether a YYYY-MM-DD date is between `since` and `until`
+    pub fn includes(&self, date: &str) -> bool {
+        let after_since = self
+            .since
+            .is_none_or(|since| date >= since.format("%Y-%m-%d").to_string().as_str());
+        let before_until = self
+            .until
+            .is_none_or(|until| date <= until.format("%Y-%m-%d").to_string().as_str());
+
+        after_since && before_until
+    }
+
+    /// The records on the page `offset` and `limit` describe
+    pub fn page<T>(&self, records: Vec<T>) -> Vec<T> {
+        records
+            .into_iter()
+            .skip(self.offset as usize)
+            .take(self.limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_includes_dates_in_range() {
+        let options = ListOptions {
+            since: NaiveDate::from_ymd_opt(2026, 1, 1),
+            until: NaiveDate::from_ymd_opt(2026, 1, 31),
+            ..ListOptions::<()>::default()
+        };
+
+        assert!(options.includes("2026-01-01"));
+        assert!(options.includes("2026-01-31"));
+        assert!(!options.includes("2025-12-31"));
+        assert!(!options.includes("2026-02-01"));
+        assert!(ListOptions::<()>::default().includes("1999-01-01"));
+    }
+
+    #[test]
+    fn test_page() {
+        let options = ListOptions {
+            limit: Some(2),
+            offset: 1,
+            ..ListOptions::<()>::default()
+        };
+
+        assert_eq!(options.page(vec![1, 2, 3, 4]), vec![2, 3]);
+        assert_eq!(options.page(vec![1]), Vec::<i32>::new());
+    }
+}
@@ -1,6 +1,10 @@
 use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, ValueEnum, PartialEq)]
+use super::contact::Contact;
+
+#[derive(Debug, Clone, ValueEnum, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Status {
     Archived,
     Cold,
@@ -19,6 +23,7 @@ impl Status {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Status> {
         match s {
             "hot" => Some(Status::Hot),
@@ -39,9 +44,61 @@ impl Status {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Where a mentee found us
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    #[value(name = "mentorcruise")]
+    MentorCruise,
+    Referral,
+    Website,
+    Other,
+}
+
+impl Source {
+    // convert enum variant to a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Source::MentorCruise => "mentorcruise",
+            Source::Referral => "referral",
+            Source::Website => "website",
+            Source::Other => "other",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Source> {
+        match s {
+            "mentorcruise" => Some(Source::MentorCruise),
+            "referral" => Some(Source::Referral),
+            "website" => Some(Source::Website),
+            "other" => Some(Source::Other),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Source::MentorCruise => "MentorCruise",
+            Source::Referral => "Referral",
+            Source::Website => "Website",
+            Source::Other => "Other",
+        }
+    }
+
+    pub fn variants() -> Vec<&'static str> {
+        vec![
+            Source::MentorCruise.as_str(),
+            Source::Referral.as_str(),
+            Source::Website.as_str(),
+            Source::Other.as_str(),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Mentee {
-    #[allow(dead_code)]
+    #[serde(skip)]
     pub id: u32,
     pub name: String,
     pub calls: u32,
@@ -50,6 +107,48 @@ pub struct Mentee {
     pub status: Status,
     pub payment_day: u32,
     pub notes: Option<String>,
+    pub source: Option<Source>,
+    /// Name of the mentee who referred them
+    pub referrer: Option<String>,
+}
+
+/// The fields needed to add a mentee without prompting for them
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NewMentee {
+    pub name: String,
+    pub calls: u32,
+    pub gross: u32,
+    pub net: u32,
+    pub status: Status,
+    pub payment_day: u32,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Defaults to referral when a referrer is given
+    #[serde(default)]
+    pub source: Option<Source>,
+    /// Name of an existing mentee who referred them
+    #[serde(default)]
+    pub referrer: Option<String>,
+}
+
+/// Changes to a mentee, any field left out stays as it is. `reason` and
+/// `status_date` are recorded in the status history and need a `status`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MenteeChanges {
+    pub name: Option<String>,
+    pub calls: Option<i32>,
+    pub gross: Option<i32>,
+    pub net: Option<i32>,
+    pub status: Option<Status>,
+    pub reason: Option<String>,
+    /// YYYY-MM-DD, defaults to today
+    pub status_date: Option<String>,
+    pub payment_day: Option<i32>,
+    pub notes: Option<String>,
+    pub source: Option<Source>,
+    pub referrer: Option<String>,
 }
 
 pub struct MenteeWithCounts {
@@ -60,6 +159,27 @@ pub struct MenteeWithCounts {
     pub remaining_calls: i64,
 }
 
+/// A mentee together with their contact methods, as written by `export`
+#[derive(Debug, Clone, Serialize)]
+pub struct MenteeWithContacts {
+    #[serde(flatten)]
+    pub mentee: Mentee,
+    pub contacts: Vec<Contact>,
+}
+
+/// A transition between statuses, `from_status` is empty when the mentee was added
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusChange {
+    #[serde(skip)]
+    pub id: u32,
+    #[serde(skip)]
+    pub mentee_id: i64,
+    pub date: String,
+    pub from_status: Option<Status>,
+    pub to_status: Status,
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct MenteeSummary {
     pub name: String,
@@ -67,6 +187,37 @@ pub struct MenteeSummary {
     pub remaining_calls: i32,
     pub status: Status,
     pub notes: Option<String>,
+    pub last_call_date: Option<String>,
+    pub days_since_last_call: Option<i64>,
+    pub avg_days_between_calls: Option<f64>,
+    pub calls_last_30_days: u32,
+    pub calls_last_90_days: u32,
+}
+
+impl MenteeSummary {
+    /// Days expected between calls given how many calls they have per month
+    pub fn expected_days_between_calls(&self) -> Option<f64> {
+        if self.calls_per_month == 0 {
+            None
+        } else {
+            Some(30.0 / self.calls_per_month as f64)
+        }
+    }
+
+    /// Whether an active mentee has gone longer without a call than their cadence
+    pub fn is_overdue(&self) -> bool {
+        if self.status == Status::Archived {
+            return false;
+        }
+
+        match (
+            self.days_since_last_call,
+            self.expected_days_between_calls(),
+        ) {
+            (Some(days), Some(expected)) => days as f64 > expected,
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -94,6 +245,37 @@ mod tests {
         assert_eq!(Status::from_str("hott"), None);
     }
 
+    fn summary(calls_per_month: u32, days_since_last_call: Option<i64>) -> MenteeSummary {
+        MenteeSummary {
+            calls_per_month,
+            days_since_last_call,
+            ..crate::fixtures::summary("dan")
+        }
+    }
+
+    #[test]
+    fn overdue_when_gap_exceeds_cadence() {
+        assert!(summary(2, Some(16)).is_overdue());
+        assert!(!summary(2, Some(15)).is_overdue());
+        assert!(!summary(0, Some(90)).is_overdue());
+        assert!(!summary(4, None).is_overdue());
+    }
+
+    #[test]
+    fn archived_mentees_are_never_overdue() {
+        let mut mentee = summary(4, Some(60));
+        mentee.status = Status::Archived;
+        assert!(!mentee.is_overdue());
+    }
+
+    #[test]
+    fn test_source_round_trips() {
+        for source in Source::variants() {
+            assert_eq!(Source::from_str(source).unwrap().as_str(), source);
+        }
+        assert_eq!(Source::from_str("mentor-cruise"), None);
+    }
+
     #[test]
     fn test_status_variants() {
         let expected = vec!["hot", "warm", "cold", "archived"];
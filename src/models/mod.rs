@@ -1,4 +1,14 @@
+pub mod agenda;
 pub mod call;
+pub mod capacity;
+pub mod contact;
+pub mod expense;
+pub mod goal;
+pub mod invoice;
+pub mod list;
 pub mod mentee;
 pub mod payment;
+pub mod report;
+pub mod statement;
+pub mod timeline;
 pub mod video;
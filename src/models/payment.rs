@@ -1,15 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
 pub struct Payment {
-    #[allow(dead_code)]
     pub id: u32,
-    #[allow(dead_code)]
     pub mentee_id: u32,
     pub date: String,
     pub amount: u32,
+    /// Empty for payments recorded before currencies were tracked
+    pub currency: Option<String>,
+    pub fee: u32,
+    pub source: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
 pub struct PaymentWithMenteeName {
     pub id: u32,
     pub mentee_name: String,
     pub date: String,
     pub amount: u32,
+    pub currency: Option<String>,
+    pub fee: u32,
+    pub source: Option<String>,
+}
+
+impl PaymentWithMenteeName {
+    pub fn currency_or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.currency.as_deref().unwrap_or(default)
+    }
+
+    /// What was left after the platform or processor fee
+    pub fn net(&self) -> i64 {
+        self.amount as i64 - self.fee as i64
+    }
+}
+
+/// The fields of a payment when it's recorded without prompting
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentInput {
+    pub date: String,
+    pub amount: u32,
+    /// Defaults to the configured currency
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub fee: u32,
+    #[serde(default)]
+    pub source: Option<String>,
 }
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// Mentees whose first payment was in `cohort` and how many of them paid in
+/// each following month, `retained[0]` being the cohort month itself
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CohortRow {
+    pub cohort: String,
+    pub size: u32,
+    pub retained: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChurnRow {
+    pub month: String,
+    pub active_at_start: u32,
+    pub churned: u32,
+}
+
+impl ChurnRow {
+    pub fn churn_rate(&self) -> f64 {
+        if self.active_at_start == 0 {
+            0.0
+        } else {
+            self.churned as f64 * 100.0 / self.active_at_start as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionReport {
+    pub cohorts: Vec<CohortRow>,
+    pub churn: Vec<ChurnRow>,
+    pub average_tenure_months: f64,
+    pub active_mentees: u32,
+    pub churned_mentees: u32,
+}
+
+/// Income grouped by `label`, a month (YYYY-MM) or payment source, and currency.
+/// Currencies are never added together. Expenses aren't tied to a payment
+/// source so they stay at zero in the per source rows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IncomeRow {
+    pub label: String,
+    pub currency: String,
+    pub payments: u32,
+    pub gross: u64,
+    pub fees: u64,
+    pub net: i64,
+    pub expenses: u64,
+    pub profit: i64,
+}
+
+/// Expenses grouped by category and currency
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExpenseRow {
+    pub category: String,
+    pub currency: String,
+    pub count: u32,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxReport {
+    pub year: i32,
+    pub start: String,
+    pub end: String,
+    pub months: Vec<IncomeRow>,
+    pub sources: Vec<IncomeRow>,
+    pub expense_categories: Vec<ExpenseRow>,
+    pub totals: Vec<IncomeRow>,
+}
+
+/// How quickly a mentee's video reviews were turned around. Only delivered
+/// videos with a received date count towards the days and on time figures.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TurnaroundRow {
+    pub mentee_name: String,
+    pub delivered: u32,
+    pub average_days: f64,
+    pub longest_days: i64,
+    /// Delivered videos that had a due date
+    pub with_due_date: u32,
+    pub on_time: u32,
+    pub outstanding: u32,
+}
+
+impl TurnaroundRow {
+    /// `None` when none of the delivered videos had a due date
+    pub fn on_time_rate(&self) -> Option<f64> {
+        if self.with_due_date == 0 {
+            None
+        } else {
+            Some(self.on_time as f64 * 100.0 / self.with_due_date as f64)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TurnaroundReport {
+    pub mentees: Vec<TurnaroundRow>,
+    pub overall: TurnaroundRow,
+}
+
+/// Mentees who found us through `source` and what they've been worth. Tenure
+/// is counted like in the retention report, only for mentees who paid.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SourceRow {
+    pub source: String,
+    pub mentees: u32,
+    /// Mentees who made at least one payment
+    pub paying_mentees: u32,
+    /// Net payments per currency
+    pub revenue: BTreeMap<String, i64>,
+    pub average_tenure_months: f64,
+}
+
+impl SourceRow {
+    pub fn conversion_rate(&self) -> f64 {
+        if self.mentees == 0 {
+            0.0
+        } else {
+            self.paying_mentees as f64 * 100.0 / self.mentees as f64
+        }
+    }
+}
+
+/// Net earned against the time spent on calls and delivered video reviews, for
+/// a mentee, a month (YYYY-MM) or everything
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RateRow {
+    pub label: String,
+    pub hours: f64,
+    /// Calls logged without a duration, counted at the configured default
+    pub untimed_calls: u32,
+    /// Net payments per currency
+    pub earned: BTreeMap<String, i64>,
+    /// Net earned per hour in each currency, empty when no time was spent
+    pub hourly_rate: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RatesReport {
+    /// Best paid per hour first
+    pub mentees: Vec<RateRow>,
+    pub months: Vec<RateRow>,
+    pub overall: RateRow,
+}
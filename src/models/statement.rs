@@ -0,0 +1,26 @@
+use serde::Serialize;
+
+/// One line of a statement. Payments credit calls, calls that weren't free debit one.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatementEntry {
+    pub date: String,
+    pub description: String,
+    pub amount: Option<u32>,
+    pub credit: u32,
+    pub debit: u32,
+    pub balance: i64,
+}
+
+/// A mentee's call balance over a period, ledger style
+#[derive(Debug, Clone, Serialize)]
+pub struct Statement {
+    pub mentee_name: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub opening_balance: i64,
+    pub entries: Vec<StatementEntry>,
+    pub closing_balance: i64,
+    pub total_paid: u32,
+    pub calls_credited: u32,
+    pub calls_used: u32,
+}
@@ -0,0 +1,33 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineEventKind {
+    // declared in the order events on the same day are listed
+    Status,
+    Payment,
+    Call,
+    Video,
+}
+
+impl TimelineEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimelineEventKind::Status => "status",
+            TimelineEventKind::Payment => "payment",
+            TimelineEventKind::Call => "call",
+            TimelineEventKind::Video => "video",
+        }
+    }
+}
+
+/// A single entry of a mentee's timeline with the call balance after it
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEvent {
+    pub date: String,
+    pub kind: TimelineEventKind,
+    pub record_id: i64,
+    pub description: String,
+    pub balance_change: i64,
+    pub balance: i64,
+}
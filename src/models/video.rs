@@ -1,3 +1,47 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Where a video analysis is in the review workflow
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoStatus {
+    Requested,
+    InProgress,
+    Delivered,
+}
+
+impl VideoStatus {
+    // convert enum variant to a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VideoStatus::Requested => "requested",
+            VideoStatus::InProgress => "in_progress",
+            VideoStatus::Delivered => "delivered",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<VideoStatus> {
+        match s {
+            "requested" => Some(VideoStatus::Requested),
+            "in_progress" => Some(VideoStatus::InProgress),
+            "delivered" => Some(VideoStatus::Delivered),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VideoStatus::Requested => "Requested",
+            VideoStatus::InProgress => "In Progress",
+            VideoStatus::Delivered => "Delivered",
+        }
+    }
+}
+
+/// `date` is the delivery date once delivered and the received date before that.
+/// `length` stays at 0 until the analysis is delivered.
+#[derive(Debug, Clone)]
 pub struct Video {
     #[allow(dead_code)]
     pub id: i64,
@@ -5,13 +49,70 @@ pub struct Video {
     pub date: String,
     pub length: u32,
     pub notes: String,
+    pub status: VideoStatus,
+    /// Not recorded for videos logged directly as delivered
+    pub received_date: Option<String>,
+    pub due_date: Option<String>,
+    pub source_url: Option<String>,
+    /// Absolute path to a local copy, with its size and SHA-256 when attached
+    pub file_path: Option<String>,
+    pub file_size: Option<i64>,
+    pub file_sha256: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct VideoWithMenteeName {
     pub id: i64,
     pub mentee_name: String,
     pub date: String,
     pub length: u32,
     pub notes: String,
+    pub status: VideoStatus,
+    pub received_date: Option<String>,
+    pub due_date: Option<String>,
+    pub source_url: Option<String>,
+    pub file_path: Option<String>,
+}
+
+fn delivered() -> VideoStatus {
+    VideoStatus::Delivered
+}
+
+/// The fields of a video when it's recorded without prompting. `date` follows
+/// the same rule as [`Video`], the received date defaults to it before delivery.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VideoInput {
+    pub date: String,
+    #[serde(default)]
+    pub length: u32,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default = "delivered")]
+    pub status: VideoStatus,
+    #[serde(default)]
+    pub received_date: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    #[serde(default)]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub file_path: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            VideoStatus::Requested,
+            VideoStatus::InProgress,
+            VideoStatus::Delivered,
+        ] {
+            assert_eq!(VideoStatus::from_str(status.as_str()), Some(status));
+        }
+        assert_eq!(VideoStatus::from_str("done"), None);
+    }
 }
@@ -1,66 +1,148 @@
 use crate::{
     constants,
-    models::call::{Call, CallWithMenteeName},
+    error::MenteeError,
+    models::{
+        call::{Call, CallWithMenteeName},
+        list::ListOptions,
+        mentee::Status,
+    },
+    CallSort,
 };
 use rusqlite::{params, Connection};
 
-pub struct CallRepository<'a> {
+use super::ListQuery;
+
+/// Where calls are kept
+pub trait CallRepository {
+    /// Inserts a call and returns its id
+    fn add_call(&self, call: Call) -> Result<i64, MenteeError>;
+
+    fn get_call_by_id(&self, id: u32) -> Result<Call, MenteeError>;
+
+    fn get_call_with_mentee_name(&self, id: u32) -> Result<CallWithMenteeName, MenteeError>;
+
+    fn update_call(
+        &self,
+        id: u32,
+        date: String,
+        notes: String,
+        free_call: bool,
+        duration: Option<u32>,
+    ) -> Result<usize, MenteeError>;
+
+    /// Calls of one mentee or everyone, `status` being the mentee's current status
+    fn get_all_calls(
+        &self,
+        mentee_id: Option<i64>,
+        status: Option<Status>,
+        options: &ListOptions<CallSort>,
+    ) -> Result<Vec<CallWithMenteeName>, MenteeError>;
+
+    /// Calls dated between `from` and `to` inclusive (YYYY-MM-DD), including
+    /// scheduled calls in the future
+    fn get_calls_between(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<CallWithMenteeName>, MenteeError>;
+
+    /// Delete a call by call id
+    fn delete_call(&self, call_id: u32) -> Result<usize, MenteeError>;
+}
+
+/// The default storage, the `calls` table
+pub struct SqliteCallRepository<'a> {
     conn: &'a Connection,
 }
 
-impl<'a> CallRepository<'a> {
+impl<'a> SqliteCallRepository<'a> {
     pub fn new(conn: &'a Connection) -> Self {
         Self { conn }
     }
+}
 
-    pub fn add_call(&self, call: Call) -> Result<usize, rusqlite::Error> {
+impl CallRepository for SqliteCallRepository<'_> {
+    fn add_call(&self, call: Call) -> Result<i64, MenteeError> {
         let sql = format!(
-            "INSERT INTO {} (mentee_id, date, notes, free_call) VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO {} (mentee_id, date, notes, free_call, duration) VALUES (?1, ?2, ?3, ?4, ?5)",
             constants::CALLS_TABLE
         );
 
         self.conn.execute(
             &sql,
-            params![call.mentee_id, call.date, call.notes, call.free_call],
-        )
+            params![
+                call.mentee_id,
+                call.date,
+                call.notes,
+                call.free_call,
+                call.duration
+            ],
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_call_by_id(&self, id: u32) -> Result<Call, rusqlite::Error> {
+    fn get_call_by_id(&self, id: u32) -> Result<Call, MenteeError> {
         let sql = format!(
-            "SELECT id, mentee_id, date, notes, free_call FROM {} WHERE id = ?1",
+            "SELECT id, mentee_id, date, notes, free_call, duration FROM {} WHERE id = ?1",
             constants::CALLS_TABLE
         );
 
-        self.conn.query_row(&sql, params![id], |row| {
+        Ok(self.conn.query_row(&sql, params![id], |row| {
             Ok(Call {
                 id: row.get(0)?,
                 mentee_id: row.get(1)?,
                 date: row.get(2)?,
                 notes: row.get(3)?,
                 free_call: row.get(4)?,
+                duration: row.get(5)?,
+            })
+        })?)
+    }
+
+    fn get_call_with_mentee_name(&self, id: u32) -> Result<CallWithMenteeName, MenteeError> {
+        let sql = format!(
+            "SELECT calls.id, mentees.name, calls.date, calls.notes, calls.free_call, calls.duration FROM {} JOIN {} ON calls.mentee_id = mentees.id WHERE calls.id = ?1",
+            constants::CALLS_TABLE,
+            constants::MENTEES_TABLE
+        );
+
+        Ok(self.conn.query_row(&sql, params![id], |row| {
+            Ok(CallWithMenteeName {
+                id: row.get(0)?,
+                mentee_name: row.get(1)?,
+                date: row.get(2)?,
+                notes: row.get(3)?,
+                free_call: row.get(4)?,
+                duration: row.get(5)?,
             })
-        })
+        })?)
     }
 
-    pub fn update_call(
+    fn update_call(
         &self,
         id: u32,
         date: String,
         notes: String,
         free_call: bool,
-    ) -> Result<usize, rusqlite::Error> {
+        duration: Option<u32>,
+    ) -> Result<usize, MenteeError> {
         let sql = format!(
-            "UPDATE {} SET date = ?1, notes = ?2, free_call = ?3 WHERE id = ?4",
+            "UPDATE {} SET date = ?1, notes = ?2, free_call = ?3, duration = ?4 WHERE id = ?5",
             constants::CALLS_TABLE
         );
 
-        self.conn.execute(&sql, params![date, notes, free_call, id])
+        Ok(self
+            .conn
+            .execute(&sql, params![date, notes, free_call, duration, id])?)
     }
 
-    pub fn get_all_calls(
+    fn get_all_calls(
         &self,
         mentee_id: Option<i64>,
-    ) -> Result<Vec<CallWithMenteeName>, rusqlite::Error> {
+        status: Option<Status>,
+        options: &ListOptions<CallSort>,
+    ) -> Result<Vec<CallWithMenteeName>, MenteeError> {
         let mut sql = format!(
             "
             SELECT 
@@ -68,7 +150,8 @@ impl<'a> CallRepository<'a> {
                 mentees.name AS mentee_name,
                 calls.date,
                 calls.notes,
-                calls.free_call
+                calls.free_call,
+                calls.duration
             FROM 
                 {}
             JOIN 
@@ -80,25 +163,41 @@ impl<'a> CallRepository<'a> {
             constants::MENTEES_TABLE
         );
 
-        let mut params: Vec<&dyn rusqlite::ToSql> = Vec::new();
-        let id_storage;
+        let mut query = ListQuery::new();
 
         if let Some(id) = mentee_id {
-            sql.push_str(" WHERE calls.mentee_id = ?1");
-            id_storage = id;
-            params.push(&id_storage);
+            query
+                .filter("calls.mentee_id = :mentee_id")
+                .bind(":mentee_id", id);
+        }
+
+        if let Some(status) = status {
+            query
+                .filter("mentees.status = :status")
+                .bind(":status", status.as_str());
         }
 
-        sql.push_str(" ORDER BY calls.date ASC");
+        query.dated("calls.date", options);
+
+        let sort_column = match options.sort {
+            CallSort::Date => "calls.date",
+            CallSort::Mentee => "mentees.name",
+        };
 
+        sql.push_str(&query.where_sql());
+        let order_sql = query.order_sql(sort_column, "calls.date, calls.id", options);
+        sql.push_str(&order_sql);
+
+        let params = query.params();
         let mut stmt = self.conn.prepare(&sql)?;
-        let call_iter = stmt.query_map(&params[..], |row| {
+        let call_iter = stmt.query_map(params.as_slice(), |row| {
             Ok(CallWithMenteeName {
                 id: row.get(0)?,
                 mentee_name: row.get(1)?,
                 date: row.get(2)?,
                 notes: row.get(3)?,
                 free_call: row.get(4)?,
+                duration: row.get(5)?,
             })
         })?;
 
@@ -110,11 +209,58 @@ impl<'a> CallRepository<'a> {
         Ok(calls)
     }
 
-    /// Delete a call by call id
-    pub fn delete_call(&self, call_id: u32) -> Result<usize, rusqlite::Error> {
+    fn get_calls_between(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<CallWithMenteeName>, MenteeError> {
+        let sql = format!(
+            "
+            SELECT
+                calls.id AS call_id,
+                mentees.name AS mentee_name,
+                calls.date,
+                calls.notes,
+                calls.free_call,
+                calls.duration
+            FROM
+                {}
+            JOIN
+                {}
+            ON
+                calls.mentee_id = mentees.id
+            WHERE
+                calls.date BETWEEN ?1 AND ?2
+            ORDER BY calls.date ASC, calls.id ASC
+            ",
+            constants::CALLS_TABLE,
+            constants::MENTEES_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let call_iter = stmt.query_map(params![from, to], |row| {
+            Ok(CallWithMenteeName {
+                id: row.get(0)?,
+                mentee_name: row.get(1)?,
+                date: row.get(2)?,
+                notes: row.get(3)?,
+                free_call: row.get(4)?,
+                duration: row.get(5)?,
+            })
+        })?;
+
+        let mut calls = Vec::new();
+        for call in call_iter {
+            calls.push(call?);
+        }
+
+        Ok(calls)
+    }
+
+    fn delete_call(&self, call_id: u32) -> Result<usize, MenteeError> {
         let sql = format!("DELETE FROM {} WHERE id = :call_id", constants::CALLS_TABLE);
 
         // TODO: change this
-        self.conn.execute(&sql, &[(":call_id", &call_id)])
+        Ok(self.conn.execute(&sql, &[(":call_id", &call_id)])?)
     }
 }
@@ -0,0 +1,82 @@
+use rusqlite::{params, Connection, Row};
+
+use crate::{
+    constants,
+    models::contact::{Contact, ContactKind},
+};
+
+pub struct ContactRepository<'a> {
+    conn: &'a Connection,
+}
+
+fn row_to_contact(row: &Row) -> Result<Contact, rusqlite::Error> {
+    let kind_str: String = row.get(2)?;
+    let kind = ContactKind::from_str(&kind_str).unwrap_or(ContactKind::Email);
+
+    Ok(Contact {
+        id: row.get(0)?,
+        mentee_id: row.get(1)?,
+        kind,
+        value: row.get(3)?,
+    })
+}
+
+impl<'a> ContactRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn add_contact(&self, contact: Contact) -> Result<usize, rusqlite::Error> {
+        let sql = format!(
+            "INSERT INTO {} (mentee_id, kind, value) VALUES (?1, ?2, ?3)",
+            constants::CONTACTS_TABLE
+        );
+
+        self.conn.execute(
+            &sql,
+            params![contact.mentee_id, contact.kind.as_str(), contact.value],
+        )
+    }
+
+    /// Fetches the contact methods of a single mentee, grouped by kind
+    pub fn get_contacts_for_mentee(&self, mentee_id: i64) -> Result<Vec<Contact>, rusqlite::Error> {
+        let sql = format!(
+            "SELECT id, mentee_id, kind, value FROM {} WHERE mentee_id = ?1 ORDER BY kind, id",
+            constants::CONTACTS_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let contact_iter = stmt.query_map(params![mentee_id], row_to_contact)?;
+
+        let mut contacts = Vec::new();
+        for contact in contact_iter {
+            contacts.push(contact?);
+        }
+
+        Ok(contacts)
+    }
+
+    /// Fetches every contact method, used when exporting all mentees
+    pub fn get_all_contacts(&self) -> Result<Vec<Contact>, rusqlite::Error> {
+        let sql = format!(
+            "SELECT id, mentee_id, kind, value FROM {} ORDER BY mentee_id, kind, id",
+            constants::CONTACTS_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let contact_iter = stmt.query_map([], row_to_contact)?;
+
+        let mut contacts = Vec::new();
+        for contact in contact_iter {
+            contacts.push(contact?);
+        }
+
+        Ok(contacts)
+    }
+
+    pub fn delete_contact(&self, contact_id: u32) -> Result<usize, rusqlite::Error> {
+        let sql = format!("DELETE FROM {} WHERE id = ?1", constants::CONTACTS_TABLE);
+
+        self.conn.execute(&sql, params![contact_id])
+    }
+}
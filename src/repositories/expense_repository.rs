@@ -0,0 +1,211 @@
+use rusqlite::{params, Connection, Row};
+
+use crate::{
+    constants,
+    models::{
+        expense::{Expense, ExpenseCategory, ExpenseWithMenteeName},
+        list::ListOptions,
+    },
+    ExpenseSort,
+};
+
+use super::ListQuery;
+
+fn category_from_row(row: &Row, index: usize) -> Result<ExpenseCategory, rusqlite::Error> {
+    let category: String = row.get(index)?;
+
+    Ok(ExpenseCategory::from_str(&category).unwrap_or(ExpenseCategory::Other))
+}
+
+pub struct ExpenseRepository<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> ExpenseRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn add_expense(&self, expense: &Expense) -> Result<usize, rusqlite::Error> {
+        let sql = format!(
+            "INSERT INTO {} (date, category, description, amount, currency, mentee_id, receipt_path)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            constants::EXPENSES_TABLE
+        );
+
+        self.conn.execute(
+            &sql,
+            params![
+                expense.date,
+                expense.category.as_str(),
+                expense.description,
+                expense.amount,
+                expense.currency,
+                expense.mentee_id,
+                expense.receipt_path
+            ],
+        )
+    }
+
+    pub fn get_expense_by_id(&self, expense_id: u32) -> Result<Expense, rusqlite::Error> {
+        let sql = format!(
+            "SELECT id, date, category, description, amount, currency, mentee_id, receipt_path
+            FROM {} WHERE id = ?1",
+            constants::EXPENSES_TABLE
+        );
+
+        self.conn.query_row(&sql, params![expense_id], |row| {
+            Ok(Expense {
+                id: row.get(0)?,
+                date: row.get(1)?,
+                category: category_from_row(row, 2)?,
+                description: row.get(3)?,
+                amount: row.get(4)?,
+                currency: row.get(5)?,
+                mentee_id: row.get(6)?,
+                receipt_path: row.get(7)?,
+            })
+        })
+    }
+
+    fn row_to_expense_with_mentee_name(
+        row: &Row,
+    ) -> Result<ExpenseWithMenteeName, rusqlite::Error> {
+        Ok(ExpenseWithMenteeName {
+            id: row.get(0)?,
+            date: row.get(1)?,
+            category: category_from_row(row, 2)?,
+            description: row.get(3)?,
+            amount: row.get(4)?,
+            currency: row.get(5)?,
+            mentee_name: row.get(6)?,
+            receipt_path: row.get(7)?,
+        })
+    }
+
+    fn select_with_mentee_name_sql() -> String {
+        format!(
+            "SELECT
+                expenses.id,
+                expenses.date,
+                expenses.category,
+                expenses.description,
+                expenses.amount,
+                expenses.currency,
+                mentees.name,
+                expenses.receipt_path
+            FROM
+                {}
+            LEFT JOIN
+                {}
+            ON
+                expenses.mentee_id = mentees.id
+            ",
+            constants::EXPENSES_TABLE,
+            constants::MENTEES_TABLE
+        )
+    }
+
+    pub fn get_all_expenses(
+        &self,
+        mentee_id: Option<i64>,
+        options: &ListOptions<ExpenseSort>,
+    ) -> Result<Vec<ExpenseWithMenteeName>, rusqlite::Error> {
+        let mut sql = Self::select_with_mentee_name_sql();
+        let mut query = ListQuery::new();
+
+        if let Some(id) = mentee_id {
+            query
+                .filter("expenses.mentee_id = :mentee_id")
+                .bind(":mentee_id", id);
+        }
+
+        query.dated("expenses.date", options);
+
+        let sort_column = match options.sort {
+            ExpenseSort::Date => "expenses.date",
+            ExpenseSort::Category => "expenses.category",
+            ExpenseSort::Amount => "expenses.amount",
+        };
+
+        sql.push_str(&query.where_sql());
+        let order_sql = query.order_sql(sort_column, "expenses.date, expenses.id", options);
+        sql.push_str(&order_sql);
+
+        let params = query.params();
+        let mut stmt = self.conn.prepare(&sql)?;
+        let expense_iter =
+            stmt.query_map(params.as_slice(), Self::row_to_expense_with_mentee_name)?;
+
+        let mut expenses = Vec::new();
+        for expense in expense_iter {
+            expenses.push(expense?);
+        }
+
+        Ok(expenses)
+    }
+
+    /// Expenses dated between `from` and `to` inclusive (YYYY-MM-DD)
+    pub fn get_expenses_between(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<ExpenseWithMenteeName>, rusqlite::Error> {
+        let sql = format!(
+            "{} WHERE expenses.date BETWEEN ?1 AND ?2 ORDER BY expenses.date ASC, expenses.id ASC",
+            Self::select_with_mentee_name_sql()
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let expense_iter =
+            stmt.query_map(params![from, to], Self::row_to_expense_with_mentee_name)?;
+
+        let mut expenses = Vec::new();
+        for expense in expense_iter {
+            expenses.push(expense?);
+        }
+
+        Ok(expenses)
+    }
+
+    /// Sum of the expenses in a month (YYYY-MM) paid in `currency`
+    pub fn get_expense_total(&self, month: &str, currency: &str) -> Result<i64, rusqlite::Error> {
+        let sql = format!(
+            "SELECT COALESCE(SUM(amount), 0) FROM {}
+            WHERE strftime('%Y-%m', date) = ?1 AND currency = ?2",
+            constants::EXPENSES_TABLE
+        );
+
+        self.conn
+            .query_row(&sql, params![month, currency], |row| row.get(0))
+    }
+
+    pub fn update_expense(&self, expense: &Expense) -> Result<usize, rusqlite::Error> {
+        let sql = format!(
+            "UPDATE {} SET date = ?1, category = ?2, description = ?3, amount = ?4, currency = ?5,
+                mentee_id = ?6, receipt_path = ?7
+            WHERE id = ?8",
+            constants::EXPENSES_TABLE
+        );
+
+        self.conn.execute(
+            &sql,
+            params![
+                expense.date,
+                expense.category.as_str(),
+                expense.description,
+                expense.amount,
+                expense.currency,
+                expense.mentee_id,
+                expense.receipt_path,
+                expense.id
+            ],
+        )
+    }
+
+    pub fn delete_expense(&self, expense_id: u32) -> Result<usize, rusqlite::Error> {
+        let sql = format!("DELETE FROM {} WHERE id = ?1", constants::EXPENSES_TABLE);
+
+        self.conn.execute(&sql, params![expense_id])
+    }
+}
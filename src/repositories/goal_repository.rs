@@ -0,0 +1,321 @@
+use rusqlite::{params, Connection};
+
+use crate::{
+    constants,
+    models::goal::{Goal, GoalStatus, GoalWithProgress, LinkedCall, Milestone},
+};
+
+pub struct GoalRepository<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> GoalRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn add_goal(&self, goal: Goal) -> Result<usize, rusqlite::Error> {
+        let sql = format!(
+            "INSERT INTO {} (mentee_id, title, target_date, status) VALUES (?1, ?2, ?3, ?4)",
+            constants::GOALS_TABLE
+        );
+
+        self.conn.execute(
+            &sql,
+            params![
+                goal.mentee_id,
+                goal.title,
+                goal.target_date,
+                goal.status.as_str()
+            ],
+        )
+    }
+
+    pub fn get_goal_by_id(&self, id: u32) -> Result<Goal, rusqlite::Error> {
+        let sql = format!(
+            "SELECT id, mentee_id, title, target_date, status FROM {} WHERE id = ?1",
+            constants::GOALS_TABLE
+        );
+
+        self.conn.query_row(&sql, params![id], |row| {
+            let status_str: String = row.get(4)?;
+
+            Ok(Goal {
+                id: row.get(0)?,
+                mentee_id: row.get(1)?,
+                title: row.get(2)?,
+                target_date: row.get(3)?,
+                status: GoalStatus::from_str(&status_str).unwrap_or(GoalStatus::Open),
+            })
+        })
+    }
+
+    pub fn update_goal(&self, goal: &Goal) -> Result<usize, rusqlite::Error> {
+        let sql = format!(
+            "UPDATE {} SET title = ?1, target_date = ?2, status = ?3 WHERE id = ?4",
+            constants::GOALS_TABLE
+        );
+
+        self.conn.execute(
+            &sql,
+            params![goal.title, goal.target_date, goal.status.as_str(), goal.id],
+        )
+    }
+
+    pub fn delete_goal(&self, id: u32) -> Result<usize, rusqlite::Error> {
+        let sql = format!("DELETE FROM {} WHERE id = ?1", constants::GOALS_TABLE);
+
+        self.conn.execute(&sql, params![id])
+    }
+
+    /// Fetches goals with milestone and linked call counts.
+    /// `open_only` hides achieved and abandoned goals, `active_only` hides archived mentees.
+    pub fn get_goals_with_progress(
+        &self,
+        mentee_id: Option<i64>,
+        open_only: bool,
+        active_only: bool,
+    ) -> Result<Vec<GoalWithProgress>, rusqlite::Error> {
+        let mut sql = format!(
+            "
+            SELECT
+                goals.id,
+                mentees.name,
+                goals.title,
+                goals.target_date,
+                goals.status,
+                (SELECT COUNT(*) FROM {milestones} WHERE milestones.goal_id = goals.id AND milestones.done = 1) AS milestones_done,
+                (SELECT COUNT(*) FROM {milestones} WHERE milestones.goal_id = goals.id) AS milestones_total,
+                (SELECT COUNT(*) FROM {call_goals} WHERE call_goals.goal_id = goals.id) AS linked_calls
+            FROM
+                {goals}
+            JOIN
+                {mentees}
+            ON
+                goals.mentee_id = mentees.id
+            WHERE 1 = 1
+            ",
+            milestones = constants::MILESTONES_TABLE,
+            call_goals = constants::CALL_GOALS_TABLE,
+            goals = constants::GOALS_TABLE,
+            mentees = constants::MENTEES_TABLE
+        );
+
+        let mut params: Vec<&dyn rusqlite::ToSql> = Vec::new();
+        let id_storage;
+
+        if let Some(id) = mentee_id {
+            sql.push_str(" AND goals.mentee_id = ?1");
+            id_storage = id;
+            params.push(&id_storage);
+        }
+
+        if open_only {
+            sql.push_str(" AND goals.status = 'open'");
+        }
+
+        if active_only {
+            sql.push_str(" AND mentees.status != 'archived'");
+        }
+
+        sql.push_str(
+            " ORDER BY goals.target_date IS NULL, goals.target_date ASC, mentees.name ASC",
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let goal_iter = stmt.query_map(&params[..], |row| {
+            let status_str: String = row.get(4)?;
+
+            Ok(GoalWithProgress {
+                id: row.get(0)?,
+                mentee_name: row.get(1)?,
+                title: row.get(2)?,
+                target_date: row.get(3)?,
+                status: GoalStatus::from_str(&status_str).unwrap_or(GoalStatus::Open),
+                milestones_done: row.get(5)?,
+                milestones_total: row.get(6)?,
+                linked_calls: row.get(7)?,
+            })
+        })?;
+
+        let mut goals = Vec::new();
+        for goal in goal_iter {
+            goals.push(goal?);
+        }
+
+        Ok(goals)
+    }
+
+    pub fn add_milestone(&self, goal_id: u32, title: &str) -> Result<usize, rusqlite::Error> {
+        let sql = format!(
+            "INSERT INTO {} (goal_id, title) VALUES (?1, ?2)",
+            constants::MILESTONES_TABLE
+        );
+
+        self.conn.execute(&sql, params![goal_id, title])
+    }
+
+    pub fn get_milestones(&self, goal_id: u32) -> Result<Vec<Milestone>, rusqlite::Error> {
+        let sql = format!(
+            "SELECT id, goal_id, title, done FROM {} WHERE goal_id = ?1 ORDER BY id ASC",
+            constants::MILESTONES_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let milestone_iter = stmt.query_map(params![goal_id], |row| {
+            Ok(Milestone {
+                id: row.get(0)?,
+                goal_id: row.get(1)?,
+                title: row.get(2)?,
+                done: row.get(3)?,
+            })
+        })?;
+
+        let mut milestones = Vec::new();
+        for milestone in milestone_iter {
+            milestones.push(milestone?);
+        }
+
+        Ok(milestones)
+    }
+
+    pub fn set_milestone_done(&self, id: u32, done: bool) -> Result<usize, rusqlite::Error> {
+        let sql = format!(
+            "UPDATE {} SET done = ?1 WHERE id = ?2",
+            constants::MILESTONES_TABLE
+        );
+
+        self.conn.execute(&sql, params![done, id])
+    }
+
+    pub fn delete_milestone(&self, id: u32) -> Result<usize, rusqlite::Error> {
+        let sql = format!("DELETE FROM {} WHERE id = ?1", constants::MILESTONES_TABLE);
+
+        self.conn.execute(&sql, params![id])
+    }
+
+    /// Records that a call advanced a goal, linking twice is a no-op
+    pub fn link_call(&self, call_id: i64, goal_id: u32) -> Result<usize, rusqlite::Error> {
+        let sql = format!(
+            "INSERT OR IGNORE INTO {} (call_id, goal_id) VALUES (?1, ?2)",
+            constants::CALL_GOALS_TABLE
+        );
+
+        self.conn.execute(&sql, params![call_id, goal_id])
+    }
+
+    pub fn unlink_call(&self, call_id: i64, goal_id: u32) -> Result<usize, rusqlite::Error> {
+        let sql = format!(
+            "DELETE FROM {} WHERE call_id = ?1 AND goal_id = ?2",
+            constants::CALL_GOALS_TABLE
+        );
+
+        self.conn.execute(&sql, params![call_id, goal_id])
+    }
+
+    /// Calls linked to a goal, oldest first
+    pub fn get_linked_calls(&self, goal_id: u32) -> Result<Vec<LinkedCall>, rusqlite::Error> {
+        let sql = format!(
+            "SELECT calls.id, calls.date FROM {} JOIN {} ON call_goals.call_id = calls.id WHERE call_goals.goal_id = ?1 ORDER BY calls.date ASC",
+            constants::CALL_GOALS_TABLE,
+            constants::CALLS_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let call_iter = stmt.query_map(params![goal_id], |row| {
+            Ok(LinkedCall {
+                call_id: row.get(0)?,
+                date: row.get(1)?,
+            })
+        })?;
+
+        let mut calls = Vec::new();
+        for call in call_iter {
+            calls.push(call?);
+        }
+
+        Ok(calls)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{
+            call::CallInput,
+            mentee::{NewMentee, Status},
+        },
+        Store,
+    };
+
+    /// A store with a goal for dan, one milestone and a call for each of dan and amy
+    fn store() -> Store {
+        let store = Store::open(":memory:").unwrap();
+        for name in ["dan", "amy"] {
+            store
+                .mentees()
+                .create_mentee(NewMentee {
+                    name: name.to_string(),
+                    calls: 2,
+                    gross: 200,
+                    net: 180,
+                    status: Status::Hot,
+                    payment_day: 5,
+                    notes: None,
+                    source: None,
+                    referrer: None,
+                })
+                .unwrap();
+            store
+                .calls()
+                .create_call(
+                    name,
+                    CallInput {
+                        date: "2026-03-02".to_string(),
+                        notes: None,
+                        free_call: false,
+                        duration: None,
+                        goal_ids: vec![],
+                    },
+                )
+                .unwrap();
+        }
+
+        let goals = GoalRepository::new(store.connection());
+        goals
+            .add_goal(Goal {
+                id: 0,
+                mentee_id: 1,
+                title: "Land a staff role".to_string(),
+                target_date: None,
+                status: GoalStatus::Open,
+            })
+            .unwrap();
+        goals.add_milestone(1, "Update CV").unwrap();
+
+        store
+    }
+
+    #[test]
+    fn links_calls_once_and_counts_progress() {
+        let store = store();
+        let goals = GoalRepository::new(store.connection());
+
+        assert_eq!(goals.link_call(1, 1).unwrap(), 1);
+        assert_eq!(goals.link_call(1, 1).unwrap(), 0);
+        assert_eq!(goals.set_milestone_done(1, true).unwrap(), 1);
+
+        let progress = &goals.get_goals_with_progress(Some(1), true, false).unwrap()[0];
+        assert_eq!(progress.linked_calls, 1);
+        assert_eq!(
+            (progress.milestones_done, progress.milestones_total),
+            (1, 1)
+        );
+        assert_eq!(goals.get_linked_calls(1).unwrap()[0].date, "2026-03-02");
+
+        assert_eq!(goals.unlink_call(1, 1).unwrap(), 1);
+        assert_eq!(goals.unlink_call(1, 1).unwrap(), 0);
+        assert_eq!(goals.set_milestone_done(99, true).unwrap(), 0);
+    }
+}
@@ -0,0 +1,151 @@
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::{config::IssuerConfig, constants, models::invoice::Invoice};
+
+pub struct InvoiceRepository<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> InvoiceRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    fn row_to_invoice(row: &Row) -> Result<Invoice, rusqlite::Error> {
+        Ok(Invoice {
+            id: row.get(0)?,
+            number: row.get(1)?,
+            payment_id: row.get(2)?,
+            mentee_name: row.get(3)?,
+            issue_date: row.get(4)?,
+            payment_date: row.get(5)?,
+            amount: row.get(6)?,
+            currency: row.get(7)?,
+            issuer_name: row.get(8)?,
+            issuer_address: row.get(9)?,
+            issuer_email: row.get(10)?,
+            issuer_tax_id: row.get(11)?,
+        })
+    }
+
+    fn select_sql() -> String {
+        format!(
+            "SELECT id, number, payment_id, mentee_name, issue_date, payment_date, amount,
+                currency, issuer_name, issuer_address, issuer_email, issuer_tax_id
+            FROM {}",
+            constants::INVOICES_TABLE
+        )
+    }
+
+    /// Issues an invoice for each payment, in order, inside one transaction.
+    ///
+    /// Numbers continue from the highest issued so far and nothing is committed
+    /// unless every invoice is written, so the sequence never has gaps.
+    /// `default_currency` is used for payments recorded without one.
+    pub fn create_invoices(
+        &self,
+        payment_ids: &[u32],
+        issue_date: &str,
+        default_currency: &str,
+        issuer: &IssuerConfig,
+    ) -> Result<Vec<u32>, rusqlite::Error> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        let mut number: u32 = tx.query_row(
+            &format!(
+                "SELECT COALESCE(MAX(number), 0) FROM {}",
+                constants::INVOICES_TABLE
+            ),
+            [],
+            |row| row.get(0),
+        )?;
+
+        let insert_sql = format!(
+            "INSERT INTO {} (number, payment_id, mentee_name, issue_date, payment_date, amount,
+                currency, issuer_name, issuer_address, issuer_email, issuer_tax_id)
+            SELECT ?1, payments.id, mentees.name, ?2, payments.date, payments.amount,
+                COALESCE(payments.currency, ?3), ?4, ?5, ?6, ?7
+            FROM {} AS payments
+            JOIN {} AS mentees ON payments.mentee_id = mentees.id
+            WHERE payments.id = ?8",
+            constants::INVOICES_TABLE,
+            constants::PAYMENTS_TABLE,
+            constants::MENTEES_TABLE
+        );
+
+        let mut numbers = Vec::new();
+        for payment_id in payment_ids {
+            number += 1;
+
+            let inserted = tx.execute(
+                &insert_sql,
+                params![
+                    number,
+                    issue_date,
+                    default_currency,
+                    issuer.name,
+                    issuer.address,
+                    issuer.email,
+                    issuer.tax_id,
+                    payment_id
+                ],
+            )?;
+
+            if inserted == 0 {
+                return Err(rusqlite::Error::QueryReturnedNoRows);
+            }
+
+            numbers.push(number);
+        }
+
+        tx.commit()?;
+
+        Ok(numbers)
+    }
+
+    /// Payments in a month (YYYY-MM) that haven't been invoiced, oldest first
+    pub fn get_uninvoiced_payment_ids(&self, month: &str) -> Result<Vec<u32>, rusqlite::Error> {
+        let sql = format!(
+            "SELECT payments.id
+            FROM {} AS payments
+            LEFT JOIN {} AS invoices ON invoices.payment_id = payments.id
+            WHERE strftime('%Y-%m', payments.date) = ?1 AND invoices.id IS NULL
+            ORDER BY payments.date, payments.id",
+            constants::PAYMENTS_TABLE,
+            constants::INVOICES_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let ids = stmt.query_map(params![month], |row| row.get(0))?;
+
+        ids.collect()
+    }
+
+    pub fn get_invoice_by_number(&self, number: u32) -> Result<Option<Invoice>, rusqlite::Error> {
+        let sql = format!("{} WHERE number = ?1", Self::select_sql());
+
+        self.conn
+            .query_row(&sql, params![number], Self::row_to_invoice)
+            .optional()
+    }
+
+    pub fn get_invoice_by_payment_id(
+        &self,
+        payment_id: u32,
+    ) -> Result<Option<Invoice>, rusqlite::Error> {
+        let sql = format!("{} WHERE payment_id = ?1", Self::select_sql());
+
+        self.conn
+            .query_row(&sql, params![payment_id], Self::row_to_invoice)
+            .optional()
+    }
+
+    pub fn get_all_invoices(&self) -> Result<Vec<Invoice>, rusqlite::Error> {
+        let sql = format!("{} ORDER BY number ASC", Self::select_sql());
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let invoices = stmt.query_map([], Self::row_to_invoice)?;
+
+        invoices.collect()
+    }
+}
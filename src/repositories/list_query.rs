@@ -0,0 +1,88 @@
+use rusqlite::ToSql;
+
+use crate::models::list::ListOptions;
+
+/// Collects the filters of a `get_all_*` query and the values they're bound
+/// to, then writes the WHERE, ORDER BY and LIMIT clauses for it
+pub(crate) struct ListQuery {
+    conditions: Vec<String>,
+    params: Vec<(&'static str, Box<dyn ToSql>)>,
+}
+
+impl ListQuery {
+    pub(crate) fn new() -> Self {
+        Self {
+            conditions: Vec::new(),
+            params: Vec::new(),
+        }
+    }
+
+    /// Only keeps rows where `condition` holds, e.g. `calls.mentee_id = :mentee_id`
+    pub(crate) fn filter(&mut self, condition: impl Into<String>) -> &mut Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
+    pub(crate) fn bind(&mut self, name: &'static str, value: impl ToSql + 'static) -> &mut Self {
+        self.params.push((name, Box::new(value)));
+        self
+    }
+
+    /// Only keeps rows where the YYYY-MM-DD `column` is between `since` and `until`
+    pub(crate) fn dated<S>(&mut self, column: &str, options: &ListOptions<S>) -> &mut Self {
+        if let Some(since) = options.since {
+            self.filter(format!("{} >= :since", column))
+                .bind(":since", since.format("%Y-%m-%d").to_string());
+        }
+
+        if let Some(until) = options.until {
+            self.filter(format!("{} <= :until", column))
+                .bind(":until", until.format("%Y-%m-%d").to_string());
+        }
+
+        self
+    }
+
+    /// The WHERE clause, empty when nothing is filtered
+    pub(crate) fn where_sql(&self) -> String {
+        if self.conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", self.conditions.join(" AND "))
+        }
+    }
+
+    /// Orders by `sort_column` with empty values last, then by `tiebreak` so
+    /// pages don't overlap, and adds the page's LIMIT and OFFSET. Only the sort
+    /// column follows `desc`.
+    pub(crate) fn order_sql<S>(
+        &mut self,
+        sort_column: &str,
+        tiebreak: &str,
+        options: &ListOptions<S>,
+    ) -> String {
+        let direction = if options.desc { "DESC" } else { "ASC" };
+        let mut sql = format!(
+            " ORDER BY {sort} IS NULL, {sort} {direction}, {tiebreak}",
+            sort = sort_column,
+            direction = direction,
+            tiebreak = tiebreak
+        );
+
+        if options.limit.is_some() || options.offset > 0 {
+            // SQLite only takes an OFFSET after a LIMIT, -1 is no limit
+            sql.push_str(" LIMIT :limit OFFSET :offset");
+            self.bind(":limit", options.limit.map_or(-1, i64::from))
+                .bind(":offset", options.offset);
+        }
+
+        sql
+    }
+
+    pub(crate) fn params(&self) -> Vec<(&str, &dyn ToSql)> {
+        self.params
+            .iter()
+            .map(|(name, value)| (*name, value.as_ref()))
+            .collect()
+    }
+}
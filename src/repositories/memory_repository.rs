@@ -0,0 +1,1142 @@
+use std::{cell::RefCell, cmp::Ordering, rc::Rc};
+
+use chrono::{Days, NaiveDate};
+
+use crate::{
+    error::MenteeError,
+    models::{
+        agenda::PaymentSchedule,
+        call::{Call, CallWithMenteeName},
+        list::ListOptions,
+        mentee::{Mentee, MenteeSummary, MenteeWithCounts, Status, StatusChange},
+        payment::{Payment, PaymentWithMenteeName},
+        video::{Video, VideoStatus, VideoWithMenteeName},
+    },
+    utils::dates::today,
+    CallSort, CountOptions, MenteeSort, PaymentSort, UpdateMentee, VideoSort,
+};
+
+use super::{CallRepository, MenteeRepository, PaymentRepository, VideoRepository};
+
+#[derive(Default, Clone)]
+struct Tables {
+    mentees: Vec<Mentee>,
+    status_changes: Vec<StatusChange>,
+    calls: Vec<Call>,
+    payments: Vec<Payment>,
+    videos: Vec<Video>,
+}
+
+impl Tables {
+    fn mentee_name(&self, mentee_id: i64) -> String {
+        self.mentees
+            .iter()
+            .find(|mentee| mentee.id as i64 == mentee_id)
+            .map(|mentee| mentee.name.clone())
+            .unwrap_or_default()
+    }
+
+    fn has_status(&self, mentee_id: i64, status: Option<&Status>) -> bool {
+        status.is_none_or(|status| {
+            self.mentees
+                .iter()
+                .any(|mentee| mentee.id as i64 == mentee_id && mentee.status == *status)
+        })
+    }
+
+    /// The mentee's status on `as_of`, following the same rules as the SQLite
+    /// backend. `None` means they hadn't been added yet.
+    fn status_as_of(&self, mentee: &Mentee, as_of: &str) -> Option<Status> {
+        let mut history: Vec<&StatusChange> = self
+            .status_changes
+            .iter()
+            .filter(|change| change.mentee_id == mentee.id as i64)
+            .collect();
+        history.sort_by(|a, b| (&a.date, a.id).cmp(&(&b.date, b.id)));
+
+        match history
+            .iter()
+            .rev()
+            .find(|change| change.date.as_str() <= as_of)
+        {
+            Some(change) => Some(change.to_status.clone()),
+            None => match history.first() {
+                Some(first) => first.from_status.clone(),
+                None => Some(mentee.status.clone()),
+            },
+        }
+    }
+
+    fn status_for(&self, mentee: &Mentee, as_of: Option<&str>) -> Option<Status> {
+        match as_of {
+            Some(date) => self.status_as_of(mentee, date),
+            None => Some(mentee.status.clone()),
+        }
+    }
+
+    fn call_with_mentee_name(&self, call: &Call) -> CallWithMenteeName {
+        CallWithMenteeName {
+            id: call.id,
+            mentee_name: self.mentee_name(call.mentee_id),
+            date: call.date.clone(),
+            notes: call.notes.clone(),
+            free_call: call.free_call,
+            duration: call.duration,
+        }
+    }
+
+    fn payment_with_mentee_name(&self, payment: &Payment) -> PaymentWithMenteeName {
+        PaymentWithMenteeName {
+            id: payment.id,
+            mentee_name: self.mentee_name(payment.mentee_id as i64),
+            date: payment.date.clone(),
+            amount: payment.amount,
+            currency: payment.currency.clone(),
+            fee: payment.fee,
+            source: payment.source.clone(),
+        }
+    }
+
+    fn video_with_mentee_name(&self, video: &Video) -> VideoWithMenteeName {
+        VideoWithMenteeName {
+            id: video.id,
+            mentee_name: self.mentee_name(video.mentee_id),
+            date: video.date.clone(),
+            length: video.length,
+            notes: video.notes.clone(),
+            status: video.status,
+            received_date: video.received_date.clone(),
+            due_date: video.due_date.clone(),
+            source_url: video.source_url.clone(),
+            file_path: video.file_path.clone(),
+        }
+    }
+
+    fn summarise(&self, mentee: &Mentee, status: Status, as_of: Option<&str>) -> MenteeSummary {
+        let reference_date = as_of.map(str::to_string).unwrap_or_else(today);
+        let counted = |date: &str| as_of.is_none_or(|as_of| date <= as_of);
+
+        let calls: Vec<&Call> = self
+            .calls
+            .iter()
+            .filter(|call| call.mentee_id == mentee.id as i64 && counted(&call.date))
+            .collect();
+        let payment_count = self
+            .payments
+            .iter()
+            .filter(|payment| payment.mentee_id == mentee.id && counted(&payment.date))
+            .count();
+
+        // scheduled calls in the future don't count as contact
+        let past_dates: Vec<&str> = calls
+            .iter()
+            .map(|call| call.date.as_str())
+            .filter(|date| *date <= reference_date.as_str())
+            .collect();
+        let first_call = past_dates.iter().min().and_then(|date| parse_date(date));
+        let last_call_date = past_dates.iter().max().map(|date| date.to_string());
+        let last_call = last_call_date.as_deref().and_then(parse_date);
+        let reference = parse_date(&reference_date);
+
+        let days_since_last_call = match (reference, last_call) {
+            (Some(reference), Some(last_call)) => Some((reference - last_call).num_days()),
+            _ => None,
+        };
+
+        let avg_days_between_calls = match (first_call, last_call) {
+            (Some(first_call), Some(last_call)) if past_dates.len() > 1 => {
+                Some((last_call - first_call).num_days() as f64 / (past_dates.len() - 1) as f64)
+            }
+            _ => None,
+        };
+
+        let calls_since = |days: u64| {
+            let since = reference
+                .and_then(|reference| reference.checked_sub_days(Days::new(days)))
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+
+            past_dates
+                .iter()
+                .filter(|date| **date > since.as_str())
+                .count() as u32
+        };
+
+        MenteeSummary {
+            name: mentee.name.clone(),
+            calls_per_month: mentee.calls,
+            remaining_calls: (mentee.calls as i64 * payment_count as i64 - calls.len() as i64)
+                as i32,
+            status,
+            notes: mentee.notes.clone(),
+            last_call_date,
+            days_since_last_call,
+            avg_days_between_calls,
+            calls_last_30_days: calls_since(30),
+            calls_last_90_days: calls_since(90),
+        }
+    }
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+fn status_rank(status: &Status) -> u8 {
+    match status {
+        Status::Hot => 1,
+        Status::Warm => 2,
+        Status::Cold => 3,
+        Status::Archived => 4,
+    }
+}
+
+/// Orders two values of the sort column, empty ones last whatever the direction
+fn compare<T: PartialOrd>(a: Option<T>, b: Option<T>, desc: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            if desc {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn amount(value: i32, field: &str) -> Result<u32, MenteeError> {
+    u32::try_from(value)
+        .map_err(|_| MenteeError::InvalidInput(format!("{} can't be negative", field)))
+}
+
+fn next_id(ids: impl Iterator<Item = i64>) -> i64 {
+    ids.max().unwrap_or(0) + 1
+}
+
+/// Keeps mentees, calls, payments and videos in memory, nothing is saved.
+///
+/// Clones share the same records, so one repository can back several services.
+/// Useful for tests, and as a model of what a new storage backend has to do.
+#[derive(Clone, Default)]
+pub struct InMemoryRepository {
+    tables: Rc<RefCell<Tables>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl MenteeRepository for InMemoryRepository {
+    fn in_transaction(
+        &self,
+        write: &mut dyn FnMut() -> Result<(), MenteeError>,
+    ) -> Result<(), MenteeError> {
+        let snapshot = self.tables.borrow().clone();
+
+        write().inspect_err(|_| *self.tables.borrow_mut() = snapshot)
+    }
+
+    fn get_mentee_id(&self, name: &str) -> Result<Option<i64>, MenteeError> {
+        Ok(self.get_mentee(name)?.map(|mentee| mentee.id as i64))
+    }
+
+    fn get_mentee_name(&self, id: i64) -> Result<Option<String>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        Ok(tables
+            .mentees
+            .iter()
+            .find(|mentee| mentee.id as i64 == id)
+            .map(|mentee| mentee.name.clone()))
+    }
+
+    fn get_mentee(&self, name: &str) -> Result<Option<Mentee>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        Ok(tables
+            .mentees
+            .iter()
+            .find(|mentee| mentee.name == name)
+            .cloned())
+    }
+
+    fn add_mentee(&self, mentee: Mentee) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+
+        if tables.mentees.iter().any(|other| other.name == mentee.name) {
+            return Err(MenteeError::UniqueViolation(mentee.name));
+        }
+
+        let id = next_id(tables.mentees.iter().map(|mentee| mentee.id as i64)) as u32;
+        tables.mentees.push(Mentee { id, ..mentee });
+
+        Ok(1)
+    }
+
+    fn get_mentee_with_counts(&self, name: &str) -> Result<MenteeWithCounts, MenteeError> {
+        let mentee = self
+            .get_mentee(name)?
+            .ok_or_else(|| MenteeError::NotFound(format!("Mentee with name {}", name)))?;
+        let tables = self.tables.borrow();
+        let mentee_id = mentee.id as i64;
+
+        let calls: Vec<&Call> = tables
+            .calls
+            .iter()
+            .filter(|call| call.mentee_id == mentee_id)
+            .collect();
+        let paid_calls = calls.iter().filter(|call| !call.free_call).count() as i64;
+        let payment_count = tables
+            .payments
+            .iter()
+            .filter(|payment| payment.mentee_id as i64 == mentee_id)
+            .count() as i64;
+        let video_count = tables
+            .videos
+            .iter()
+            .filter(|video| video.mentee_id == mentee_id)
+            .count() as i64;
+
+        Ok(MenteeWithCounts {
+            remaining_calls: mentee.calls as i64 * payment_count - paid_calls,
+            call_count: calls.len() as i64,
+            payment_count,
+            video_count,
+            mentee,
+        })
+    }
+
+    fn get_all_mentees(
+        &self,
+        show_all: bool,
+        as_of: Option<&str>,
+        status: Option<Status>,
+        options: &ListOptions<MenteeSort>,
+    ) -> Result<Vec<MenteeSummary>, MenteeError> {
+        let tables = self.tables.borrow();
+        let desc = options.desc;
+        let dated = options.since.is_some() || options.until.is_some();
+
+        let mut mentees: Vec<MenteeSummary> = tables
+            .mentees
+            .iter()
+            .filter_map(|mentee| {
+                let effective = tables.status_for(mentee, as_of)?;
+                let listed = match &status {
+                    Some(status) => effective == *status,
+                    None => show_all || effective != Status::Archived,
+                };
+                listed.then(|| tables.summarise(mentee, effective, as_of))
+            })
+            .filter(|summary| match summary.last_call_date.as_deref() {
+                Some(date) => options.includes(date),
+                None => !dated,
+            })
+            .collect();
+
+        mentees.sort_by(|a, b| {
+            let ordering = match options.sort {
+                MenteeSort::Status => compare(
+                    Some(status_rank(&a.status)),
+                    Some(status_rank(&b.status)),
+                    desc,
+                ),
+                MenteeSort::Name => compare(Some(&a.name), Some(&b.name), desc),
+                MenteeSort::Remaining => {
+                    compare(Some(a.remaining_calls), Some(b.remaining_calls), desc)
+                }
+                MenteeSort::LastCall => {
+                    compare(a.last_call_date.as_ref(), b.last_call_date.as_ref(), desc)
+                }
+                MenteeSort::DaysSince => {
+                    compare(a.days_since_last_call, b.days_since_last_call, desc)
+                }
+                MenteeSort::Cadence => {
+                    compare(a.avg_days_between_calls, b.avg_days_between_calls, desc)
+                }
+                MenteeSort::Calls30 => {
+                    compare(Some(a.calls_last_30_days), Some(b.calls_last_30_days), desc)
+                }
+                MenteeSort::Calls90 => {
+                    compare(Some(a.calls_last_90_days), Some(b.calls_last_90_days), desc)
+                }
+            };
+
+            ordering
+                .then_with(|| status_rank(&a.status).cmp(&status_rank(&b.status)))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Ok(options.page(mentees))
+    }
+
+    fn get_payment_schedules(&self) -> Result<Vec<PaymentSchedule>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mut schedules: Vec<PaymentSchedule> = tables
+            .mentees
+            .iter()
+            .filter(|mentee| mentee.status != Status::Archived)
+            .map(|mentee| PaymentSchedule {
+                mentee_name: mentee.name.clone(),
+                payment_day: mentee.payment_day,
+                last_payment_date: tables
+                    .payments
+                    .iter()
+                    .filter(|payment| payment.mentee_id == mentee.id)
+                    .map(|payment| payment.date.clone())
+                    .max(),
+            })
+            .collect();
+        schedules.sort_by(|a, b| a.mentee_name.cmp(&b.mentee_name));
+
+        Ok(schedules)
+    }
+
+    fn record_status_change(&self, change: StatusChange) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+
+        let id = next_id(tables.status_changes.iter().map(|change| change.id as i64)) as u32;
+        tables.status_changes.push(StatusChange { id, ..change });
+
+        Ok(1)
+    }
+
+    fn get_status_history(&self, mentee_id: i64) -> Result<Vec<StatusChange>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mut changes: Vec<StatusChange> = tables
+            .status_changes
+            .iter()
+            .filter(|change| change.mentee_id == mentee_id)
+            .cloned()
+            .collect();
+        changes.sort_by(|a, b| (&a.date, a.id).cmp(&(&b.date, b.id)));
+
+        Ok(changes)
+    }
+
+    fn get_mentees(&self, show_all: bool) -> Result<Vec<Mentee>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mut mentees: Vec<Mentee> = tables
+            .mentees
+            .iter()
+            .filter(|mentee| show_all || mentee.status != Status::Archived)
+            .cloned()
+            .collect();
+        mentees.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(mentees)
+    }
+
+    fn delete_mentee_by_id(&self, id: i64) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+        let before = tables.mentees.len();
+
+        let name = tables.mentee_name(id);
+
+        // the same records the foreign keys cascade to in SQLite
+        tables.mentees.retain(|mentee| mentee.id as i64 != id);
+        for mentee in tables.mentees.iter_mut() {
+            if mentee.referrer.as_deref() == Some(name.as_str()) {
+                mentee.referrer = None;
+            }
+        }
+        tables
+            .status_changes
+            .retain(|change| change.mentee_id != id);
+        tables.calls.retain(|call| call.mentee_id != id);
+        tables
+            .payments
+            .retain(|payment| payment.mentee_id as i64 != id);
+        tables.videos.retain(|video| video.mentee_id != id);
+
+        Ok(before - tables.mentees.len())
+    }
+
+    fn get_mentee_count(
+        &self,
+        count_type: Option<CountOptions>,
+        as_of: Option<&str>,
+    ) -> Result<i64, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mentees: Vec<&Mentee> = tables
+            .mentees
+            .iter()
+            .filter(|mentee| {
+                tables
+                    .status_for(mentee, as_of)
+                    .is_some_and(|status| status != Status::Archived)
+            })
+            .collect();
+
+        let sum = |field: fn(&Mentee) -> u32| -> i64 {
+            mentees.iter().map(|mentee| field(mentee) as i64).sum()
+        };
+
+        Ok(match count_type {
+            Some(CountOptions::Calls) => sum(|mentee| mentee.calls),
+            Some(CountOptions::Gross) => sum(|mentee| mentee.gross),
+            Some(CountOptions::Net) => sum(|mentee| mentee.net),
+            Some(CountOptions::NetPerCall) if !mentees.is_empty() => {
+                let total: i64 = mentees
+                    .iter()
+                    .map(|mentee| match mentee.calls {
+                        0 => mentee.net as i64,
+                        calls => (mentee.net / calls) as i64,
+                    })
+                    .sum();
+
+                (total as f64 / mentees.len() as f64) as i64
+            }
+            Some(CountOptions::NetPerCall) => 0,
+            _ => mentees.len() as i64,
+        })
+    }
+
+    fn update_mentee(&self, update_args: &UpdateMentee) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+
+        if let Some(new_name) = &update_args.new_name {
+            if tables
+                .mentees
+                .iter()
+                .any(|mentee| mentee.name == *new_name && mentee.name != update_args.name)
+            {
+                return Err(MenteeError::UniqueViolation(new_name.clone()));
+            }
+        }
+
+        // checked before anything changes, so a bad value leaves the mentee as it was
+        let calls = update_args
+            .calls
+            .map(|calls| amount(calls, "calls"))
+            .transpose()?;
+        let gross = update_args
+            .gross
+            .map(|gross| amount(gross, "gross"))
+            .transpose()?;
+        let net = update_args.net.map(|net| amount(net, "net")).transpose()?;
+        let payment_day = update_args
+            .payment_day
+            .map(|payment_day| amount(payment_day, "payment day"))
+            .transpose()?;
+
+        let Some(mentee) = tables
+            .mentees
+            .iter_mut()
+            .find(|mentee| mentee.name == update_args.name)
+        else {
+            return Ok(0);
+        };
+
+        if let Some(new_name) = &update_args.new_name {
+            mentee.name = new_name.clone();
+        }
+        if let Some(source) = update_args.source {
+            mentee.source = Some(source);
+        }
+        if let Some(referrer) = &update_args.referrer {
+            mentee.referrer = Some(referrer.clone());
+        }
+        if let Some(calls) = calls {
+            mentee.calls = calls;
+        }
+        if let Some(gross) = gross {
+            mentee.gross = gross;
+        }
+        if let Some(net) = net {
+            mentee.net = net;
+        }
+        if let Some(status) = &update_args.status {
+            mentee.status = status.clone();
+        }
+        if let Some(payment_day) = payment_day {
+            mentee.payment_day = payment_day;
+        }
+        if let Some(notes) = &update_args.notes {
+            mentee.notes = Some(notes.clone());
+        }
+
+        // referrers are kept by id in SQLite, so they follow a rename
+        if let Some(new_name) = &update_args.new_name {
+            for other in tables.mentees.iter_mut() {
+                if other.referrer.as_deref() == Some(update_args.name.as_str()) {
+                    other.referrer = Some(new_name.clone());
+                }
+            }
+        }
+
+        Ok(1)
+    }
+}
+
+impl CallRepository for InMemoryRepository {
+    fn add_call(&self, call: Call) -> Result<i64, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+
+        let id = next_id(tables.calls.iter().map(|call| call.id as i64));
+        tables.calls.push(Call {
+            id: id as u32,
+            ..call
+        });
+
+        Ok(id)
+    }
+
+    fn get_call_by_id(&self, id: u32) -> Result<Call, MenteeError> {
+        let tables = self.tables.borrow();
+
+        tables
+            .calls
+            .iter()
+            .find(|call| call.id == id)
+            .cloned()
+            .ok_or_else(|| MenteeError::NotFound(format!("Call with ID {}", id)))
+    }
+
+    fn get_call_with_mentee_name(&self, id: u32) -> Result<CallWithMenteeName, MenteeError> {
+        let call = self.get_call_by_id(id)?;
+
+        Ok(self.tables.borrow().call_with_mentee_name(&call))
+    }
+
+    fn update_call(
+        &self,
+        id: u32,
+        date: String,
+        notes: String,
+        free_call: bool,
+        duration: Option<u32>,
+    ) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+
+        match tables.calls.iter_mut().find(|call| call.id == id) {
+            Some(call) => {
+                call.date = date;
+                call.notes = Some(notes);
+                call.free_call = free_call;
+                call.duration = duration;
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn get_all_calls(
+        &self,
+        mentee_id: Option<i64>,
+        status: Option<Status>,
+        options: &ListOptions<CallSort>,
+    ) -> Result<Vec<CallWithMenteeName>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mut calls: Vec<CallWithMenteeName> = tables
+            .calls
+            .iter()
+            .filter(|call| {
+                mentee_id.is_none_or(|id| call.mentee_id == id)
+                    && tables.has_status(call.mentee_id, status.as_ref())
+                    && options.includes(&call.date)
+            })
+            .map(|call| tables.call_with_mentee_name(call))
+            .collect();
+
+        calls.sort_by(|a, b| {
+            let ordering = match options.sort {
+                CallSort::Date => compare(Some(&a.date), Some(&b.date), options.desc),
+                CallSort::Mentee => {
+                    compare(Some(&a.mentee_name), Some(&b.mentee_name), options.desc)
+                }
+            };
+
+            ordering.then_with(|| (&a.date, a.id).cmp(&(&b.date, b.id)))
+        });
+
+        Ok(options.page(calls))
+    }
+
+    fn get_calls_between(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<CallWithMenteeName>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mut calls: Vec<&Call> = tables
+            .calls
+            .iter()
+            .filter(|call| call.date.as_str() >= from && call.date.as_str() <= to)
+            .collect();
+        calls.sort_by(|a, b| (&a.date, a.id).cmp(&(&b.date, b.id)));
+
+        Ok(calls
+            .into_iter()
+            .map(|call| tables.call_with_mentee_name(call))
+            .collect())
+    }
+
+    fn delete_call(&self, call_id: u32) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+        let before = tables.calls.len();
+
+        tables.calls.retain(|call| call.id != call_id);
+
+        Ok(before - tables.calls.len())
+    }
+}
+
+impl PaymentRepository for InMemoryRepository {
+    fn add_payment(&self, payment: &Payment) -> Result<i64, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+
+        let id = next_id(tables.payments.iter().map(|payment| payment.id as i64));
+        tables.payments.push(Payment {
+            id: id as u32,
+            ..payment.clone()
+        });
+
+        Ok(id)
+    }
+
+    fn get_payment_by_id(&self, id: u32) -> Result<Payment, MenteeError> {
+        let tables = self.tables.borrow();
+
+        tables
+            .payments
+            .iter()
+            .find(|payment| payment.id == id)
+            .cloned()
+            .ok_or_else(|| MenteeError::NotFound(format!("Payment with id {}", id)))
+    }
+
+    fn get_payment_with_mentee_name(&self, id: u32) -> Result<PaymentWithMenteeName, MenteeError> {
+        let payment = self.get_payment_by_id(id)?;
+
+        Ok(self.tables.borrow().payment_with_mentee_name(&payment))
+    }
+
+    fn get_all_payments(
+        &self,
+        mentee_id: Option<i64>,
+        status: Option<Status>,
+        options: &ListOptions<PaymentSort>,
+    ) -> Result<Vec<PaymentWithMenteeName>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mut payments: Vec<PaymentWithMenteeName> = tables
+            .payments
+            .iter()
+            .filter(|payment| {
+                mentee_id.is_none_or(|id| payment.mentee_id as i64 == id)
+                    && tables.has_status(payment.mentee_id as i64, status.as_ref())
+                    && options.includes(&payment.date)
+            })
+            .map(|payment| tables.payment_with_mentee_name(payment))
+            .collect();
+
+        payments.sort_by(|a, b| {
+            let ordering = match options.sort {
+                PaymentSort::Date => compare(Some(&a.date), Some(&b.date), options.desc),
+                PaymentSort::Mentee => {
+                    compare(Some(&a.mentee_name), Some(&b.mentee_name), options.desc)
+                }
+                PaymentSort::Amount => compare(Some(a.amount), Some(b.amount), options.desc),
+                PaymentSort::Net => compare(Some(a.net()), Some(b.net()), options.desc),
+            };
+
+            ordering.then_with(|| (&a.date, a.id).cmp(&(&b.date, b.id)))
+        });
+
+        Ok(options.page(payments))
+    }
+
+    fn get_payments_between(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<PaymentWithMenteeName>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mut payments: Vec<&Payment> = tables
+            .payments
+            .iter()
+            .filter(|payment| payment.date.as_str() >= from && payment.date.as_str() <= to)
+            .collect();
+        payments.sort_by(|a, b| (&a.date, a.id).cmp(&(&b.date, b.id)));
+
+        Ok(payments
+            .into_iter()
+            .map(|payment| tables.payment_with_mentee_name(payment))
+            .collect())
+    }
+
+    fn update_payment(&self, payment: &Payment) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+
+        match tables
+            .payments
+            .iter_mut()
+            .find(|existing| existing.id == payment.id)
+        {
+            Some(existing) => {
+                *existing = Payment {
+                    mentee_id: existing.mentee_id,
+                    ..payment.clone()
+                };
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn delete_payment(&self, payment_id: u32) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+        let before = tables.payments.len();
+
+        tables.payments.retain(|payment| payment.id != payment_id);
+
+        Ok(before - tables.payments.len())
+    }
+}
+
+impl VideoRepository for InMemoryRepository {
+    fn add_video(&self, video: Video) -> Result<i64, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+
+        let id = next_id(tables.videos.iter().map(|video| video.id));
+        tables.videos.push(Video { id, ..video });
+
+        Ok(id)
+    }
+
+    fn get_video_by_id(&self, video_id: u32) -> Result<Video, MenteeError> {
+        let tables = self.tables.borrow();
+
+        tables
+            .videos
+            .iter()
+            .find(|video| video.id == video_id as i64)
+            .cloned()
+            .ok_or_else(|| MenteeError::NotFound(format!("Video with id of {}", video_id)))
+    }
+
+    fn get_video_with_mentee_name(
+        &self,
+        video_id: u32,
+    ) -> Result<VideoWithMenteeName, MenteeError> {
+        let video = self.get_video_by_id(video_id)?;
+
+        Ok(self.tables.borrow().video_with_mentee_name(&video))
+    }
+
+    fn get_all_videos(
+        &self,
+        mentee_id: Option<i64>,
+        status: Option<VideoStatus>,
+        options: &ListOptions<VideoSort>,
+    ) -> Result<Vec<VideoWithMenteeName>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mut videos: Vec<VideoWithMenteeName> = tables
+            .videos
+            .iter()
+            .filter(|video| {
+                mentee_id.is_none_or(|id| video.mentee_id == id)
+                    && status.is_none_or(|status| video.status == status)
+                    && options.includes(&video.date)
+            })
+            .map(|video| tables.video_with_mentee_name(video))
+            .collect();
+
+        // the order videos move through the review
+        let stage = |status: VideoStatus| match status {
+            VideoStatus::Requested => 1,
+            VideoStatus::InProgress => 2,
+            VideoStatus::Delivered => 3,
+        };
+
+        videos.sort_by(|a, b| {
+            let ordering = match options.sort {
+                VideoSort::Date => compare(Some(&a.date), Some(&b.date), options.desc),
+                VideoSort::Mentee => {
+                    compare(Some(&a.mentee_name), Some(&b.mentee_name), options.desc)
+                }
+                VideoSort::Status => {
+                    compare(Some(stage(a.status)), Some(stage(b.status)), options.desc)
+                }
+                VideoSort::Due => compare(a.due_date.as_ref(), b.due_date.as_ref(), options.desc),
+                VideoSort::Length => compare(Some(a.length), Some(b.length), options.desc),
+            };
+
+            ordering.then_with(|| (&a.date, a.id).cmp(&(&b.date, b.id)))
+        });
+
+        Ok(options.page(videos))
+    }
+
+    fn get_outstanding_videos(&self) -> Result<Vec<VideoWithMenteeName>, MenteeError> {
+        let tables = self.tables.borrow();
+
+        let mut videos: Vec<&Video> = tables
+            .videos
+            .iter()
+            .filter(|video| video.status != VideoStatus::Delivered)
+            .collect();
+        videos.sort_by(|a, b| {
+            compare(a.due_date.as_ref(), b.due_date.as_ref(), false)
+                .then_with(|| (&a.date, a.id).cmp(&(&b.date, b.id)))
+        });
+
+        Ok(videos
+            .into_iter()
+            .map(|video| tables.video_with_mentee_name(video))
+            .collect())
+    }
+
+    fn update_video(&self, video: &Video) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+
+        match tables
+            .videos
+            .iter_mut()
+            .find(|existing| existing.id == video.id)
+        {
+            Some(existing) => {
+                *existing = Video {
+                    mentee_id: existing.mentee_id,
+                    ..video.clone()
+                };
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn delete_video(&self, video_id: u32) -> Result<usize, MenteeError> {
+        let mut tables = self.tables.borrow_mut();
+        let before = tables.videos.len();
+
+        tables.videos.retain(|video| video.id != video_id as i64);
+
+        Ok(before - tables.videos.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_mentee(repo: &InMemoryRepository, name: &str, status: Status) -> i64 {
+        repo.add_mentee(Mentee {
+            id: 0,
+            name: name.to_string(),
+            calls: 2,
+            gross: 200,
+            net: 180,
+            status,
+            payment_day: 5,
+            notes: None,
+            source: None,
+            referrer: None,
+        })
+        .unwrap();
+
+        repo.get_mentee_id(name).unwrap().unwrap()
+    }
+
+    fn add_call(repo: &InMemoryRepository, mentee_id: i64, date: &str, free_call: bool) {
+        repo.add_call(Call {
+            id: 0,
+            mentee_id,
+            date: date.to_string(),
+            notes: None,
+            free_call,
+            duration: None,
+        })
+        .unwrap();
+    }
+
+    fn add_payment(repo: &InMemoryRepository, mentee_id: i64, date: &str) {
+        repo.add_payment(&Payment {
+            id: 0,
+            mentee_id: mentee_id as u32,
+            date: date.to_string(),
+            amount: 200,
+            currency: None,
+            fee: 20,
+            source: None,
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn rejected_update_changes_nothing() {
+        let repo = InMemoryRepository::new();
+        add_mentee(&repo, "dan", Status::Warm);
+
+        let result = repo.update_mentee(&UpdateMentee {
+            name: "dan".to_string(),
+            new_name: Some("daniel".to_string()),
+            calls: Some(4),
+            status: Some(Status::Hot),
+            reason: None,
+            status_date: None,
+            payment_day: None,
+            gross: None,
+            net: Some(-1),
+            notes: None,
+            edit_notes: false,
+            source: None,
+            referrer: None,
+            strict: false,
+        });
+
+        assert!(matches!(result, Err(MenteeError::InvalidInput(_))));
+        let dan = repo.get_mentee("dan").unwrap().unwrap();
+        assert_eq!((dan.calls, dan.status), (2, Status::Warm));
+    }
+
+    #[test]
+    fn rolls_back_a_failed_transaction() {
+        let repo = InMemoryRepository::new();
+        add_mentee(&repo, "amy", Status::Warm);
+
+        let result = repo.in_transaction(&mut || {
+            add_mentee(&repo, "dan", Status::Hot);
+            Err(MenteeError::InvalidInput("failed".to_string()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(repo.get_mentee_id("dan").unwrap(), None);
+        assert!(repo.get_mentee_id("amy").unwrap().is_some());
+    }
+
+    #[test]
+    fn summarises_mentees_as_of_a_date() {
+        let repo = InMemoryRepository::new();
+        let dan = add_mentee(&repo, "dan page", Status::Warm);
+        let amy = add_mentee(&repo, "amy", Status::Hot);
+        add_mentee(&repo, "bob", Status::Archived);
+
+        add_payment(&repo, dan, "2026-03-01");
+        add_call(&repo, dan, "2026-03-02", false);
+        add_call(&repo, dan, "2026-03-12", true);
+        add_call(&repo, dan, "2026-04-02", false);
+
+        let summaries = repo
+            .get_all_mentees(
+                false,
+                Some("2026-03-20"),
+                None,
+                &ListOptions {
+                    sort: MenteeSort::LastCall,
+                    ..ListOptions::default()
+                },
+            )
+            .unwrap();
+
+        let names: Vec<&str> = summaries.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["dan page", "amy"]);
+
+        let dan_summary = &summaries[0];
+        assert_eq!(dan_summary.remaining_calls, 0);
+        assert_eq!(dan_summary.last_call_date.as_deref(), Some("2026-03-12"));
+        assert_eq!(dan_summary.days_since_last_call, Some(8));
+        assert_eq!(dan_summary.avg_days_between_calls, Some(10.0));
+        assert_eq!(dan_summary.calls_last_30_days, 2);
+
+        // free calls don't use up the calls they paid for
+        let counts = repo.get_mentee_with_counts("dan page").unwrap();
+        assert_eq!(counts.call_count, 3);
+        assert_eq!(counts.remaining_calls, 0);
+
+        repo.delete_mentee_by_id(amy).unwrap();
+        assert_eq!(repo.get_mentee_count(None, None).unwrap(), 1);
+    }
+
+    #[test]
+    fn reconstructs_status_from_history() {
+        let repo = InMemoryRepository::new();
+        let dan = add_mentee(&repo, "dan page", Status::Cold);
+
+        for (date, from_status, to_status) in [
+            ("2026-01-10", None, Status::Hot),
+            ("2026-03-01", Some(Status::Hot), Status::Cold),
+        ] {
+            repo.record_status_change(StatusChange {
+                id: 0,
+                mentee_id: dan,
+                date: date.to_string(),
+                from_status,
+                to_status,
+                reason: None,
+            })
+            .unwrap();
+        }
+
+        let status_on = |date| {
+            repo.get_all_mentees(true, Some(date), None, &ListOptions::default())
+                .unwrap()
+                .first()
+                .map(|mentee| mentee.status.clone())
+        };
+
+        assert_eq!(status_on("2026-01-01"), None);
+        assert_eq!(status_on("2026-02-01"), Some(Status::Hot));
+        assert_eq!(status_on("2026-03-01"), Some(Status::Cold));
+    }
+
+    #[test]
+    fn filters_sorts_and_pages_lists() {
+        let repo = InMemoryRepository::new();
+        let dan = add_mentee(&repo, "dan page", Status::Hot);
+        let amy = add_mentee(&repo, "amy", Status::Archived);
+
+        for date in ["2026-01-05", "2026-02-05", "2026-03-05"] {
+            add_call(&repo, dan, date, false);
+        }
+        add_call(&repo, amy, "2026-02-10", false);
+
+        let options = ListOptions {
+            since: NaiveDate::from_ymd_opt(2026, 2, 1),
+            sort: CallSort::Date,
+            desc: true,
+            limit: Some(2),
+            ..ListOptions::default()
+        };
+        let dates = |calls: Vec<CallWithMenteeName>| -> Vec<String> {
+            calls.into_iter().map(|call| call.date).collect()
+        };
+
+        let calls = repo.get_all_calls(None, None, &options).unwrap();
+        assert_eq!(dates(calls), vec!["2026-03-05", "2026-02-10"]);
+
+        let calls = repo
+            .get_all_calls(None, Some(Status::Hot), &options)
+            .unwrap();
+        assert_eq!(dates(calls), vec!["2026-03-05", "2026-02-05"]);
+
+        let offset = ListOptions {
+            offset: 2,
+            ..options
+        };
+        let calls = repo.get_all_calls(None, None, &offset).unwrap();
+        assert_eq!(dates(calls), vec!["2026-02-05"]);
+
+        // asking for a status shows archived mentees without --all
+        let archived = repo
+            .get_all_mentees(false, None, Some(Status::Archived), &ListOptions::default())
+            .unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].name, "amy");
+    }
+}
@@ -2,83 +2,200 @@ use rusqlite::{params, Connection, OptionalExtension};
 
 use crate::{
     constants,
-    models::mentee::{Mentee, MenteeSummary, MenteeWithCounts, Status},
-    CountOptions, UpdateMentee,
+    error::MenteeError,
+    models::{
+        agenda::PaymentSchedule,
+        list::ListOptions,
+        mentee::{Mentee, MenteeSummary, MenteeWithCounts, Source, Status, StatusChange},
+    },
+    CountOptions, MenteeSort, UpdateMentee,
 };
 
-pub struct MenteeRepository<'a> {
+use super::ListQuery;
+
+/// SQL expression for a mentee's status on the `:as_of` date. Uses the latest
+/// transition on or before the date, otherwise the status the first later
+/// transition moved away from. NULL means the mentee hadn't been added yet and
+/// mentees without any history fall back to their current status.
+fn status_as_of_sql() -> String {
+    format!(
+        "CASE
+            WHEN EXISTS (SELECT 1 FROM {table} sc WHERE sc.mentee_id = mentees.id AND sc.date <= :as_of)
+                THEN (SELECT sc.to_status FROM {table} sc WHERE sc.mentee_id = mentees.id AND sc.date <= :as_of ORDER BY sc.date DESC, sc.id DESC LIMIT 1)
+            WHEN EXISTS (SELECT 1 FROM {table} sc WHERE sc.mentee_id = mentees.id)
+                THEN (SELECT sc.from_status FROM {table} sc WHERE sc.mentee_id = mentees.id ORDER BY sc.date ASC, sc.id ASC LIMIT 1)
+            ELSE mentees.status
+        END",
+        table = constants::STATUS_CHANGES_TABLE
+    )
+}
+
+/// Columns read by [`mentee_from_row`], with the referrer looked up by id
+const MENTEE_COLUMNS: &str = "mentees.id, mentees.name, mentees.calls, mentees.gross, mentees.net, mentees.status, mentees.payment_day, mentees.notes, mentees.source,
+    (SELECT referrers.name FROM mentees AS referrers WHERE referrers.id = mentees.referrer_id)";
+
+fn mentee_from_row(row: &rusqlite::Row) -> rusqlite::Result<Mentee> {
+    let status_str: String = row.get(5)?;
+    let source: Option<String> = row.get(8)?;
+
+    Ok(Mentee {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        calls: row.get(2)?,
+        gross: row.get(3)?,
+        net: row.get(4)?,
+        status: Status::from_str(&status_str).unwrap_or(Status::Warm),
+        payment_day: row.get(6)?,
+        notes: row.get(7)?,
+        source: source.as_deref().and_then(Source::from_str),
+        referrer: row.get(9)?,
+    })
+}
+
+/// Where mentees and their status history are kept. Names are unique, adding
+/// or renaming to a taken name fails with [`MenteeError::UniqueViolation`].
+pub trait MenteeRepository {
+    /// Fetches a mentee's ID by name
+    fn get_mentee_id(&self, name: &str) -> Result<Option<i64>, MenteeError>;
+
+    /// Fetches a mentee's name by ID
+    fn get_mentee_name(&self, id: i64) -> Result<Option<String>, MenteeError>;
+
+    fn get_mentee(&self, name: &str) -> Result<Option<Mentee>, MenteeError>;
+
+    fn add_mentee(&self, mentee: Mentee) -> Result<usize, MenteeError>;
+
+    fn get_mentee_with_counts(&self, name: &str) -> Result<MenteeWithCounts, MenteeError>;
+
+    /// Summaries of the mentees, `since` and `until` apply to their last call.
+    /// Archived mentees are left out unless `show_all` or `status` asks for them.
+    fn get_all_mentees(
+        &self,
+        show_all: bool,
+        as_of: Option<&str>,
+        status: Option<Status>,
+        options: &ListOptions<MenteeSort>,
+    ) -> Result<Vec<MenteeSummary>, MenteeError>;
+
+    /// Payment day and latest payment of every mentee who isn't archived
+    fn get_payment_schedules(&self) -> Result<Vec<PaymentSchedule>, MenteeError>;
+
+    fn record_status_change(&self, change: StatusChange) -> Result<usize, MenteeError>;
+
+    /// Fetches a mentee's status transitions, oldest first
+    fn get_status_history(&self, mentee_id: i64) -> Result<Vec<StatusChange>, MenteeError>;
+
+    /// Fetches the full record of every mentee, used when exporting
+    fn get_mentees(&self, show_all: bool) -> Result<Vec<Mentee>, MenteeError>;
+
+    fn delete_mentee_by_id(&self, id: i64) -> Result<usize, MenteeError>;
+
+    fn get_mentee_count(
+        &self,
+        count_type: Option<CountOptions>,
+        as_of: Option<&str>,
+    ) -> Result<i64, MenteeError>;
+
+    fn update_mentee(&self, update_args: &UpdateMentee) -> Result<usize, MenteeError>;
+
+    /// Runs `write` so that either every change it makes is saved, or none
+    /// are when it fails
+    fn in_transaction(
+        &self,
+        write: &mut dyn FnMut() -> Result<(), MenteeError>,
+    ) -> Result<(), MenteeError>;
+}
+
+/// The default storage, the `mentees` and `status_changes` tables
+pub struct SqliteMenteeRepository<'a> {
     conn: &'a Connection,
 }
 
-impl<'a> MenteeRepository<'a> {
+impl<'a> SqliteMenteeRepository<'a> {
     pub fn new(conn: &'a Connection) -> Self {
         Self { conn }
     }
+}
 
-    /// Fetches a mentee's ID by name
-    pub fn get_mentee_id(&self, name: &str) -> Result<Option<i64>, rusqlite::Error> {
+/// Turns SQLite's unique constraint failure on the name column into the error
+/// every backend reports for a name that's already taken
+fn name_taken(err: rusqlite::Error, name: &str) -> MenteeError {
+    match err {
+        rusqlite::Error::SqliteFailure(ref failure, _) if failure.extended_code == 2067 => {
+            MenteeError::UniqueViolation(name.to_string())
+        }
+        err => MenteeError::from(err),
+    }
+}
+
+impl MenteeRepository for SqliteMenteeRepository<'_> {
+    fn get_mentee_id(&self, name: &str) -> Result<Option<i64>, MenteeError> {
         let sql = format!(
             "SELECT id FROM {} WHERE name = ?1 LIMIT 1",
             constants::MENTEES_TABLE
         );
 
-        self.conn
+        Ok(self
+            .conn
             .query_row(&sql, params![name], |row| row.get(0))
-            .optional()
+            .optional()?)
     }
 
-    pub fn get_mentee(&self, name: &str) -> Result<Option<Mentee>, rusqlite::Error> {
+    fn get_mentee_name(&self, id: i64) -> Result<Option<String>, MenteeError> {
         let sql = format!(
-            "SELECT * FROM {} WHERE name = ?1 LIMIT 1",
+            "SELECT name FROM {} WHERE id = ?1",
             constants::MENTEES_TABLE
         );
 
-        self.conn
-            .query_row(&sql, params![name], |row| {
-                let status_str: String = row.get(5)?;
-                let status = Status::from_str(&status_str).unwrap_or(Status::Warm);
-
-                Ok(Mentee {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    calls: row.get(2)?,
-                    gross: row.get(3)?,
-                    net: row.get(4)?,
-                    status,
-                    payment_day: row.get(6)?,
-                    notes: row.get(7)?,
-                })
-            })
-            .optional()
+        Ok(self
+            .conn
+            .query_row(&sql, params![id], |row| row.get(0))
+            .optional()?)
     }
 
-    pub fn add_mentee(&self, mentee: Mentee) -> Result<usize, rusqlite::Error> {
+    fn get_mentee(&self, name: &str) -> Result<Option<Mentee>, MenteeError> {
         let sql = format!(
-            "INSERT INTO {} (name, calls, gross, net, status, payment_day, notes) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)", 
-            constants::MENTEES_TABLE);
+            "SELECT {} FROM {} WHERE name = ?1 LIMIT 1",
+            MENTEE_COLUMNS,
+            constants::MENTEES_TABLE
+        );
 
-        self.conn.execute(
-            &sql,
-            params![
-                mentee.name,
-                mentee.calls,
-                mentee.gross,
-                mentee.net,
-                mentee.status.as_str(),
-                mentee.payment_day,
-                mentee.notes
-            ],
-        )
+        Ok(self
+            .conn
+            .query_row(&sql, params![name], mentee_from_row)
+            .optional()?)
     }
 
-    pub fn get_mentee_with_counts(
-        &self,
-        name: &String,
-    ) -> Result<MenteeWithCounts, rusqlite::Error> {
+    fn add_mentee(&self, mentee: Mentee) -> Result<usize, MenteeError> {
+        let sql = format!(
+            "INSERT INTO {table} (name, calls, gross, net, status, payment_day, notes, source, referrer_id)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, (SELECT id FROM {table} WHERE name = ?9))",
+            table = constants::MENTEES_TABLE
+        );
+
+        self.conn
+            .execute(
+                &sql,
+                params![
+                    mentee.name,
+                    mentee.calls,
+                    mentee.gross,
+                    mentee.net,
+                    mentee.status.as_str(),
+                    mentee.payment_day,
+                    mentee.notes,
+                    mentee.source.as_ref().map(Source::as_str),
+                    mentee.referrer
+                ],
+            )
+            .map_err(|err| name_taken(err, &mentee.name))
+    }
+
+    fn get_mentee_with_counts(&self, name: &str) -> Result<MenteeWithCounts, MenteeError> {
         let sql = format!(
             "
             SELECT 
-                mentees.*,
+                {},
                 COALESCE(COUNT(DISTINCT calls.id), 0) AS call_count, 
                 COALESCE(COUNT(DISTINCT payments.id), 0) AS payment_count,
                 COALESCE(COUNT(DISTINCT videos.id), 0) AS video_count,
@@ -92,83 +209,149 @@ impl<'a> MenteeRepository<'a> {
             LEFT JOIN 
                 {} ON videos.mentee_id = mentees.id
             WHERE 
-                name = ?
+                mentees.name = ?
             GROUP BY
                 mentees.id
             ",
+            MENTEE_COLUMNS,
             constants::MENTEES_TABLE,
             constants::CALLS_TABLE,
             constants::PAYMENTS_TABLE,
             constants::VIDEOS_TABLE
         );
 
-        self.conn.query_row(&sql, params![name], |row| {
-            let status_str: String = row.get(5)?;
-            let status = Status::from_str(&status_str).unwrap_or(Status::Warm);
-
+        Ok(self.conn.query_row(&sql, params![name], |row| {
             Ok(MenteeWithCounts {
-                mentee: Mentee {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    calls: row.get(2)?,
-                    gross: row.get(3)?,
-                    net: row.get(4)?,
-                    status,
-                    payment_day: row.get(6)?,
-                    notes: row.get(7)?,
-                },
-                call_count: row.get(8)?,
-                payment_count: row.get(9)?,
-                video_count: row.get(10)?,
-                remaining_calls: row.get(11)?,
+                mentee: mentee_from_row(row)?,
+                call_count: row.get(10)?,
+                payment_count: row.get(11)?,
+                video_count: row.get(12)?,
+                remaining_calls: row.get(13)?,
             })
-        })
+        })?)
     }
 
-    pub fn get_all_mentees(&self, show_all: bool) -> Result<Vec<MenteeSummary>, rusqlite::Error> {
-        let mut sql = format!(
+    fn get_all_mentees(
+        &self,
+        show_all: bool,
+        as_of: Option<&str>,
+        status: Option<Status>,
+        options: &ListOptions<MenteeSort>,
+    ) -> Result<Vec<MenteeSummary>, MenteeError> {
+        // when looking back in time, status comes from the history and only the
+        // calls and payments made by then count towards the remaining calls
+        let (status_sql, date_filter, reference_date) = match as_of {
+            Some(_) => (
+                status_as_of_sql(),
+                (" AND calls.date <= :as_of", " AND payments.date <= :as_of"),
+                ":as_of",
+            ),
+            None => (
+                "mentees.status".to_string(),
+                ("", ""),
+                "date('now', 'localtime')",
+            ),
+        };
+
+        // scheduled calls in the future don't count as contact
+        let past_call_date = format!(
+            "CASE WHEN calls.date <= {} THEN calls.date END",
+            reference_date
+        );
+        let past_call_id = format!(
+            "CASE WHEN calls.date <= {} THEN calls.id END",
+            reference_date
+        );
+
+        let inner_sql = format!(
             "
             SELECT 
                 mentees.id,
                 mentees.name,
                 mentees.calls,
                 (mentees.calls * COALESCE(COUNT(DISTINCT payments.id), 0)) - COALESCE(COUNT(DISTINCT calls.id), 0) AS remaining_calls,
-                mentees.status,
-                mentees.notes
+                {status} AS effective_status,
+                mentees.notes,
+                MAX({past_call_date}) AS last_call_date,
+                CAST(julianday({reference_date}) - julianday(MAX({past_call_date})) AS INTEGER) AS days_since_last_call,
+                CASE WHEN COUNT(DISTINCT {past_call_id}) > 1
+                    THEN (julianday(MAX({past_call_date})) - julianday(MIN({past_call_date}))) / (COUNT(DISTINCT {past_call_id}) - 1)
+                END AS avg_days_between_calls,
+                COUNT(DISTINCT CASE WHEN calls.date > date({reference_date}, '-30 days') THEN {past_call_id} END) AS calls_last_30_days,
+                COUNT(DISTINCT CASE WHEN calls.date > date({reference_date}, '-90 days') THEN {past_call_id} END) AS calls_last_90_days
             FROM 
-                {}
+                {mentees}
             LEFT JOIN
-                {} ON calls.mentee_id = mentees.id
+                {calls} ON calls.mentee_id = mentees.id{calls_filter}
             LEFT JOIN 
-                {} ON payments.mentee_id = mentees.id
+                {payments} ON payments.mentee_id = mentees.id{payments_filter}
+            WHERE
+                effective_status IS NOT NULL
             ",
-            constants::MENTEES_TABLE,
-            constants::CALLS_TABLE,
-            constants::PAYMENTS_TABLE
+            status = status_sql,
+            past_call_date = past_call_date,
+            past_call_id = past_call_id,
+            reference_date = reference_date,
+            mentees = constants::MENTEES_TABLE,
+            calls = constants::CALLS_TABLE,
+            calls_filter = date_filter.0,
+            payments = constants::PAYMENTS_TABLE,
+            payments_filter = date_filter.1
         );
 
-        if !show_all {
-            sql = format!("{} WHERE status != 'archived'", sql)
+        // filtering and sorting the summaries lets them use the computed columns
+        let mut query = ListQuery::new();
+
+        match status {
+            Some(status) => {
+                query
+                    .filter("effective_status = :status")
+                    .bind(":status", status.as_str());
+            }
+            None if !show_all => {
+                query.filter("effective_status != 'archived'");
+            }
+            None => {}
         }
 
-        sql = format!(
-            "{} 
-            GROUP BY
-                mentees.id
-            ORDER BY 
-                CASE status 
+        query.dated("last_call_date", options);
+
+        if let Some(date) = as_of {
+            query.bind(":as_of", date.to_string());
+        }
+
+        let status_rank = "CASE effective_status
                     WHEN 'hot' THEN 1
                     WHEN 'warm' THEN 2
                     WHEN 'cold' THEN 3
                     ELSE 4
-                END
-            ",
-            sql
+                END";
+
+        let sort_column = match options.sort {
+            MenteeSort::Status => status_rank,
+            MenteeSort::Name => "name",
+            MenteeSort::Remaining => "remaining_calls",
+            MenteeSort::LastCall => "last_call_date",
+            MenteeSort::DaysSince => "days_since_last_call",
+            MenteeSort::Cadence => "avg_days_between_calls",
+            MenteeSort::Calls30 => "calls_last_30_days",
+            MenteeSort::Calls90 => "calls_last_90_days",
+        };
+
+        // mentees without calls have no values for the call columns, keep them last
+        let order_sql = query.order_sql(sort_column, &format!("{}, name", status_rank), options);
+
+        let sql = format!(
+            "SELECT * FROM ({} GROUP BY mentees.id) AS summaries{}{}",
+            inner_sql,
+            query.where_sql(),
+            order_sql
         );
+        let params = query.params();
 
         let mut stmt = self.conn.prepare(&sql)?;
 
-        let mentee_iter = stmt.query_map([], |row| {
+        let mentee_iter = stmt.query_map(params.as_slice(), |row| {
             let status_str: String = row.get(4)?;
 
             let status = Status::from_str(&status_str).unwrap_or(Status::Warm);
@@ -179,6 +362,11 @@ impl<'a> MenteeRepository<'a> {
                 remaining_calls: row.get(3)?,
                 status,
                 notes: row.get(5)?,
+                last_call_date: row.get(6)?,
+                days_since_last_call: row.get(7)?,
+                avg_days_between_calls: row.get(8)?,
+                calls_last_30_days: row.get(9)?,
+                calls_last_90_days: row.get(10)?,
             })
         })?;
 
@@ -191,38 +379,170 @@ impl<'a> MenteeRepository<'a> {
         Ok(mentees)
     }
 
-    pub fn delete_mentee_by_id(&self, id: i64) -> Result<usize, rusqlite::Error> {
+    fn get_payment_schedules(&self) -> Result<Vec<PaymentSchedule>, MenteeError> {
+        let sql = format!(
+            "SELECT mentees.name, mentees.payment_day, MAX(payments.date)
+            FROM {}
+            LEFT JOIN {} ON payments.mentee_id = mentees.id
+            WHERE mentees.status != 'archived'
+            GROUP BY mentees.id
+            ORDER BY mentees.name",
+            constants::MENTEES_TABLE,
+            constants::PAYMENTS_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let schedules = stmt.query_map([], |row| {
+            Ok(PaymentSchedule {
+                mentee_name: row.get(0)?,
+                payment_day: row.get(1)?,
+                last_payment_date: row.get(2)?,
+            })
+        })?;
+
+        Ok(schedules.collect::<Result<_, _>>()?)
+    }
+
+    fn in_transaction(
+        &self,
+        write: &mut dyn FnMut() -> Result<(), MenteeError>,
+    ) -> Result<(), MenteeError> {
+        // rolled back when dropped without committing
+        let transaction = self.conn.unchecked_transaction()?;
+        write()?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn record_status_change(&self, change: StatusChange) -> Result<usize, MenteeError> {
+        let sql = format!(
+            "INSERT INTO {} (mentee_id, date, from_status, to_status, reason) VALUES (?1, ?2, ?3, ?4, ?5)",
+            constants::STATUS_CHANGES_TABLE
+        );
+
+        Ok(self.conn.execute(
+            &sql,
+            params![
+                change.mentee_id,
+                change.date,
+                change.from_status.as_ref().map(Status::as_str),
+                change.to_status.as_str(),
+                change.reason
+            ],
+        )?)
+    }
+
+    fn get_status_history(&self, mentee_id: i64) -> Result<Vec<StatusChange>, MenteeError> {
+        let sql = format!(
+            "SELECT id, mentee_id, date, from_status, to_status, reason FROM {} WHERE mentee_id = ?1 ORDER BY date ASC, id ASC",
+            constants::STATUS_CHANGES_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let change_iter = stmt.query_map(params![mentee_id], |row| {
+            let from_status: Option<String> = row.get(3)?;
+            let to_status: String = row.get(4)?;
+
+            Ok(StatusChange {
+                id: row.get(0)?,
+                mentee_id: row.get(1)?,
+                date: row.get(2)?,
+                from_status: from_status.as_deref().and_then(Status::from_str),
+                to_status: Status::from_str(&to_status).unwrap_or(Status::Warm),
+                reason: row.get(5)?,
+            })
+        })?;
+
+        let mut changes = Vec::new();
+        for change in change_iter {
+            changes.push(change?);
+        }
+
+        Ok(changes)
+    }
+
+    fn get_mentees(&self, show_all: bool) -> Result<Vec<Mentee>, MenteeError> {
+        let mut sql = format!(
+            "SELECT {} FROM {}",
+            MENTEE_COLUMNS,
+            constants::MENTEES_TABLE
+        );
+
+        if !show_all {
+            sql.push_str(" WHERE mentees.status != 'archived'");
+        }
+
+        sql.push_str(" ORDER BY mentees.name ASC");
+
+        let mut stmt = self.conn.prepare(&sql)?;
+
+        let mentee_iter = stmt.query_map([], mentee_from_row)?;
+
+        let mut mentees = Vec::new();
+        for mentee in mentee_iter {
+            mentees.push(mentee?);
+        }
+
+        Ok(mentees)
+    }
+
+    fn delete_mentee_by_id(&self, id: i64) -> Result<usize, MenteeError> {
         let sql = format!("DELETE FROM {} WHERE id = ?1", constants::MENTEES_TABLE);
 
-        self.conn.execute(&sql, params![id])
+        Ok(self.conn.execute(&sql, params![id])?)
     }
 
-    pub fn get_mentee_count(
+    fn get_mentee_count(
         &self,
         count_type: Option<CountOptions>,
-    ) -> Result<i64, rusqlite::Error> {
+        as_of: Option<&str>,
+    ) -> Result<i64, MenteeError> {
+        let status_filter = match as_of {
+            Some(_) => format!(
+                "WHERE COALESCE({}, 'archived') != 'archived'",
+                status_as_of_sql()
+            ),
+            None => "WHERE status != 'archived'".to_string(),
+        };
+
         let sql = match count_type {
-            Some(CountOptions::Calls) => "SELECT SUM(calls) FROM mentees",
-            Some(CountOptions::Gross) => "SELECT SUM(gross) FROM mentees",
-            Some(CountOptions::Net) => "SELECT SUM(net) FROM mentees",
-            Some(CountOptions::NetPerCall) => {
-                "SELECT CAST(AVG(net_per_call) AS INTEGER) AS average_net_per_call
+            Some(CountOptions::Calls) => format!(
+                "SELECT COALESCE(SUM(calls), 0) FROM mentees {}",
+                status_filter
+            ),
+            Some(CountOptions::Gross) => format!(
+                "SELECT COALESCE(SUM(gross), 0) FROM mentees {}",
+                status_filter
+            ),
+            Some(CountOptions::Net) => format!(
+                "SELECT COALESCE(SUM(net), 0) FROM mentees {}",
+                status_filter
+            ),
+            Some(CountOptions::NetPerCall) => format!(
+                "SELECT CAST(COALESCE(AVG(net_per_call), 0) AS INTEGER) AS average_net_per_call
                     FROM (
                         SELECT CASE 
                             WHEN calls > 0 THEN net / calls 
                             ELSE net 
                             END AS net_per_call
                     FROM mentees
-                )"
-            }
-            _ => "SELECT COUNT(*) FROM mentees",
+                    {}
+                )",
+                status_filter
+            ),
+            _ => format!("SELECT COUNT(*) FROM mentees {}", status_filter),
         };
 
-        let sql = format!("{} WHERE status != 'archived'", sql);
-        self.conn.query_row(&sql, [], |row| row.get(0))
+        Ok(match as_of {
+            Some(date) => self
+                .conn
+                .query_row(&sql, &[(":as_of", &date)], |row| row.get(0))?,
+            None => self.conn.query_row(&sql, [], |row| row.get(0))?,
+        })
     }
 
-    pub fn update_mentee(&self, update_args: &UpdateMentee) -> Result<usize, rusqlite::Error> {
+    fn update_mentee(&self, update_args: &UpdateMentee) -> Result<usize, MenteeError> {
         let mut updates = Vec::new();
         let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
@@ -261,6 +581,16 @@ impl<'a> MenteeRepository<'a> {
             params.push(Box::new(notes));
         }
 
+        if let Some(source) = update_args.source {
+            updates.push("source = ?");
+            params.push(Box::new(source.as_str()));
+        }
+
+        if let Some(referrer) = update_args.referrer.as_ref() {
+            updates.push("referrer_id = (SELECT id FROM mentees WHERE name = ?)");
+            params.push(Box::new(referrer));
+        }
+
         if updates.is_empty() {
             return Ok(0); // No updates to make
         }
@@ -280,6 +610,10 @@ impl<'a> MenteeRepository<'a> {
         // Append mentee name to params (for WHERE clause)
         params_refs.push(&update_args.name);
 
-        self.conn.execute(&sql, params_refs.as_slice())
+        let name = update_args.new_name.as_deref().unwrap_or(&update_args.name);
+
+        self.conn
+            .execute(&sql, params_refs.as_slice())
+            .map_err(|err| name_taken(err, name))
     }
 }
@@ -1,9 +1,23 @@
 pub mod call_repository;
+pub mod contact_repository;
+pub mod expense_repository;
+pub mod goal_repository;
+pub mod invoice_repository;
+mod list_query;
+pub mod memory_repository;
 pub mod mentee_repository;
 pub mod payment_repository;
+pub mod report_repository;
 pub mod video_repository;
 
-pub use call_repository::CallRepository;
-pub use mentee_repository::MenteeRepository;
-pub use payment_repository::PaymentRepository;
-pub use video_repository::VideoRepository;
+pub use call_repository::{CallRepository, SqliteCallRepository};
+pub use contact_repository::ContactRepository;
+pub use expense_repository::ExpenseRepository;
+pub use goal_repository::GoalRepository;
+pub use invoice_repository::InvoiceRepository;
+use list_query::ListQuery;
+pub use memory_repository::InMemoryRepository;
+pub use mentee_repository::{MenteeRepository, SqliteMenteeRepository};
+pub use payment_repository::{PaymentRepository, SqlitePaymentRepository};
+pub use report_repository::ReportRepository;
+pub use video_repository::{SqliteVideoRepository, VideoRepository};
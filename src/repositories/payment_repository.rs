@@ -1,87 +1,183 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, Row};
 
 use crate::{
     constants,
-    models::payment::{Payment, PaymentWithMenteeName},
+    error::MenteeError,
+    models::{
+        list::ListOptions,
+        mentee::Status,
+        payment::{Payment, PaymentWithMenteeName},
+    },
+    PaymentSort,
 };
 
-pub struct PaymentRepository<'a> {
+use super::ListQuery;
+
+/// Where payments are kept
+pub trait PaymentRepository {
+    fn add_payment(&self, payment: &Payment) -> Result<i64, MenteeError>;
+
+    fn get_payment_by_id(&self, id: u32) -> Result<Payment, MenteeError>;
+
+    fn get_payment_with_mentee_name(&self, id: u32) -> Result<PaymentWithMenteeName, MenteeError>;
+
+    /// Payments of one mentee or everyone, `status` being the mentee's current status
+    fn get_all_payments(
+        &self,
+        mentee_id: Option<i64>,
+        status: Option<Status>,
+        options: &ListOptions<PaymentSort>,
+    ) -> Result<Vec<PaymentWithMenteeName>, MenteeError>;
+
+    /// Payments dated between `from` and `to` inclusive (YYYY-MM-DD)
+    fn get_payments_between(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<PaymentWithMenteeName>, MenteeError>;
+
+    fn update_payment(&self, payment: &Payment) -> Result<usize, MenteeError>;
+
+    fn delete_payment(&self, payment_id: u32) -> Result<usize, MenteeError>;
+}
+
+/// The default storage, the `payments` table
+pub struct SqlitePaymentRepository<'a> {
     conn: &'a Connection,
 }
 
-impl<'a> PaymentRepository<'a> {
+impl<'a> SqlitePaymentRepository<'a> {
     pub fn new(conn: &'a Connection) -> Self {
         Self { conn }
     }
 
-    pub fn add_payment(
-        &self,
-        mentee_id: u32,
-        date: String,
-        amount: u32,
-    ) -> Result<usize, rusqlite::Error> {
+    fn select_with_mentee_name_sql() -> String {
+        format!(
+            "SELECT 
+                payments.id AS payment_id,
+                mentees.name AS mentee_name,
+                payments.date,
+                payments.amount,
+                payments.currency,
+                payments.fee,
+                payments.source
+            FROM 
+                {}
+            JOIN
+                {}
+            ON
+                payments.mentee_id = mentees.id            
+            ",
+            constants::PAYMENTS_TABLE,
+            constants::MENTEES_TABLE
+        )
+    }
+
+    fn row_to_payment_with_mentee_name(
+        row: &Row,
+    ) -> Result<PaymentWithMenteeName, rusqlite::Error> {
+        Ok(PaymentWithMenteeName {
+            id: row.get(0)?,
+            mentee_name: row.get(1)?,
+            date: row.get(2)?,
+            amount: row.get(3)?,
+            currency: row.get(4)?,
+            fee: row.get(5)?,
+            source: row.get(6)?,
+        })
+    }
+}
+
+impl PaymentRepository for SqlitePaymentRepository<'_> {
+    fn add_payment(&self, payment: &Payment) -> Result<i64, MenteeError> {
         let sql = format!(
-            "INSERT INTO {} (mentee_id, date, amount) VALUES (?1, ?2, ?3)",
+            "INSERT INTO {} (mentee_id, date, amount, currency, fee, source) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             constants::PAYMENTS_TABLE
         );
 
-        self.conn.execute(&sql, params![mentee_id, date, amount])
+        self.conn.execute(
+            &sql,
+            params![
+                payment.mentee_id,
+                payment.date,
+                payment.amount,
+                payment.currency,
+                payment.fee,
+                payment.source
+            ],
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_payment_by_id(&self, id: u32) -> Result<Payment, rusqlite::Error> {
-        let sql = format!("SELECT * FROM {} WHERE id = ?1", constants::PAYMENTS_TABLE);
+    fn get_payment_by_id(&self, id: u32) -> Result<Payment, MenteeError> {
+        let sql = format!(
+            "SELECT id, mentee_id, date, amount, currency, fee, source FROM {} WHERE id = ?1",
+            constants::PAYMENTS_TABLE
+        );
 
-        self.conn.query_row(&sql, params![id], |row| {
+        Ok(self.conn.query_row(&sql, params![id], |row| {
             Ok(Payment {
                 id: row.get(0)?,
                 mentee_id: row.get(1)?,
                 date: row.get(2)?,
                 amount: row.get(3)?,
+                currency: row.get(4)?,
+                fee: row.get(5)?,
+                source: row.get(6)?,
             })
-        })
+        })?)
     }
 
-    pub fn get_all_payments(
-        &self,
-        mentee_id: Option<i64>,
-    ) -> Result<Vec<PaymentWithMenteeName>, rusqlite::Error> {
-        let mut sql = format!(
-            "SELECT 
-                payments.id AS payment_id,
-                mentees.name AS mentee_name,
-                payments.date,
-                payments.amount
-            FROM 
-                {}
-            JOIN
-                {}
-            ON
-                payments.mentee_id = mentees.id            
-            ",
-            constants::PAYMENTS_TABLE,
-            constants::MENTEES_TABLE
+    fn get_payment_with_mentee_name(&self, id: u32) -> Result<PaymentWithMenteeName, MenteeError> {
+        let sql = format!(
+            "{} WHERE payments.id = ?1",
+            Self::select_with_mentee_name_sql()
         );
 
-        let mut params: Vec<&dyn rusqlite::ToSql> = Vec::new();
-        let id_storage;
+        Ok(self
+            .conn
+            .query_row(&sql, params![id], Self::row_to_payment_with_mentee_name)?)
+    }
+
+    fn get_all_payments(
+        &self,
+        mentee_id: Option<i64>,
+        status: Option<Status>,
+        options: &ListOptions<PaymentSort>,
+    ) -> Result<Vec<PaymentWithMenteeName>, MenteeError> {
+        let mut sql = Self::select_with_mentee_name_sql();
+        let mut query = ListQuery::new();
 
         if let Some(id) = mentee_id {
-            sql.push_str(" WHERE payments.mentee_id = ?1");
-            id_storage = id;
-            params.push(&id_storage);
+            query
+                .filter("payments.mentee_id = :mentee_id")
+                .bind(":mentee_id", id);
+        }
+
+        if let Some(status) = status {
+            query
+                .filter("mentees.status = :status")
+                .bind(":status", status.as_str());
         }
 
-        sql.push_str(" ORDER BY payments.date ASC");
+        query.dated("payments.date", options);
 
+        let sort_column = match options.sort {
+            PaymentSort::Date => "payments.date",
+            PaymentSort::Mentee => "mentees.name",
+            PaymentSort::Amount => "payments.amount",
+            PaymentSort::Net => "payments.amount - payments.fee",
+        };
+
+        sql.push_str(&query.where_sql());
+        let order_sql = query.order_sql(sort_column, "payments.date, payments.id", options);
+        sql.push_str(&order_sql);
+
+        let params = query.params();
         let mut stmt = self.conn.prepare(&sql)?;
-        let payment_iter = stmt.query_map(&params[..], |row| {
-            Ok(PaymentWithMenteeName {
-                id: row.get(0)?,
-                mentee_name: row.get(1)?,
-                date: row.get(2)?,
-                amount: row.get(3)?,
-            })
-        })?;
+        let payment_iter =
+            stmt.query_map(params.as_slice(), Self::row_to_payment_with_mentee_name)?;
 
         let mut payments = Vec::new();
         for payment in payment_iter {
@@ -91,23 +187,50 @@ impl<'a> PaymentRepository<'a> {
         Ok(payments)
     }
 
-    pub fn update_payment(
+    fn get_payments_between(
         &self,
-        date: &String,
-        amount: u32,
-        payment_id: u32,
-    ) -> Result<usize, rusqlite::Error> {
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<PaymentWithMenteeName>, MenteeError> {
+        let sql = format!(
+            "{} WHERE payments.date BETWEEN ?1 AND ?2 ORDER BY payments.date ASC, payments.id ASC",
+            Self::select_with_mentee_name_sql()
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let payment_iter =
+            stmt.query_map(params![from, to], Self::row_to_payment_with_mentee_name)?;
+
+        let mut payments = Vec::new();
+        for payment in payment_iter {
+            payments.push(payment?);
+        }
+
+        Ok(payments)
+    }
+
+    fn update_payment(&self, payment: &Payment) -> Result<usize, MenteeError> {
         let sql = format!(
-            "UPDATE {} SET date =?1, amount = ?2 WHERE id = ?3",
+            "UPDATE {} SET date = ?1, amount = ?2, currency = ?3, fee = ?4, source = ?5 WHERE id = ?6",
             constants::PAYMENTS_TABLE
         );
 
-        self.conn.execute(&sql, params![date, amount, payment_id])
+        Ok(self.conn.execute(
+            &sql,
+            params![
+                payment.date,
+                payment.amount,
+                payment.currency,
+                payment.fee,
+                payment.source,
+                payment.id
+            ],
+        )?)
     }
 
-    pub fn delete_payment(&self, payment_id: u32) -> Result<usize, rusqlite::Error> {
+    fn delete_payment(&self, payment_id: u32) -> Result<usize, MenteeError> {
         let sql = format!("DELETE FROM {} WHERE id = ?1", constants::PAYMENTS_TABLE);
 
-        self.conn.execute(&sql, params![payment_id])
+        Ok(self.conn.execute(&sql, params![payment_id])?)
     }
 }
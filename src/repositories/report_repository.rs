@@ -0,0 +1,96 @@
+use rusqlite::Connection;
+
+use crate::constants;
+
+/// Read-only queries that aggregate across tables for the `report` commands
+pub struct ReportRepository<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> ReportRepository<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Every month (YYYY-MM) each mentee made a payment in
+    pub fn get_payment_months(&self) -> Result<Vec<(i64, String)>, rusqlite::Error> {
+        let sql = format!(
+            "SELECT DISTINCT mentee_id, strftime('%Y-%m', date) AS month FROM {} ORDER BY mentee_id, month",
+            constants::PAYMENTS_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let month_iter = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        let mut months = Vec::new();
+        for month in month_iter {
+            months.push(month?);
+        }
+
+        Ok(months)
+    }
+
+    /// Archived mentees with the date they were last archived, if it was recorded
+    pub fn get_archive_dates(&self) -> Result<Vec<(i64, Option<String>)>, rusqlite::Error> {
+        let sql = format!(
+            "
+            SELECT
+                mentees.id,
+                (SELECT MAX(status_changes.date) FROM {} WHERE status_changes.mentee_id = mentees.id AND status_changes.to_status = 'archived')
+            FROM
+                {}
+            WHERE
+                mentees.status = 'archived'
+            ",
+            constants::STATUS_CHANGES_TABLE,
+            constants::MENTEES_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let archive_iter = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        let mut archived = Vec::new();
+        for mentee in archive_iter {
+            archived.push(mentee?);
+        }
+
+        Ok(archived)
+    }
+
+    /// Every mentee with where they found us, if it was recorded
+    pub fn get_mentee_sources(&self) -> Result<Vec<(i64, Option<String>)>, rusqlite::Error> {
+        let sql = format!(
+            "SELECT id, source FROM {} ORDER BY id",
+            constants::MENTEES_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let source_iter = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        let mut sources = Vec::new();
+        for source in source_iter {
+            sources.push(source?);
+        }
+
+        Ok(sources)
+    }
+
+    /// Net paid by each mentee per currency, the currency is empty for payments
+    /// recorded before currencies were
+    pub fn get_net_payments(&self) -> Result<Vec<(i64, Option<String>, i64)>, rusqlite::Error> {
+        let sql = format!(
+            "SELECT mentee_id, currency, SUM(amount - fee) FROM {} GROUP BY mentee_id, currency",
+            constants::PAYMENTS_TABLE
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let total_iter = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+
+        let mut totals = Vec::new();
+        for total in total_iter {
+            totals.push(total?);
+        }
+
+        Ok(totals)
+    }
+}
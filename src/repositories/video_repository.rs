@@ -1,57 +1,87 @@
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, Row};
 
 use crate::{
     constants,
-    models::video::{Video, VideoWithMenteeName},
+    error::MenteeError,
+    models::{
+        list::ListOptions,
+        video::{Video, VideoStatus, VideoWithMenteeName},
+    },
+    VideoSort,
 };
 
-pub struct VideoRepository<'a> {
+use super::ListQuery;
+
+fn status_from_row(row: &Row, index: usize) -> Result<VideoStatus, rusqlite::Error> {
+    let status: String = row.get(index)?;
+
+    Ok(VideoStatus::from_str(&status).unwrap_or(VideoStatus::Delivered))
+}
+
+/// Where video reviews are kept
+pub trait VideoRepository {
+    fn add_video(&self, video: Video) -> Result<i64, MenteeError>;
+
+    fn get_video_by_id(&self, video_id: u32) -> Result<Video, MenteeError>;
+
+    fn get_video_with_mentee_name(&self, video_id: u32)
+        -> Result<VideoWithMenteeName, MenteeError>;
+
+    fn get_all_videos(
+        &self,
+        mentee_id: Option<i64>,
+        status: Option<VideoStatus>,
+        options: &ListOptions<VideoSort>,
+    ) -> Result<Vec<VideoWithMenteeName>, MenteeError>;
+
+    /// Videos not yet delivered, soonest due first and those without a due date last
+    fn get_outstanding_videos(&self) -> Result<Vec<VideoWithMenteeName>, MenteeError>;
+
+    fn update_video(&self, video: &Video) -> Result<usize, MenteeError>;
+
+    // Delete a video by video id
+    fn delete_video(&self, video_id: u32) -> Result<usize, MenteeError>;
+}
+
+/// The default storage, the `videos` table
+pub struct SqliteVideoRepository<'a> {
     conn: &'a Connection,
 }
 
-impl<'a> VideoRepository<'a> {
+impl<'a> SqliteVideoRepository<'a> {
     pub fn new(conn: &'a Connection) -> Self {
         Self { conn }
     }
 
-    pub fn add_video(&self, video: Video) -> Result<usize, rusqlite::Error> {
-        let sql = format!(
-            "INSERT INTO {} (mentee_id, date, length, notes) VALUES (?1, ?2, ?3, ?4)",
-            constants::VIDEOS_TABLE
-        );
-
-        self.conn.execute(
-            &sql,
-            params![video.mentee_id, video.date, video.length, video.notes],
-        )
-    }
-
-    pub fn get_video_by_id(&self, video_id: u32) -> Result<Video, rusqlite::Error> {
-        let sql = format!("SELECT * FROM {} WHERE id = ?1", constants::VIDEOS_TABLE);
-
-        self.conn.query_row(&sql, params![video_id], |row| {
-            Ok(Video {
-                id: row.get(0)?,
-                mentee_id: row.get(1)?,
-                date: row.get(2)?,
-                length: row.get(3)?,
-                notes: row.get(4)?,
-            })
+    fn row_to_video_with_mentee_name(row: &Row) -> Result<VideoWithMenteeName, rusqlite::Error> {
+        Ok(VideoWithMenteeName {
+            id: row.get(0)?,
+            mentee_name: row.get(1)?,
+            date: row.get(2)?,
+            length: row.get(3)?,
+            notes: row.get(4)?,
+            status: status_from_row(row, 5)?,
+            received_date: row.get(6)?,
+            due_date: row.get(7)?,
+            source_url: row.get(8)?,
+            file_path: row.get(9)?,
         })
     }
 
-    pub fn get_all_videos(
-        &self,
-        mentee_id: Option<i64>,
-    ) -> Result<Vec<VideoWithMenteeName>, rusqlite::Error> {
-        let mut sql = format!(
+    fn select_with_mentee_name_sql() -> String {
+        format!(
             "
         SELECT
             videos.id AS video_id,
             mentees.name AS mentee_name,
             videos.date,
             videos.length,
-            videos.notes
+            videos.notes,
+            videos.status,
+            videos.received_date,
+            videos.due_date,
+            videos.source_url,
+            videos.file_path
         FROM
             {}
         JOIN
@@ -61,28 +91,120 @@ impl<'a> VideoRepository<'a> {
         ",
             constants::VIDEOS_TABLE,
             constants::MENTEES_TABLE
+        )
+    }
+}
+
+impl VideoRepository for SqliteVideoRepository<'_> {
+    fn add_video(&self, video: Video) -> Result<i64, MenteeError> {
+        let sql = format!(
+            "INSERT INTO {} (mentee_id, date, length, notes, status, received_date, due_date,
+                source_url, file_path, file_size, file_sha256)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            constants::VIDEOS_TABLE
         );
-        let mut params: Vec<&dyn rusqlite::ToSql> = Vec::new();
-        let id_storage;
 
-        if let Some(id) = mentee_id {
-            sql.push_str(" WHERE videos.mentee_id = ?1");
-            id_storage = id;
-            params.push(&id_storage);
-        }
+        self.conn.execute(
+            &sql,
+            params![
+                video.mentee_id,
+                video.date,
+                video.length,
+                video.notes,
+                video.status.as_str(),
+                video.received_date,
+                video.due_date,
+                video.source_url,
+                video.file_path,
+                video.file_size,
+                video.file_sha256
+            ],
+        )?;
 
-        sql.push_str(" ORDER BY videos.date ASC");
+        Ok(self.conn.last_insert_rowid())
+    }
 
-        let mut stmt = self.conn.prepare(&sql)?;
-        let video_iter = stmt.query_map(&params[..], |row| {
-            Ok(VideoWithMenteeName {
+    fn get_video_by_id(&self, video_id: u32) -> Result<Video, MenteeError> {
+        let sql = format!(
+            "SELECT id, mentee_id, date, length, notes, status, received_date, due_date,
+                source_url, file_path, file_size, file_sha256
+            FROM {} WHERE id = ?1",
+            constants::VIDEOS_TABLE
+        );
+
+        Ok(self.conn.query_row(&sql, params![video_id], |row| {
+            Ok(Video {
                 id: row.get(0)?,
-                mentee_name: row.get(1)?,
+                mentee_id: row.get(1)?,
                 date: row.get(2)?,
                 length: row.get(3)?,
                 notes: row.get(4)?,
+                status: status_from_row(row, 5)?,
+                received_date: row.get(6)?,
+                due_date: row.get(7)?,
+                source_url: row.get(8)?,
+                file_path: row.get(9)?,
+                file_size: row.get(10)?,
+                file_sha256: row.get(11)?,
             })
-        })?;
+        })?)
+    }
+
+    fn get_video_with_mentee_name(
+        &self,
+        video_id: u32,
+    ) -> Result<VideoWithMenteeName, MenteeError> {
+        let sql = format!(
+            "{} WHERE videos.id = ?1",
+            Self::select_with_mentee_name_sql()
+        );
+
+        Ok(self
+            .conn
+            .query_row(&sql, params![video_id], Self::row_to_video_with_mentee_name)?)
+    }
+
+    fn get_all_videos(
+        &self,
+        mentee_id: Option<i64>,
+        status: Option<VideoStatus>,
+        options: &ListOptions<VideoSort>,
+    ) -> Result<Vec<VideoWithMenteeName>, MenteeError> {
+        let mut sql = Self::select_with_mentee_name_sql();
+        let mut query = ListQuery::new();
+
+        if let Some(id) = mentee_id {
+            query
+                .filter("videos.mentee_id = :mentee_id")
+                .bind(":mentee_id", id);
+        }
+
+        if let Some(status) = status {
+            query
+                .filter("videos.status = :status")
+                .bind(":status", status.as_str());
+        }
+
+        query.dated("videos.date", options);
+
+        let sort_column = match options.sort {
+            VideoSort::Date => "videos.date",
+            VideoSort::Mentee => "mentees.name",
+            // the order videos move through the review
+            VideoSort::Status => {
+                "CASE videos.status WHEN 'requested' THEN 1 WHEN 'in_progress' THEN 2 ELSE 3 END"
+            }
+            VideoSort::Due => "videos.due_date",
+            VideoSort::Length => "videos.length",
+        };
+
+        sql.push_str(&query.where_sql());
+        let order_sql = query.order_sql(sort_column, "videos.date, videos.id", options);
+        sql.push_str(&order_sql);
+
+        let params = query.params();
+        let mut stmt = self.conn.prepare(&sql)?;
+        let video_iter = stmt.query_map(params.as_slice(), Self::row_to_video_with_mentee_name)?;
 
         let mut videos = Vec::new();
         for video in video_iter {
@@ -92,25 +214,56 @@ impl<'a> VideoRepository<'a> {
         Ok(videos)
     }
 
-    pub fn update_video(
-        &self,
-        date: String,
-        length: u32,
-        notes: String,
-        id: u32,
-    ) -> Result<usize, rusqlite::Error> {
+    fn get_outstanding_videos(&self) -> Result<Vec<VideoWithMenteeName>, MenteeError> {
+        let sql = format!(
+            "{} WHERE videos.status != ?1
+            ORDER BY videos.due_date IS NULL, videos.due_date ASC, videos.date ASC, videos.id ASC",
+            Self::select_with_mentee_name_sql()
+        );
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let video_iter = stmt.query_map(
+            params![VideoStatus::Delivered.as_str()],
+            Self::row_to_video_with_mentee_name,
+        )?;
+
+        let mut videos = Vec::new();
+        for video in video_iter {
+            videos.push(video?);
+        }
+
+        Ok(videos)
+    }
+
+    fn update_video(&self, video: &Video) -> Result<usize, MenteeError> {
         let sql = format!(
-            "UPDATE {} SET date = ?1, length = ?2, notes = ?3 WHERE id = ?4",
+            "UPDATE {} SET date = ?1, length = ?2, notes = ?3, status = ?4, received_date = ?5,
+                due_date = ?6, source_url = ?7, file_path = ?8, file_size = ?9, file_sha256 = ?10
+            WHERE id = ?11",
             constants::VIDEOS_TABLE
         );
 
-        self.conn.execute(&sql, params![date, length, notes, id])
+        Ok(self.conn.execute(
+            &sql,
+            params![
+                video.date,
+                video.length,
+                video.notes,
+                video.status.as_str(),
+                video.received_date,
+                video.due_date,
+                video.source_url,
+                video.file_path,
+                video.file_size,
+                video.file_sha256,
+                video.id
+            ],
+        )?)
     }
 
-    // Delete a video by video id
-    pub fn delete_video(&self, video_id: u32) -> Result<usize, rusqlite::Error> {
+    fn delete_video(&self, video_id: u32) -> Result<usize, MenteeError> {
         let sql = format!("DELETE FROM {} WHERE id = ?1", constants::VIDEOS_TABLE);
 
-        self.conn.execute(&sql, params![video_id])
+        Ok(self.conn.execute(&sql, params![video_id])?)
     }
 }
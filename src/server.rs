@@ -0,0 +1,435 @@
+use std::io;
+
+use rusqlite::Connection;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{
+    config::Config,
+    error::MenteeError,
+    services::{CallService, MenteeService, PaymentService, VideoService},
+};
+
+/// Collections served by the API, each at `/<name>` and `/<name>/<key>`
+const RESOURCES: [&str; 4] = ["mentees", "calls", "videos", "payments"];
+
+/// Decodes `%XX` escapes, and `+` as a space in query strings
+fn percent_decode(input: &str, plus_as_space: bool) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(match bytes[i] {
+                    b'+' if plus_as_space => b' ',
+                    byte => byte,
+                });
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The decoded value of `key` in a query string, empty when given without one
+fn query_value(query: &str, key: &str) -> Option<String> {
+    query
+        .split('&')
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .find(|(name, _)| percent_decode(name, true) == key)
+        .map(|(_, value)| percent_decode(value, true))
+}
+
+fn query_flag(query: &str, key: &str) -> bool {
+    query_value(query, key).is_some_and(|value| matches!(value.as_str(), "" | "true" | "1"))
+}
+
+/// A failed request, turned into a JSON error body
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<MenteeError> for ApiError {
+    fn from(err: MenteeError) -> Self {
+        let status = match err {
+            MenteeError::NotFound(_) => 404,
+            MenteeError::InvalidInput(_) | MenteeError::ValidationError(_) => 400,
+            MenteeError::UniqueViolation(_) => 409,
+            _ => 500,
+        };
+
+        ApiError::new(status, err.to_string())
+    }
+}
+
+/// A successful request, `None` is answered with an empty body
+struct Reply {
+    status: u16,
+    body: Option<Value>,
+}
+
+fn ok<T: Serialize>(value: T) -> Result<Reply, ApiError> {
+    reply(200, value)
+}
+
+fn created<T: Serialize>(value: T) -> Result<Reply, ApiError> {
+    reply(201, value)
+}
+
+fn reply<T: Serialize>(status: u16, value: T) -> Result<Reply, ApiError> {
+    let body = serde_json::to_value(value).map_err(MenteeError::from)?;
+
+    Ok(Reply {
+        status,
+        body: Some(body),
+    })
+}
+
+fn no_content() -> Result<Reply, ApiError> {
+    Ok(Reply {
+        status: 204,
+        body: None,
+    })
+}
+
+fn parse_body<T: DeserializeOwned>(body: &str) -> Result<T, ApiError> {
+    serde_json::from_str(body)
+        .map_err(|err| ApiError::new(400, format!("Invalid input: request body: {}", err)))
+}
+
+/// Splits the mentee's name out of the body of a new call, video or payment
+fn parse_record<T: DeserializeOwned>(body: &str) -> Result<(String, T), ApiError> {
+    let mut value: Value = parse_body(body)?;
+
+    let mentee = match value
+        .as_object_mut()
+        .and_then(|fields| fields.remove("mentee"))
+    {
+        Some(Value::String(mentee)) => mentee.to_lowercase(),
+        _ => {
+            return Err(ApiError::new(
+                400,
+                "Invalid input: \"mentee\" must be the name of a mentee",
+            ))
+        }
+    };
+
+    let record = serde_json::from_value(value)
+        .map_err(|err| ApiError::new(400, format!("Invalid input: request body: {}", err)))?;
+
+    Ok((mentee, record))
+}
+
+fn parse_id(id: &str) -> Result<u32, ApiError> {
+    id.parse()
+        .map_err(|_| ApiError::new(404, format!("'{}' not found", id)))
+}
+
+/// Serves the mentees, calls, videos and payments as JSON. Everything goes
+/// through the same services and validation as the commands do.
+struct Api<'a> {
+    mentees: MenteeService<'a>,
+    calls: CallService<'a>,
+    videos: VideoService<'a>,
+    payments: PaymentService<'a>,
+    token: Option<String>,
+}
+
+impl<'a> Api<'a> {
+    fn new(conn: &'a Connection, config: &'a Config, token: Option<String>) -> Self {
+        Self {
+            mentees: MenteeService::new(conn, config),
+            calls: CallService::new(conn, config),
+            videos: VideoService::new(conn, config),
+            payments: PaymentService::new(conn, config),
+            token,
+        }
+    }
+
+    /// Whether a request with this `Authorization` header may be answered
+    fn is_authorized(&self, authorization: Option<&str>) -> bool {
+        match self.token.as_deref() {
+            Some(token) => {
+                authorization.and_then(|value| value.strip_prefix("Bearer ")) == Some(token)
+            }
+            None => true,
+        }
+    }
+
+    fn route(&self, method: &Method, url: &str, body: &str) -> Result<Reply, ApiError> {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let segments: Vec<String> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| percent_decode(segment, false))
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let mentee = query_value(query, "mentee").map(|name| name.to_lowercase());
+
+        match (method, segments.as_slice()) {
+            (Method::Get, ["mentees"]) => ok(self.mentees.get_mentees(query_flag(query, "all"))?),
+            (Method::Post, ["mentees"]) => created(self.mentees.create_mentee(parse_body(body)?)?),
+            (Method::Get, ["mentees", name]) => ok(self.mentees.get_mentee(name)?),
+            (Method::Patch, ["mentees", name]) => {
+                ok(self.mentees.change_mentee(name, parse_body(body)?)?)
+            }
+            (Method::Delete, ["mentees", name]) => {
+                self.mentees.delete_mentee(name.to_lowercase())?;
+                no_content()
+            }
+
+            (Method::Get, ["calls"]) => ok(self.calls.get_all_calls(mentee)?),
+            (Method::Post, ["calls"]) => {
+                let (mentee, call) = parse_record(body)?;
+                created(self.calls.create_call(&mentee, call)?)
+            }
+            (Method::Get, ["calls", id]) => ok(self.calls.get_call(parse_id(id)?)?),
+            (Method::Put, ["calls", id]) => {
+                ok(self.calls.replace_call(parse_id(id)?, parse_body(body)?)?)
+            }
+            (Method::Delete, ["calls", id]) => {
+                self.calls.delete_call(parse_id(id)?)?;
+                no_content()
+            }
+
+            (Method::Get, ["videos"]) => ok(self.videos.get_all_videos(mentee)?),
+            (Method::Post, ["videos"]) => {
+                let (mentee, video) = parse_record(body)?;
+                created(self.videos.create_video(&mentee, video)?)
+            }
+            (Method::Get, ["videos", id]) => {
+                ok(self.videos.get_video_with_mentee_name(parse_id(id)?)?)
+            }
+            (Method::Put, ["videos", id]) => ok(self
+                .videos
+                .replace_video(parse_id(id)?, parse_body(body)?)?),
+            (Method::Delete, ["videos", id]) => {
+                self.videos.delete_video(parse_id(id)?)?;
+                no_content()
+            }
+
+            (Method::Get, ["payments"]) => ok(self.payments.get_all_payments(mentee)?),
+            (Method::Post, ["payments"]) => {
+                let (mentee, payment) = parse_record(body)?;
+                created(self.payments.create_payment(&mentee, payment)?)
+            }
+            (Method::Get, ["payments", id]) => ok(self.payments.get_payment(parse_id(id)?)?),
+            (Method::Put, ["payments", id]) => ok(self
+                .payments
+                .replace_payment(parse_id(id)?, parse_body(body)?)?),
+            (Method::Delete, ["payments", id]) => {
+                self.payments.delete_payment(parse_id(id)?)?;
+                no_content()
+            }
+
+            (_, [resource] | [resource, _]) if RESOURCES.contains(resource) => Err(ApiError::new(
+                405,
+                format!("{} is not supported on {}", method, path),
+            )),
+            _ => Err(ApiError::new(404, format!("No route for {}", path))),
+        }
+    }
+
+    fn handle(&self, request: &mut Request) -> Reply {
+        let authorization = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("Authorization"))
+            .map(|header| header.value.as_str().to_string());
+
+        let result = if self.is_authorized(authorization.as_deref()) {
+            let mut body = String::new();
+            match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => self.route(request.method(), request.url(), &body),
+                Err(err) => Err(ApiError::new(
+                    400,
+                    format!("Could not read the body: {}", err),
+                )),
+            }
+        } else {
+            Err(ApiError::new(401, "Missing or wrong bearer token"))
+        };
+
+        result.unwrap_or_else(|err| Reply {
+            status: err.status,
+            body: Some(serde_json::json!({ "error": err.message })),
+        })
+    }
+}
+
+/// Serves the API on localhost until stopped. With a `token`, requests need an
+/// `Authorization: Bearer <token>` header.
+pub fn serve(
+    conn: &Connection,
+    config: &Config,
+    port: u16,
+    token: Option<String>,
+) -> Result<(), MenteeError> {
+    let server = Server::http(("127.0.0.1", port)).map_err(|err| {
+        MenteeError::IOError(io::Error::other(format!(
+            "could not listen on port {}: {}",
+            port, err
+        )))
+    })?;
+
+    let api = Api::new(conn, config, token);
+
+    println!("Listening on http://127.0.0.1:{}", port);
+
+    for mut request in server.incoming_requests() {
+        let reply = api.handle(&mut request);
+        println!("{} {} {}", request.method(), request.url(), reply.status);
+
+        let result = match reply.body {
+            Some(body) => {
+                let content_type = Header::from_bytes("Content-Type", "application/json")
+                    .expect("static header is valid");
+                request.respond(
+                    Response::from_string(body.to_string())
+                        .with_status_code(reply.status)
+                        .with_header(content_type),
+                )
+            }
+            None => request.respond(Response::empty(reply.status)),
+        };
+
+        if let Err(err) = result {
+            eprintln!("Could not send the response: {}", err);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Store;
+
+    #[test]
+    fn decodes_paths_and_queries() {
+        assert_eq!(percent_decode("dan%20page", false), "dan page");
+        assert_eq!(percent_decode("dan+page", false), "dan+page");
+        assert_eq!(percent_decode("dan+page", true), "dan page");
+        assert_eq!(percent_decode("100%", false), "100%");
+        assert_eq!(percent_decode("%zz", false), "%zz");
+    }
+
+    #[test]
+    fn reads_query_values() {
+        let query = "mentee=dan%20page&all";
+
+        assert_eq!(query_value(query, "mentee").as_deref(), Some("dan page"));
+        assert_eq!(query_value(query, "missing"), None);
+        assert!(query_flag(query, "all"));
+        assert!(!query_flag("all=false", "all"));
+    }
+
+    fn status(result: Result<Reply, ApiError>) -> u16 {
+        match result {
+            Ok(reply) => reply.status,
+            Err(err) => err.status,
+        }
+    }
+
+    const DAN: &str = r#"{"name": "Dan Page", "calls": 2, "gross": 200, "net": 180,
+        "status": "hot", "payment_day": 5}"#;
+
+    #[test]
+    fn creates_reads_and_deletes_records() {
+        let store = Store::open(":memory:").unwrap();
+        let api = Api::new(store.connection(), store.config(), None);
+
+        assert_eq!(status(api.route(&Method::Post, "/mentees", DAN)), 201);
+        assert_eq!(
+            status(api.route(&Method::Get, "/mentees/dan%20page", "")),
+            200
+        );
+
+        let call = api
+            .route(
+                &Method::Post,
+                "/calls",
+                r#"{"mentee": "Dan Page", "date": "2026-3-2"}"#,
+            )
+            .ok()
+            .unwrap();
+        assert_eq!(call.status, 201);
+        let body = call.body.unwrap();
+        assert_eq!(body["date"], "2026-03-02");
+        let id = body["id"].as_u64().unwrap();
+
+        let calls = api
+            .route(&Method::Get, "/calls?mentee=dan+page", "")
+            .ok()
+            .unwrap();
+        assert_eq!(calls.status, 200);
+        assert_eq!(calls.body.unwrap().as_array().unwrap().len(), 1);
+
+        let url = format!("/calls/{}", id);
+        let deleted = api.route(&Method::Delete, &url, "").ok().unwrap();
+        assert_eq!((deleted.status, deleted.body), (204, None));
+        assert_eq!(status(api.route(&Method::Get, &url, "")), 404);
+    }
+
+    #[test]
+    fn rejects_bad_requests() {
+        let store = Store::open(":memory:").unwrap();
+        let api = Api::new(store.connection(), store.config(), None);
+        api.route(&Method::Post, "/mentees", DAN).ok().unwrap();
+
+        let bad_date = r#"{"mentee": "dan page", "date": "02/03/2026"}"#;
+        assert_eq!(status(api.route(&Method::Post, "/calls", bad_date)), 400);
+        assert_eq!(status(api.route(&Method::Post, "/calls", "not json")), 400);
+        assert_eq!(status(api.route(&Method::Post, "/mentees", DAN)), 409);
+
+        assert_eq!(status(api.route(&Method::Get, "/payments/99", "")), 404);
+        assert_eq!(status(api.route(&Method::Get, "/calls/abc", "")), 404);
+        assert_eq!(status(api.route(&Method::Get, "/invoices", "")), 404);
+
+        assert_eq!(status(api.route(&Method::Patch, "/calls/1", "")), 405);
+        assert_eq!(status(api.route(&Method::Put, "/mentees", "")), 405);
+    }
+
+    #[test]
+    fn needs_the_bearer_token_when_one_is_set() {
+        let store = Store::open(":memory:").unwrap();
+        let api = Api::new(
+            store.connection(),
+            store.config(),
+            Some("secret".to_string()),
+        );
+
+        assert!(api.is_authorized(Some("Bearer secret")));
+        assert!(!api.is_authorized(None));
+        assert!(!api.is_authorized(Some("Bearer wrong")));
+        assert!(!api.is_authorized(Some("secret")));
+
+        let open = Api::new(store.connection(), store.config(), None);
+        assert!(open.is_authorized(None));
+    }
+}
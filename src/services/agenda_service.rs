@@ -0,0 +1,397 @@
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
+use rusqlite::Connection;
+
+use crate::{
+    error::MenteeError,
+    models::{
+        agenda::{Agenda, AgendaItem, AgendaItemKind, AgendaPriority, PaymentSchedule},
+        call::CallWithMenteeName,
+        list::ListOptions,
+        mentee::MenteeSummary,
+        video::{VideoStatus, VideoWithMenteeName},
+    },
+    repositories::{
+        CallRepository, MenteeRepository, SqliteCallRepository, SqliteMenteeRepository,
+        SqliteVideoRepository, VideoRepository,
+    },
+    utils::{
+        dates::{day_in_month, month_index, month_index_of},
+        validation::parse_date_from_db,
+    },
+    MenteeSort,
+};
+
+/// Days covered by the agenda, including the day it's for
+const AGENDA_DAYS: u64 = 7;
+
+fn format_day(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Where a dated item falls relative to the agenda's week, `None` once it's past the week
+fn priority_for(due: NaiveDate, date: NaiveDate, until: NaiveDate) -> Option<AgendaPriority> {
+    if due < date {
+        Some(AgendaPriority::Overdue)
+    } else if due == date {
+        Some(AgendaPriority::Today)
+    } else if due <= until {
+        Some(AgendaPriority::ThisWeek)
+    } else {
+        None
+    }
+}
+
+fn call_items(
+    calls: Vec<CallWithMenteeName>,
+    date: NaiveDate,
+    until: NaiveDate,
+) -> impl Iterator<Item = AgendaItem> {
+    calls.into_iter().filter_map(move |call| {
+        let call_date = parse_date_from_db(&call.date).ok()?;
+        // calls before the agenda date already happened
+        let priority = priority_for(call_date, date, until)
+            .filter(|priority| *priority != AgendaPriority::Overdue)?;
+
+        let first_line = call
+            .notes
+            .as_deref()
+            .and_then(|notes| notes.lines().find(|line| !line.trim().is_empty()))
+            .map(|line| format!(": {}", line.trim_start_matches(['#', '-', '*', '>', ' '])))
+            .unwrap_or_default();
+
+        Some(AgendaItem {
+            priority,
+            date: Some(call.date),
+            kind: AgendaItemKind::Call,
+            mentee_name: call.mentee_name,
+            description: format!(
+                "{}{}",
+                if call.free_call { "Free call" } else { "Call" },
+                first_line
+            ),
+        })
+    })
+}
+
+/// A payment is due on the payment day of each month, or the last day of months
+/// too short for it. It's settled by any payment made that month or later.
+fn payment_items(
+    schedules: Vec<PaymentSchedule>,
+    date: NaiveDate,
+    until: NaiveDate,
+) -> Vec<AgendaItem> {
+    let mut items = Vec::new();
+
+    for schedule in schedules {
+        let paid_through = schedule.last_payment_date.as_deref().and_then(month_index);
+
+        let months = [Some(date), date.checked_add_months(Months::new(1))];
+        for month in months.into_iter().flatten() {
+            let Some(due) = day_in_month(month.year(), month.month(), schedule.payment_day) else {
+                continue;
+            };
+
+            if paid_through.is_some_and(|paid| paid >= month_index_of(due)) {
+                continue;
+            }
+
+            let Some(priority) = priority_for(due, date, until) else {
+                continue;
+            };
+
+            let description = if priority == AgendaPriority::Overdue {
+                let days = (date - due).num_days();
+                format!(
+                    "Payment was due {} day{} ago",
+                    days,
+                    if days == 1 { "" } else { "s" }
+                )
+            } else {
+                "Payment due".to_string()
+            };
+
+            items.push(AgendaItem {
+                priority,
+                date: Some(format_day(due)),
+                kind: AgendaItemKind::Payment,
+                mentee_name: schedule.mentee_name.clone(),
+                description,
+            });
+        }
+    }
+
+    items
+}
+
+fn video_items(
+    videos: Vec<VideoWithMenteeName>,
+    date: NaiveDate,
+    until: NaiveDate,
+) -> impl Iterator<Item = AgendaItem> {
+    videos
+        .into_iter()
+        .filter(|video| video.status != VideoStatus::Delivered)
+        .map(move |video| {
+            let due = video
+                .due_date
+                .as_deref()
+                .and_then(|due_date| parse_date_from_db(due_date).ok());
+            let received = video.received_date.as_deref().unwrap_or(&video.date);
+
+            let status = match video.status {
+                VideoStatus::InProgress => "in progress",
+                _ => "waiting",
+            };
+
+            AgendaItem {
+                priority: due
+                    .and_then(|due| priority_for(due, date, until))
+                    .unwrap_or(AgendaPriority::Attention),
+                date: video.due_date.clone(),
+                kind: AgendaItemKind::Video,
+                mentee_name: video.mentee_name,
+                description: format!(
+                    "Video review {} (#{}, received {})",
+                    status, video.id, received
+                ),
+            }
+        })
+}
+
+fn balance_items(mentees: Vec<MenteeSummary>) -> impl Iterator<Item = AgendaItem> {
+    mentees
+        .into_iter()
+        .filter(|mentee| mentee.remaining_calls < 0)
+        .map(|mentee| {
+            let owed = -mentee.remaining_calls;
+
+            AgendaItem {
+                priority: AgendaPriority::Attention,
+                date: None,
+                kind: AgendaItemKind::Balance,
+                mentee_name: mentee.name,
+                description: format!(
+                    "{} call{} taken beyond what's been paid for",
+                    owed,
+                    if owed == 1 { "" } else { "s" }
+                ),
+            }
+        })
+}
+
+/// Collects the calls, payments, video reviews and call balances needing
+/// attention in the week starting on `date`, most urgent first
+pub fn build_agenda(
+    date: NaiveDate,
+    calls: Vec<CallWithMenteeName>,
+    schedules: Vec<PaymentSchedule>,
+    videos: Vec<VideoWithMenteeName>,
+    mentees: Vec<MenteeSummary>,
+) -> Agenda {
+    let until = date
+        .checked_add_days(Days::new(AGENDA_DAYS - 1))
+        .unwrap_or(date);
+
+    let mut items: Vec<AgendaItem> = Vec::new();
+    items.extend(call_items(calls, date, until));
+    items.extend(payment_items(schedules, date, until));
+    items.extend(video_items(videos, date, until));
+    items.extend(balance_items(mentees));
+
+    // undated items go last within their section
+    items.sort_by(|a, b| {
+        (
+            a.priority,
+            a.date.is_none(),
+            &a.date,
+            a.kind,
+            &a.mentee_name,
+        )
+            .cmp(&(
+                b.priority,
+                b.date.is_none(),
+                &b.date,
+                b.kind,
+                &b.mentee_name,
+            ))
+    });
+
+    Agenda {
+        date: format_day(date),
+        until: format_day(until),
+        items,
+    }
+}
+
+pub struct AgendaService<'a> {
+    call_repo: Box<dyn CallRepository + 'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    video_repo: Box<dyn VideoRepository + 'a>,
+}
+
+impl<'a> AgendaService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self {
+            call_repo: Box::new(SqliteCallRepository::new(conn)),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            video_repo: Box::new(SqliteVideoRepository::new(conn)),
+        }
+    }
+
+    /// The agenda for `date`, today when not given
+    pub fn get_agenda(&self, date: Option<NaiveDate>) -> Result<Agenda, MenteeError> {
+        let today = Local::now().date_naive();
+        let date = date.unwrap_or(today);
+        let until = date
+            .checked_add_days(Days::new(AGENDA_DAYS - 1))
+            .unwrap_or(date);
+
+        // balances are worked out as of the agenda date when looking elsewhere in time
+        let as_of = (date != today).then(|| format_day(date));
+
+        Ok(build_agenda(
+            date,
+            self.call_repo
+                .get_calls_between(&format_day(date), &format_day(until))?,
+            self.mentee_repo.get_payment_schedules()?,
+            self.video_repo.get_outstanding_videos()?,
+            self.mentee_repo.get_all_mentees(
+                false,
+                as_of.as_deref(),
+                None,
+                &ListOptions {
+                    sort: MenteeSort::Name,
+                    ..ListOptions::default()
+                },
+            )?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn day(date: &str) -> NaiveDate {
+        parse_date_from_db(date).unwrap()
+    }
+
+    fn schedule(name: &str, payment_day: u32, last_payment: Option<&str>) -> PaymentSchedule {
+        PaymentSchedule {
+            mentee_name: name.to_string(),
+            payment_day,
+            last_payment_date: last_payment.map(str::to_string),
+        }
+    }
+
+    fn summary(name: &str, remaining_calls: i32) -> MenteeSummary {
+        MenteeSummary {
+            remaining_calls,
+            ..fixtures::summary(name)
+        }
+    }
+
+    #[test]
+    fn payments_due_overdue_and_settled() {
+        let date = day("2026-03-28");
+
+        let items = payment_items(
+            vec![
+                // paid this month, next month's payment falls in the week
+                schedule("amy", 2, Some("2026-03-02")),
+                // hasn't paid since February
+                schedule("dan", 20, Some("2026-02-20")),
+                // due today
+                schedule("eve", 28, Some("2026-02-28")),
+                // paid this month, next due after the week
+                schedule("joe", 15, Some("2026-03-15")),
+            ],
+            date,
+            day("2026-04-03"),
+        );
+
+        let summary: Vec<(&str, AgendaPriority, Option<&str>)> = items
+            .iter()
+            .map(|item| {
+                (
+                    item.mentee_name.as_str(),
+                    item.priority,
+                    item.date.as_deref(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("amy", AgendaPriority::ThisWeek, Some("2026-04-02")),
+                ("dan", AgendaPriority::Overdue, Some("2026-03-20")),
+                ("eve", AgendaPriority::Today, Some("2026-03-28")),
+            ]
+        );
+        assert_eq!(items[1].description, "Payment was due 8 days ago");
+    }
+
+    #[test]
+    fn orders_items_by_priority_then_date() {
+        let call = CallWithMenteeName {
+            mentee_name: "amy".to_string(),
+            notes: Some("## Mock interview".to_string()),
+            ..fixtures::call(1, "2026-03-12", false)
+        };
+        let past_call = CallWithMenteeName {
+            date: "2026-03-01".to_string(),
+            ..call.clone()
+        };
+        let video = VideoWithMenteeName {
+            id: 4,
+            mentee_name: "dan".to_string(),
+            date: "2026-03-01".to_string(),
+            length: 0,
+            notes: String::new(),
+            status: VideoStatus::Requested,
+            received_date: Some("2026-03-01".to_string()),
+            due_date: Some("2026-03-08".to_string()),
+            source_url: None,
+            file_path: None,
+        };
+
+        let agenda = build_agenda(
+            day("2026-03-10"),
+            vec![call, past_call],
+            vec![],
+            vec![video],
+            vec![summary("eve", -2), summary("joe", 1)],
+        );
+
+        assert_eq!(agenda.until, "2026-03-16");
+
+        let summary: Vec<(AgendaPriority, AgendaItemKind, &str)> = agenda
+            .items
+            .iter()
+            .map(|item| (item.priority, item.kind, item.description.as_str()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    AgendaPriority::Overdue,
+                    AgendaItemKind::Video,
+                    "Video review waiting (#4, received 2026-03-01)"
+                ),
+                (
+                    AgendaPriority::ThisWeek,
+                    AgendaItemKind::Call,
+                    "Call: Mock interview"
+                ),
+                (
+                    AgendaPriority::Attention,
+                    AgendaItemKind::Balance,
+                    "2 calls taken beyond what's been paid for"
+                ),
+            ]
+        );
+    }
+}
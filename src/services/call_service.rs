@@ -1,30 +1,93 @@
-use inquire::{DateSelect, Select, Text};
+use inquire::{CustomType, DateSelect, MultiSelect, Select};
 use rusqlite::Connection;
+use serde_json::json;
 
 use crate::{
+    config::Config,
     error::MenteeError,
-    models::call::{Call, CallWithMenteeName},
-    repositories::{mentee_repository::MenteeRepository, CallRepository},
-    utils::validation::parse_date_from_db,
+    hooks::{HookEvent, Hooks},
+    models::{
+        call::{Call, CallInput, CallWithMenteeName},
+        goal::GoalWithProgress,
+        list::ListOptions,
+        mentee::Status,
+    },
+    repositories::{
+        CallRepository, GoalRepository, MenteeRepository, SqliteCallRepository,
+        SqliteMenteeRepository,
+    },
+    utils::{
+        ui::prompt_notes,
+        validation::{normalize_date, parse_date_from_db},
+    },
+    CallSort,
 };
 
+fn goal_label(goal: &GoalWithProgress) -> String {
+    format!("{} (#{})", goal.title, goal.id)
+}
+
 pub struct CallService<'a> {
-    call_repo: CallRepository<'a>,
-    mentee_repo: MenteeRepository<'a>,
+    call_repo: Box<dyn CallRepository + 'a>,
+    /// Goals only live in SQLite, without them no mentee has open goals
+    goal_repo: Option<GoalRepository<'a>>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    hooks: Hooks<'a>,
 }
 
 impl<'a> CallService<'a> {
-    pub fn new(conn: &'a Connection) -> Self {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
         Self {
-            call_repo: CallRepository::new(conn),
-            mentee_repo: MenteeRepository::new(conn),
+            call_repo: Box::new(SqliteCallRepository::new(conn)),
+            goal_repo: Some(GoalRepository::new(conn)),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            hooks: Hooks::new(&config.hooks),
         }
     }
 
-    pub fn add_call(&self, name: String) -> Result<String, MenteeError> {
-        let mentee_id = self.mentee_repo.get_mentee_id(&name)?.ok_or_else(|| {
-            MenteeError::NotFound(format!("No mentee found with name '{}'", name))
-        })?;
+    /// Builds the service on another storage backend
+    pub fn with_repositories(
+        call_repo: impl CallRepository + 'a,
+        mentee_repo: impl MenteeRepository + 'a,
+        config: &'a Config,
+    ) -> Self {
+        Self {
+            call_repo: Box::new(call_repo),
+            goal_repo: None,
+            mentee_repo: Box::new(mentee_repo),
+            hooks: Hooks::new(&config.hooks),
+        }
+    }
+
+    fn get_open_goals(&self, mentee_id: i64) -> Result<Vec<GoalWithProgress>, MenteeError> {
+        match &self.goal_repo {
+            Some(goal_repo) => {
+                Ok(goal_repo.get_goals_with_progress(Some(mentee_id), true, false)?)
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Asks how many minutes a call took, pressing escape keeps `current`
+    fn prompt_duration(current: Option<u32>) -> Result<Option<u32>, MenteeError> {
+        let mut duration_prompt = CustomType::<u32>::new("How many minutes did the call take?")
+            .with_help_message("esc to skip");
+        let starting_duration = current.map(|duration| duration.to_string());
+        if let Some(starting_duration) = starting_duration.as_deref() {
+            duration_prompt = duration_prompt.with_starting_input(starting_duration);
+        }
+
+        Ok(duration_prompt.prompt_skippable()?.or(current))
+    }
+
+    fn get_mentee_id(&self, name: &str) -> Result<i64, MenteeError> {
+        self.mentee_repo
+            .get_mentee_id(name)?
+            .ok_or_else(|| MenteeError::NotFound(format!("No mentee found with name '{}'", name)))
+    }
+
+    pub(crate) fn add_call(&self, name: String) -> Result<String, MenteeError> {
+        let mentee_id = self.get_mentee_id(&name)?;
 
         let date = DateSelect::new("Enter the date of the call:")
             .prompt()
@@ -32,10 +95,7 @@ impl<'a> CallService<'a> {
             .format("%Y-%m-%d")
             .to_string();
 
-        let notes = Text::new("Enter any notes for the call:")
-            .with_placeholder("e.g. Discussed project progress ")
-            .prompt()
-            .expect("Failed to read notes");
+        let notes = prompt_notes("Enter any notes for the call:", None)?;
 
         let is_free = Select::new("Was this a free call?", vec!["No", "Yes"])
             .with_starting_cursor(0) // "No" is selected by default
@@ -43,23 +103,111 @@ impl<'a> CallService<'a> {
             .map(|answer| answer == "Yes")
             .unwrap_or(false); // default to false if input fails
 
-        let result = self.call_repo.add_call(Call {
+        let duration = Self::prompt_duration(None)?;
+
+        let open_goals = self.get_open_goals(mentee_id)?;
+
+        let advanced_goals = if open_goals.is_empty() {
+            Vec::new()
+        } else {
+            let titles: Vec<String> = open_goals.iter().map(goal_label).collect();
+
+            MultiSelect::new("Which goals did this call advance?", titles)
+                .prompt_skippable()?
+                .unwrap_or_default()
+        };
+
+        let goal_ids = open_goals
+            .iter()
+            .filter(|goal| advanced_goals.contains(&goal_label(goal)))
+            .map(|goal| goal.id)
+            .collect();
+
+        let call = self.create_call(
+            &name,
+            CallInput {
+                date,
+                notes: Some(notes),
+                free_call: is_free,
+                duration,
+                goal_ids,
+            },
+        )?;
+
+        Ok(format!("Call with {name} on {} added.", call.date))
+    }
+
+    /// Records a call without prompting, linking it to the open goals it advanced
+    pub fn create_call(
+        &self,
+        name: &str,
+        input: CallInput,
+    ) -> Result<CallWithMenteeName, MenteeError> {
+        let mentee_id = self.get_mentee_id(name)?;
+
+        let date = normalize_date(&input.date).map_err(MenteeError::InvalidInput)?;
+
+        let open_goals = self.get_open_goals(mentee_id)?;
+
+        let mut advanced_goals = Vec::new();
+        for goal_id in &input.goal_ids {
+            let goal = open_goals
+                .iter()
+                .find(|goal| goal.id == *goal_id)
+                .ok_or_else(|| {
+                    MenteeError::InvalidInput(format!(
+                        "goal {} isn't an open goal of {}",
+                        goal_id, name
+                    ))
+                })?;
+            advanced_goals.push(goal);
+        }
+
+        let call_id = self.call_repo.add_call(Call {
             id: 0,
             mentee_id,
             date: date.clone(),
-            notes: Some(notes),
-            free_call: is_free,
-        });
+            notes: input.notes.clone(),
+            free_call: input.free_call,
+            duration: input.duration,
+        })?;
 
-        match result {
-            Ok(..) => Ok(format!("Call with {name} on {date} added.")),
-            Err(err) => Err(MenteeError::DatabaseError(err)),
+        if let Some(goal_repo) = &self.goal_repo {
+            for goal in &advanced_goals {
+                goal_repo.link_call(call_id, goal.id)?;
+            }
         }
+
+        self.hooks.fire(
+            HookEvent::CallAdded,
+            name,
+            json!({
+                "id": call_id,
+                "date": date,
+                "notes": input.notes,
+                "free_call": input.free_call,
+                "duration": input.duration,
+                "goals": advanced_goals.into_iter().map(goal_label).collect::<Vec<_>>(),
+            }),
+        );
+
+        self.get_call(call_id as u32)
     }
 
     pub fn get_all_calls(
         &self,
         name: Option<String>,
+    ) -> Result<Vec<CallWithMenteeName>, MenteeError> {
+        self.list_calls(name, None, &ListOptions::default())
+    }
+
+    /// Calls filtered, sorted and paged as the options say. `status` is the
+    /// mentee's current status.
+    pub fn list_calls(
+        &self,
+        name: Option<String>,
+        status: Option<Status>,
+        options: &ListOptions<CallSort>,
     ) -> Result<Vec<CallWithMenteeName>, MenteeError> {
         let mentee_id = if let Some(name) = name {
             match self.mentee_repo.get_mentee_id(&name)? {
@@ -75,12 +223,16 @@ impl<'a> CallService<'a> {
             None
         };
 
+        self.call_repo.get_all_calls(mentee_id, status, options)
+    }
+
+    pub fn get_call(&self, call_id: u32) -> Result<CallWithMenteeName, MenteeError> {
         self.call_repo
-            .get_all_calls(mentee_id)
-            .map_err(MenteeError::DatabaseError)
+            .get_call_with_mentee_name(call_id)
+            .map_err(|_| MenteeError::NotFound(format!("Can't find a call with id of {}", call_id)))
     }
 
-    pub fn update_call(&self, call_id: u32) -> Result<String, MenteeError> {
+    pub(crate) fn update_call(&self, call_id: u32) -> Result<String, MenteeError> {
         let call = self.call_repo.get_call_by_id(call_id).map_err(|_| {
             MenteeError::NotFound(format!("Can't find a call with id of {}", call_id))
         })?;
@@ -94,11 +246,7 @@ impl<'a> CallService<'a> {
             .format("%Y-%m-%d")
             .to_string();
 
-        let notes = Text::new("Enter any notes for the call:")
-            .with_placeholder("e.g. Discussed project progress ")
-            .with_initial_value(call.notes.as_deref().unwrap_or(""))
-            .prompt()
-            .expect("Failed to read notes");
+        let notes = prompt_notes("Enter any notes for the call:", call.notes.as_deref())?;
 
         let default_cursor = if call.free_call { 1 } else { 0 };
 
@@ -108,16 +256,112 @@ impl<'a> CallService<'a> {
             .map(|answer| answer == "Yes")
             .unwrap_or(call.free_call);
 
-        let updated_rows = self.call_repo.update_call(call.id, date, notes, is_free)?;
+        let duration = Self::prompt_duration(call.duration)?;
+
+        let updated_rows = self
+            .call_repo
+            .update_call(call.id, date, notes, is_free, duration)?;
 
         Ok(format!("{updated_rows} call record updated"))
     }
 
-    pub fn delete_call(&self, call_id: u32) -> Result<String, String> {
-        match self.call_repo.delete_call(call_id) {
-            Ok(0) => Err(format!("Call with ID {} not found.", call_id)),
-            Ok(_) => Ok(format!("Deleted call {}", call_id)),
-            Err(e) => Err(format!("Database error: {}", e)),
+    /// Replaces the date, notes, free flag and duration of a call without prompting
+    pub fn replace_call(
+        &self,
+        call_id: u32,
+        input: CallInput,
+    ) -> Result<CallWithMenteeName, MenteeError> {
+        let call = self
+            .call_repo
+            .get_call_by_id(call_id)
+            .map_err(|_| MenteeError::NotFound(format!("Call with ID {}", call_id)))?;
+
+        let date = normalize_date(&input.date).map_err(MenteeError::InvalidInput)?;
+
+        self.call_repo.update_call(
+            call.id,
+            date,
+            input.notes.unwrap_or_default(),
+            input.free_call,
+            input.duration,
+        )?;
+
+        self.get_call(call_id)
+    }
+
+    pub fn delete_call(&self, call_id: u32) -> Result<String, MenteeError> {
+        match self.call_repo.delete_call(call_id)? {
+            0 => Err(MenteeError::NotFound(format!("Call with ID {}", call_id))),
+            _ => Ok(format!("Deleted call {}", call_id)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::{
+        models::mentee::{NewMentee, Status},
+        repositories::InMemoryRepository,
+        services::MenteeService,
+    };
+
+    #[test]
+    fn records_calls_against_the_mentee() {
+        let config = Config::for_database(PathBuf::new());
+        let repo = InMemoryRepository::new();
+        let mentees = MenteeService::with_repositories(repo.clone(), &config);
+        let calls = CallService::with_repositories(repo.clone(), repo, &config);
+
+        mentees
+            .create_mentee(NewMentee {
+                name: "dan page".to_string(),
+                calls: 2,
+                gross: 200,
+                net: 180,
+                status: Status::Hot,
+                payment_day: 5,
+                notes: None,
+                source: None,
+                referrer: None,
+            })
+            .unwrap();
+
+        let call = calls
+            .create_call(
+                "dan page",
+                CallInput {
+                    date: "2026-3-2".to_string(),
+                    notes: None,
+                    free_call: false,
+                    duration: None,
+                    goal_ids: vec![],
+                },
+            )
+            .unwrap();
+        assert_eq!(call.mentee_name, "dan page");
+        assert_eq!(call.date, "2026-03-02");
+        assert_eq!(calls.get_all_calls(None).unwrap().len(), 1);
+
+        // without goal storage there are no open goals to link
+        let with_goal = calls.create_call(
+            "dan page",
+            CallInput {
+                date: "2026-03-09".to_string(),
+                notes: None,
+                free_call: false,
+                duration: None,
+                goal_ids: vec![1],
+            },
+        );
+        assert!(matches!(with_goal, Err(MenteeError::InvalidInput(_))));
+
+        calls.delete_call(call.id).unwrap();
+        assert!(matches!(
+            calls.get_call(call.id),
+            Err(MenteeError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use chrono::Local;
+use rusqlite::Connection;
+
+use crate::{
+    config::{CapacityConfig, Config},
+    error::MenteeError,
+    models::{call::CallWithMenteeName, capacity::CapacityMonth},
+    repositories::{
+        CallRepository, MenteeRepository, SqliteCallRepository, SqliteMenteeRepository,
+    },
+    utils::dates::{month_index_of, month_label},
+    CountOptions,
+};
+
+/// Capacity of `months` months from `first_month` (a month index). What's
+/// committed is the same every month, as it's what mentees have now, while
+/// `calls` are the ones actually booked.
+pub fn build_capacity(
+    committed_calls: u32,
+    calls: &[CallWithMenteeName],
+    first_month: i32,
+    months: u32,
+    capacity: &CapacityConfig,
+    minutes_per_call: u32,
+) -> Vec<CapacityMonth> {
+    let mut booked: HashMap<&str, u32> = HashMap::new();
+    for call in calls {
+        *booked
+            .entry(call.date.get(..7).unwrap_or(&call.date))
+            .or_default() += 1;
+    }
+
+    (first_month..first_month + months as i32)
+        .map(|index| {
+            let month = month_label(index);
+
+            CapacityMonth {
+                booked_calls: booked.get(month.as_str()).copied().unwrap_or_default(),
+                month,
+                committed_calls,
+                committed_hours: committed_calls as f64 * minutes_per_call as f64 / 60.0,
+                capacity_calls: capacity.calls_per_month,
+                capacity_hours: capacity.hours_per_month,
+            }
+        })
+        .collect()
+}
+
+pub struct CapacityService<'a> {
+    call_repo: Box<dyn CallRepository + 'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    capacity: &'a CapacityConfig,
+    minutes_per_call: u32,
+}
+
+impl<'a> CapacityService<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self {
+            call_repo: Box::new(SqliteCallRepository::new(conn)),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            capacity: &config.capacity,
+            minutes_per_call: config.calls.default_minutes,
+        }
+    }
+
+    /// Committed against available calls and hours for this month and the
+    /// `months - 1` after it, always at least this month
+    pub fn get_capacity(&self, months: u32) -> Result<Vec<CapacityMonth>, MenteeError> {
+        let months = months.max(1);
+        let first_month = month_index_of(Local::now().date_naive());
+        let last_month = first_month + months as i32 - 1;
+
+        let committed = self
+            .mentee_repo
+            .get_mentee_count(Some(CountOptions::Calls), None)?;
+        // dates compare as strings, so the 31st covers the end of any month
+        let calls = self.call_repo.get_calls_between(
+            &format!("{}-01", month_label(first_month)),
+            &format!("{}-31", month_label(last_month)),
+        )?;
+
+        Ok(build_capacity(
+            committed.max(0) as u32,
+            &calls,
+            first_month,
+            months,
+            self.capacity,
+            self.minutes_per_call,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::call, utils::dates::month_index, Store};
+
+    #[test]
+    fn compares_commitments_with_capacity_per_month() {
+        let capacity = CapacityConfig {
+            calls_per_month: Some(20),
+            hours_per_month: Some(15),
+        };
+        let calls = vec![
+            call(1, "2026-03-02", false),
+            call(2, "2026-03-30", false),
+            call(3, "2026-05-01", false),
+        ];
+
+        let months = build_capacity(
+            18,
+            &calls,
+            month_index("2026-03").unwrap(),
+            3,
+            &capacity,
+            60,
+        );
+
+        let booked: Vec<(&str, u32)> = months
+            .iter()
+            .map(|month| (month.month.as_str(), month.booked_calls))
+            .collect();
+        assert_eq!(booked, vec![("2026-03", 2), ("2026-04", 0), ("2026-05", 1)]);
+
+        assert_eq!(months[0].available_calls(), Some(2));
+        assert_eq!(months[0].available_hours(), Some(-3.0));
+        assert_eq!(
+            build_capacity(18, &[], 0, 1, &CapacityConfig::default(), 60)[0].available_calls(),
+            None
+        );
+    }
+
+    #[test]
+    fn always_shows_this_month() {
+        let store = Store::open(":memory:").unwrap();
+        let service = CapacityService::new(store.connection(), store.config());
+
+        let months = service.get_capacity(0).unwrap();
+
+        assert_eq!(months.len(), 1);
+        assert_eq!(
+            months[0].month,
+            month_label(month_index_of(Local::now().date_naive()))
+        );
+    }
+}
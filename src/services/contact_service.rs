@@ -0,0 +1,82 @@
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::{
+    error::MenteeError,
+    models::contact::{Contact, ContactKind},
+    repositories::{ContactRepository, MenteeRepository, SqliteMenteeRepository},
+    utils::validation::validate_contact_core,
+};
+
+pub struct ContactService<'a> {
+    contact_repo: ContactRepository<'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+}
+
+impl<'a> ContactService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self {
+            contact_repo: ContactRepository::new(conn),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+        }
+    }
+
+    fn find_mentee_id(&self, name: &str) -> Result<i64, MenteeError> {
+        self.mentee_repo
+            .get_mentee_id(&name.to_lowercase())?
+            .ok_or_else(|| MenteeError::NotFound(format!("No mentee found with name '{}'", name)))
+    }
+
+    pub fn add_contact(
+        &self,
+        name: String,
+        kind: ContactKind,
+        value: String,
+    ) -> Result<String, MenteeError> {
+        let mentee_id = self.find_mentee_id(&name)?;
+        let value = value.trim().to_string();
+
+        validate_contact_core(kind, &value).map_err(MenteeError::ValidationError)?;
+
+        let result = self.contact_repo.add_contact(Contact {
+            id: 0,
+            mentee_id,
+            kind,
+            value: value.clone(),
+        });
+
+        match result {
+            Ok(_) => Ok(format!("Added {} {} for {}", kind.as_str(), value, name)
+                .green()
+                .to_string()),
+            Err(rusqlite::Error::SqliteFailure(ref err, _)) if err.extended_code == 2067 => {
+                Err(MenteeError::InvalidInput(format!(
+                    "{} already has {} {}",
+                    name,
+                    kind.as_str(),
+                    value
+                )))
+            }
+            Err(err) => Err(MenteeError::DatabaseError(err)),
+        }
+    }
+
+    pub fn get_contacts(&self, name: String) -> Result<Vec<Contact>, MenteeError> {
+        let mentee_id = self.find_mentee_id(&name)?;
+
+        self.contact_repo
+            .get_contacts_for_mentee(mentee_id)
+            .map_err(MenteeError::DatabaseError)
+    }
+
+    pub fn delete_contact(&self, contact_id: u32) -> Result<String, MenteeError> {
+        match self.contact_repo.delete_contact(contact_id) {
+            Ok(0) => Err(MenteeError::NotFound(format!(
+                "Contact with id {}",
+                contact_id
+            ))),
+            Ok(_) => Ok(format!("Contact with id {} deleted", contact_id)),
+            Err(err) => Err(MenteeError::DatabaseError(err)),
+        }
+    }
+}
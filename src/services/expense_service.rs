@@ -0,0 +1,232 @@
+use std::path::Path;
+
+use colored::Colorize;
+use inquire::{validator::Validation, CustomType, DateSelect, Select, Text};
+use rusqlite::Connection;
+use serde_json::json;
+
+use crate::{
+    config::{Config, PaymentsConfig},
+    error::MenteeError,
+    hooks::{HookEvent, Hooks},
+    models::{
+        expense::{Expense, ExpenseCategory, ExpenseWithMenteeName},
+        list::ListOptions,
+    },
+    repositories::{ExpenseRepository, MenteeRepository, SqliteMenteeRepository},
+    utils::{ui::prompt_currency, validation::parse_date_from_db},
+    ExpenseSort,
+};
+
+const NO_MENTEE: &str = "No mentee";
+
+pub struct ExpenseService<'a> {
+    expense_repo: ExpenseRepository<'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    settings: &'a PaymentsConfig,
+    hooks: Hooks<'a>,
+}
+
+impl<'a> ExpenseService<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self {
+            expense_repo: ExpenseRepository::new(conn),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            settings: &config.payments,
+            hooks: Hooks::new(&config.hooks),
+        }
+    }
+
+    fn select_category(current: Option<ExpenseCategory>) -> Result<ExpenseCategory, MenteeError> {
+        let options = ExpenseCategory::variants();
+        let starting_cursor = current
+            .and_then(|current| options.iter().position(|name| *name == current.as_str()))
+            .unwrap_or(0);
+
+        let selected = Select::new("What kind of expense?", options)
+            .with_starting_cursor(starting_cursor)
+            .prompt()?;
+
+        ExpenseCategory::from_str(selected).ok_or_else(|| "Invalid category selected".into())
+    }
+
+    fn select_mentee(&self, current: Option<u32>) -> Result<Option<u32>, MenteeError> {
+        let mentees = self.mentee_repo.get_mentees(true)?;
+
+        let mut options = vec![NO_MENTEE.to_string()];
+        options.extend(mentees.iter().map(|mentee| mentee.name.clone()));
+
+        let starting_cursor = current
+            .and_then(|id| mentees.iter().position(|mentee| mentee.id == id))
+            .map_or(0, |index| index + 1);
+
+        let selected = Select::new("Was it for a particular mentee?", options)
+            .with_starting_cursor(starting_cursor)
+            .prompt()?;
+
+        Ok(mentees
+            .iter()
+            .find(|mentee| mentee.name == selected)
+            .map(|mentee| mentee.id))
+    }
+
+    /// Asks for a receipt file, stored as an absolute path so it opens from anywhere
+    fn prompt_receipt(current: Option<&str>) -> Result<Option<String>, MenteeError> {
+        let receipt = Text::new("Path to the receipt (optional):")
+            .with_initial_value(current.unwrap_or(""))
+            .with_validator(|input: &str| {
+                let input = input.trim();
+                if input.is_empty() || Path::new(input).is_file() {
+                    Ok(Validation::Valid)
+                } else {
+                    Ok(Validation::Invalid(
+                        format!("No file found at '{}'", input).into(),
+                    ))
+                }
+            })
+            .prompt()?;
+
+        let receipt = receipt.trim();
+        if receipt.is_empty() {
+            return Ok(None);
+        }
+
+        let path = std::fs::canonicalize(receipt)?;
+
+        Ok(Some(path.display().to_string()))
+    }
+
+    fn prompt_expense(&self, current: Option<&Expense>) -> Result<Expense, MenteeError> {
+        let mut date_select = DateSelect::new("Enter the date of the expense:");
+        if let Some(parsed) = current.and_then(|expense| parse_date_from_db(&expense.date).ok()) {
+            date_select = date_select.with_default(parsed);
+        }
+        let date = date_select.prompt()?.format("%Y-%m-%d").to_string();
+
+        let category = Self::select_category(current.map(|expense| expense.category))?;
+
+        let description = Text::new("What was it?")
+            .with_placeholder("e.g., Notion subscription")
+            .with_initial_value(current.map_or("", |expense| expense.description.as_str()))
+            .prompt()?;
+
+        let mut amount_prompt = CustomType::<u32>::new("How much?");
+        let starting_amount = current.map(|expense| expense.amount.to_string());
+        if let Some(starting_amount) = starting_amount.as_deref() {
+            amount_prompt = amount_prompt.with_starting_input(starting_amount);
+        }
+        let amount = amount_prompt.prompt()?;
+
+        let currency =
+            prompt_currency(current.map_or(self.settings.currency.as_str(), |expense| {
+                expense.currency.as_str()
+            }))?;
+
+        let mentee_id = self.select_mentee(current.and_then(|expense| expense.mentee_id))?;
+
+        let receipt_path =
+            Self::prompt_receipt(current.and_then(|expense| expense.receipt_path.as_deref()))?;
+
+        Ok(Expense {
+            id: current.map_or(0, |expense| expense.id),
+            date,
+            category,
+            description: description.trim().to_string(),
+            amount,
+            currency,
+            mentee_id,
+            receipt_path,
+        })
+    }
+
+    pub fn add_expense(&self) -> Result<String, MenteeError> {
+        let expense = self.prompt_expense(None)?;
+
+        match self.expense_repo.add_expense(&expense) {
+            Ok(_) => {
+                let mentee_name = match expense.mentee_id {
+                    Some(id) => self.mentee_repo.get_mentee_name(id.into())?,
+                    None => None,
+                };
+
+                self.hooks.fire(
+                    HookEvent::ExpenseAdded,
+                    mentee_name.as_deref().unwrap_or(""),
+                    json!({
+                        "date": expense.date,
+                        "category": expense.category.as_str(),
+                        "description": expense.description,
+                        "amount": expense.amount,
+                        "currency": expense.currency,
+                        "receipt_path": expense.receipt_path,
+                    }),
+                );
+
+                Ok(format!(
+                    "Expense of {} {} on {} added",
+                    expense.amount, expense.currency, expense.date
+                )
+                .green()
+                .to_string())
+            }
+            Err(err) => Err(MenteeError::DatabaseError(err)),
+        }
+    }
+
+    pub fn get_all_expenses(
+        &self,
+        name: Option<String>,
+    ) -> Result<Vec<ExpenseWithMenteeName>, MenteeError> {
+        self.list_expenses(name, &ListOptions::default())
+    }
+
+    /// Expenses filtered, sorted and paged as the options say
+    pub fn list_expenses(
+        &self,
+        name: Option<String>,
+        options: &ListOptions<ExpenseSort>,
+    ) -> Result<Vec<ExpenseWithMenteeName>, MenteeError> {
+        let mentee_id = if let Some(name) = name {
+            match self.mentee_repo.get_mentee_id(&name.to_lowercase())? {
+                Some(id) => Some(id),
+                None => {
+                    return Err(MenteeError::NotFound(format!(
+                        "No mentee found with name '{}'",
+                        name
+                    )))
+                }
+            }
+        } else {
+            None
+        };
+
+        self.expense_repo
+            .get_all_expenses(mentee_id, options)
+            .map_err(MenteeError::DatabaseError)
+    }
+
+    pub fn update_expense(&self, expense_id: u32) -> Result<String, MenteeError> {
+        let expense = self
+            .expense_repo
+            .get_expense_by_id(expense_id)
+            .map_err(|_| MenteeError::NotFound(format!("Expense with id {}", expense_id)))?;
+
+        let updated = self.prompt_expense(Some(&expense))?;
+
+        match self.expense_repo.update_expense(&updated) {
+            Ok(_) => Ok(format!("Expense with id {} updated", expense_id)),
+            Err(err) => Err(MenteeError::DatabaseError(err)),
+        }
+    }
+
+    pub fn delete_expense(&self, expense_id: u32) -> Result<String, MenteeError> {
+        match self.expense_repo.delete_expense(expense_id) {
+            Ok(0) => Err(MenteeError::NotFound(format!(
+                "Expense with id {}",
+                expense_id
+            ))),
+            Ok(_) => Ok(format!("Expense with id {} deleted", expense_id)),
+            Err(err) => Err(MenteeError::DatabaseError(err)),
+        }
+    }
+}
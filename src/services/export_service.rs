@@ -0,0 +1,41 @@
+use rusqlite::Connection;
+
+use crate::{
+    error::MenteeError,
+    models::mentee::MenteeWithContacts,
+    repositories::{ContactRepository, MenteeRepository, SqliteMenteeRepository},
+};
+
+pub struct ExportService<'a> {
+    contact_repo: ContactRepository<'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+}
+
+impl<'a> ExportService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self {
+            contact_repo: ContactRepository::new(conn),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+        }
+    }
+
+    /// Collects every mentee with their contact methods attached
+    pub fn export_mentees(&self, show_all: bool) -> Result<Vec<MenteeWithContacts>, MenteeError> {
+        let mentees = self.mentee_repo.get_mentees(show_all)?;
+        let contacts = self.contact_repo.get_all_contacts()?;
+
+        let records = mentees
+            .into_iter()
+            .map(|mentee| MenteeWithContacts {
+                contacts: contacts
+                    .iter()
+                    .filter(|contact| contact.mentee_id == mentee.id as i64)
+                    .cloned()
+                    .collect(),
+                mentee,
+            })
+            .collect();
+
+        Ok(records)
+    }
+}
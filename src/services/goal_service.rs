@@ -0,0 +1,333 @@
+use colored::Colorize;
+use inquire::{DateSelect, Select, Text};
+use rusqlite::Connection;
+
+use crate::{
+    error::MenteeError,
+    models::goal::{Goal, GoalDetails, GoalStatus, GoalWithProgress},
+    repositories::{
+        CallRepository, GoalRepository, MenteeRepository, SqliteCallRepository,
+        SqliteMenteeRepository,
+    },
+    utils::validation::parse_date_from_db,
+};
+
+pub struct GoalService<'a> {
+    call_repo: Box<dyn CallRepository + 'a>,
+    goal_repo: GoalRepository<'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+}
+
+impl<'a> GoalService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self {
+            call_repo: Box::new(SqliteCallRepository::new(conn)),
+            goal_repo: GoalRepository::new(conn),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+        }
+    }
+
+    fn find_mentee_id(&self, name: &str) -> Result<i64, MenteeError> {
+        self.mentee_repo
+            .get_mentee_id(&name.to_lowercase())?
+            .ok_or_else(|| MenteeError::NotFound(format!("No mentee found with name '{}'", name)))
+    }
+
+    fn find_goal(&self, goal_id: u32) -> Result<Goal, MenteeError> {
+        self.goal_repo
+            .get_goal_by_id(goal_id)
+            .map_err(|_| MenteeError::NotFound(format!("Goal with id {}", goal_id)))
+    }
+
+    /// Lists goals for one mentee, or the open goals of every active mentee when no
+    /// name is given. `all` includes achieved and abandoned goals.
+    pub fn get_goals(
+        &self,
+        name: Option<String>,
+        all: bool,
+    ) -> Result<Vec<GoalWithProgress>, MenteeError> {
+        let mentee_id = match name {
+            Some(name) => Some(self.find_mentee_id(&name)?),
+            None => None,
+        };
+
+        self.goal_repo
+            .get_goals_with_progress(mentee_id, !all, mentee_id.is_none())
+            .map_err(MenteeError::DatabaseError)
+    }
+
+    pub fn add_goal(&self, name: String) -> Result<String, MenteeError> {
+        let mentee_id = self.find_mentee_id(&name)?;
+
+        let title = Text::new("What is the goal?")
+            .with_placeholder("e.g. Land a frontend role")
+            .prompt()?;
+
+        let target_date = DateSelect::new("When should it be achieved by?")
+            .with_help_message("Press esc to skip")
+            .prompt_skippable()?
+            .map(|date| date.format("%Y-%m-%d").to_string());
+
+        self.goal_repo.add_goal(Goal {
+            id: 0,
+            mentee_id,
+            title: title.clone(),
+            target_date,
+            status: GoalStatus::Open,
+        })?;
+
+        Ok(format!("Goal '{title}' added for {name}")
+            .green()
+            .to_string())
+    }
+
+    pub fn update_goal(&self, goal_id: u32) -> Result<String, MenteeError> {
+        let goal = self.find_goal(goal_id)?;
+
+        let title = Text::new("What is the goal?")
+            .with_initial_value(&goal.title)
+            .prompt()?;
+
+        let mut date_select = DateSelect::new("When should it be achieved by?")
+            .with_help_message("Press esc to skip");
+        if let Some(parsed) = goal
+            .target_date
+            .as_deref()
+            .and_then(|date| parse_date_from_db(date).ok())
+        {
+            date_select = date_select.with_default(parsed);
+        }
+        let target_date = date_select
+            .prompt_skippable()?
+            .map(|date| date.format("%Y-%m-%d").to_string());
+
+        let current = GoalStatus::variants()
+            .iter()
+            .position(|status| *status == goal.status.as_str())
+            .unwrap_or(0);
+        let selected = Select::new("What is the status of the goal?", GoalStatus::variants())
+            .with_starting_cursor(current)
+            .prompt()?;
+        let status = GoalStatus::from_str(selected).unwrap_or(goal.status);
+
+        let updated = self.goal_repo.update_goal(&Goal {
+            title,
+            target_date,
+            status,
+            ..goal
+        })?;
+
+        Ok(format!("{updated} goal record updated"))
+    }
+
+    pub fn delete_goal(&self, goal_id: u32) -> Result<String, MenteeError> {
+        match self.goal_repo.delete_goal(goal_id) {
+            Ok(0) => Err(MenteeError::NotFound(format!("Goal with id {}", goal_id))),
+            Ok(_) => Ok(format!("Goal with id {} deleted", goal_id)),
+            Err(err) => Err(MenteeError::DatabaseError(err)),
+        }
+    }
+
+    /// Fetches a goal with its milestones and the calls that advanced it
+    pub fn get_goal_details(&self, goal_id: u32) -> Result<GoalDetails, MenteeError> {
+        let goal = self.find_goal(goal_id)?;
+
+        let progress = self
+            .goal_repo
+            .get_goals_with_progress(Some(goal.mentee_id), false, false)?
+            .into_iter()
+            .find(|progress| progress.id == goal_id)
+            .ok_or_else(|| MenteeError::NotFound(format!("Goal with id {}", goal_id)))?;
+
+        Ok(GoalDetails {
+            goal: progress,
+            milestones: self.goal_repo.get_milestones(goal_id)?,
+            linked_calls: self.goal_repo.get_linked_calls(goal_id)?,
+        })
+    }
+
+    pub fn add_milestone(&self, goal_id: u32, title: String) -> Result<String, MenteeError> {
+        self.find_goal(goal_id)?;
+
+        if title.trim().is_empty() {
+            return Err(MenteeError::InvalidInput(
+                "Milestone title cannot be empty".to_string(),
+            ));
+        }
+
+        self.goal_repo.add_milestone(goal_id, title.trim())?;
+
+        Ok(format!(
+            "Milestone '{}' added to goal {}",
+            title.trim(),
+            goal_id
+        ))
+    }
+
+    pub fn set_milestone_done(&self, milestone_id: u32, done: bool) -> Result<String, MenteeError> {
+        match self.goal_repo.set_milestone_done(milestone_id, done) {
+            Ok(0) => Err(MenteeError::NotFound(format!(
+                "Milestone with id {}",
+                milestone_id
+            ))),
+            Ok(_) if done => Ok(format!("Milestone {} completed", milestone_id)
+                .green()
+                .to_string()),
+            Ok(_) => Ok(format!("Milestone {} reopened", milestone_id)),
+            Err(err) => Err(MenteeError::DatabaseError(err)),
+        }
+    }
+
+    pub fn delete_milestone(&self, milestone_id: u32) -> Result<String, MenteeError> {
+        match self.goal_repo.delete_milestone(milestone_id) {
+            Ok(0) => Err(MenteeError::NotFound(format!(
+                "Milestone with id {}",
+                milestone_id
+            ))),
+            Ok(_) => Ok(format!("Milestone with id {} deleted", milestone_id)),
+            Err(err) => Err(MenteeError::DatabaseError(err)),
+        }
+    }
+
+    /// Links a call to a goal of the same mentee
+    pub fn link_call(&self, call_id: u32, goal_id: u32) -> Result<String, MenteeError> {
+        let goal = self.find_goal(goal_id)?;
+        let call = self.call_repo.get_call_by_id(call_id).map_err(|_| {
+            MenteeError::NotFound(format!("Can't find a call with id of {}", call_id))
+        })?;
+
+        if call.mentee_id != goal.mentee_id {
+            return Err(MenteeError::InvalidInput(format!(
+                "Call {} and goal {} belong to different mentees",
+                call_id, goal_id
+            )));
+        }
+
+        self.goal_repo.link_call(call_id as i64, goal_id)?;
+
+        Ok(format!("Call {} linked to goal '{}'", call_id, goal.title))
+    }
+
+    pub fn unlink_call(&self, call_id: u32, goal_id: u32) -> Result<String, MenteeError> {
+        match self.goal_repo.unlink_call(call_id as i64, goal_id) {
+            Ok(0) => Err(MenteeError::NotFound(format!(
+                "Link between call {} and goal {}",
+                call_id, goal_id
+            ))),
+            Ok(_) => Ok(format!("Call {} unlinked from goal {}", call_id, goal_id)),
+            Err(err) => Err(MenteeError::DatabaseError(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::goal::GoalStatus,
+        models::{
+            call::CallInput,
+            mentee::{NewMentee, Status},
+        },
+        Store,
+    };
+
+    /// A store with a goal for dan, one milestone and a call for each of dan and amy
+    fn store() -> Store {
+        let store = Store::open(":memory:").unwrap();
+        for name in ["dan", "amy"] {
+            store
+                .mentees()
+                .create_mentee(NewMentee {
+                    name: name.to_string(),
+                    calls: 2,
+                    gross: 200,
+                    net: 180,
+                    status: Status::Hot,
+                    payment_day: 5,
+                    notes: None,
+                    source: None,
+                    referrer: None,
+                })
+                .unwrap();
+            store
+                .calls()
+                .create_call(
+                    name,
+                    CallInput {
+                        date: "2026-03-02".to_string(),
+                        notes: None,
+                        free_call: false,
+                        duration: None,
+                        goal_ids: vec![],
+                    },
+                )
+                .unwrap();
+        }
+
+        let goals = GoalRepository::new(store.connection());
+        goals
+            .add_goal(Goal {
+                id: 0,
+                mentee_id: 1,
+                title: "Land a staff role".to_string(),
+                target_date: None,
+                status: GoalStatus::Open,
+            })
+            .unwrap();
+        goals.add_milestone(1, "Update CV").unwrap();
+
+        store
+    }
+
+    #[test]
+    fn links_only_calls_of_the_goals_mentee() {
+        let store = store();
+        let service = GoalService::new(store.connection());
+
+        // call 2 is amy's, the goal is dan's
+        assert!(matches!(
+            service.link_call(2, 1),
+            Err(MenteeError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            service.link_call(99, 1),
+            Err(MenteeError::NotFound(_))
+        ));
+        assert!(matches!(
+            service.link_call(1, 99),
+            Err(MenteeError::NotFound(_))
+        ));
+
+        service.link_call(1, 1).unwrap();
+        let details = service.get_goal_details(1).unwrap();
+        assert_eq!(details.linked_calls.len(), 1);
+        assert_eq!(details.linked_calls[0].call_id, 1);
+    }
+
+    #[test]
+    fn completes_and_reopens_milestones() {
+        let store = store();
+        let service = GoalService::new(store.connection());
+
+        service.set_milestone_done(1, true).unwrap();
+        let details = service.get_goal_details(1).unwrap();
+        assert!(details.milestones[0].done);
+        assert_eq!(details.goal.milestones_done, 1);
+
+        assert_eq!(
+            service.set_milestone_done(1, false).unwrap(),
+            "Milestone 1 reopened"
+        );
+        assert!(!service.get_goal_details(1).unwrap().milestones[0].done);
+
+        assert!(matches!(
+            service.set_milestone_done(99, true),
+            Err(MenteeError::NotFound(_))
+        ));
+        assert!(matches!(
+            service.add_milestone(1, "  ".to_string()),
+            Err(MenteeError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,461 @@
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
+
+use rusqlite::Connection;
+
+use crate::{
+    config::Config,
+    error::MenteeError,
+    models::{
+        call::Call,
+        payment::{Payment, PaymentInput},
+        video::{Video, VideoInput, VideoStatus},
+    },
+    repositories::{
+        CallRepository, MenteeRepository, PaymentRepository, SqliteCallRepository,
+        SqliteMenteeRepository, SqlitePaymentRepository, SqliteVideoRepository, VideoRepository,
+    },
+    services::{PaymentService, VideoService},
+    utils::validation::normalize_date,
+};
+
+/// The kind of record each row of an imported CSV becomes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Calls,
+    Payments,
+    Videos,
+}
+
+impl ImportKind {
+    /// Fields a column can be mapped to, the first ones are required
+    fn fields(&self) -> &'static [&'static str] {
+        match self {
+            ImportKind::Calls => &["mentee", "date", "notes", "free_call", "duration"],
+            ImportKind::Payments => &["mentee", "date", "amount", "currency", "fee", "source"],
+            ImportKind::Videos => &[
+                "mentee",
+                "date",
+                "length",
+                "notes",
+                "status",
+                "received_date",
+                "due_date",
+                "source_url",
+                "file_path",
+            ],
+        }
+    }
+
+    fn required(&self) -> &'static [&'static str] {
+        match self {
+            ImportKind::Payments => &self.fields()[..3],
+            _ => &self.fields()[..2],
+        }
+    }
+
+    fn noun(&self) -> &'static str {
+        match self {
+            ImportKind::Calls => "calls",
+            ImportKind::Payments => "payments",
+            ImportKind::Videos => "videos",
+        }
+    }
+}
+
+/// A row that passed validation, waiting for the rest of the file
+enum ImportRecord {
+    Call(Call),
+    Payment(Payment),
+    Video(Video),
+}
+
+/// One CSV row with its values looked up by field rather than column
+struct Row<'r> {
+    record: &'r csv::StringRecord,
+    columns: &'r HashMap<&'static str, usize>,
+}
+
+impl Row<'_> {
+    /// The trimmed value of a field, nothing when it's empty or not mapped
+    fn get(&self, field: &str) -> Option<&str> {
+        self.columns
+            .get(field)
+            .and_then(|index| self.record.get(*index))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    }
+
+    fn required(&self, field: &str) -> Result<&str, String> {
+        self.get(field).ok_or_else(|| format!("{} is empty", field))
+    }
+
+    fn number(&self, field: &str) -> Result<Option<u32>, String> {
+        self.get(field)
+            .map(|value| {
+                value
+                    .parse::<u32>()
+                    .map_err(|_| format!("{} must be a whole number, got '{}'", field, value))
+            })
+            .transpose()
+    }
+}
+
+/// The message of an error, without the prefix it gets when printed on its own
+fn row_error(err: MenteeError) -> String {
+    match err {
+        MenteeError::InvalidInput(msg) | MenteeError::ValidationError(msg) => msg,
+        other => other.to_string(),
+    }
+}
+
+pub struct ImportService<'a> {
+    conn: &'a Connection,
+    config: &'a Config,
+    call_repo: Box<dyn CallRepository + 'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    payment_repo: Box<dyn PaymentRepository + 'a>,
+    video_repo: Box<dyn VideoRepository + 'a>,
+}
+
+impl<'a> ImportService<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self {
+            conn,
+            config,
+            call_repo: Box::new(SqliteCallRepository::new(conn)),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            payment_repo: Box::new(SqlitePaymentRepository::new(conn)),
+            video_repo: Box::new(SqliteVideoRepository::new(conn)),
+        }
+    }
+
+    /// Imports every row of a CSV file or none of them. Columns are matched to
+    /// fields by name unless `mappings` says otherwise, e.g. `("date", "When")`.
+    /// With `dry_run` the rows are only checked. Hooks don't run for imported records.
+    pub(crate) fn import(
+        &self,
+        kind: ImportKind,
+        path: &Path,
+        mappings: &[(String, String)],
+        dry_run: bool,
+    ) -> Result<String, MenteeError> {
+        let file = File::open(path).map_err(|err| {
+            MenteeError::InvalidInput(format!("can't read '{}': {}", path.display(), err))
+        })?;
+
+        self.import_from_reader(kind, file, mappings, dry_run)
+    }
+
+    fn import_from_reader(
+        &self,
+        kind: ImportKind,
+        reader: impl Read,
+        mappings: &[(String, String)],
+        dry_run: bool,
+    ) -> Result<String, MenteeError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::Headers)
+            .from_reader(reader);
+        let columns = Self::map_columns(kind, reader.headers()?, mappings)?;
+
+        let mut mentee_ids: HashMap<String, Option<i64>> = HashMap::new();
+        let mut records = Vec::new();
+        let mut errors = Vec::new();
+
+        for result in reader.records() {
+            let record = match result {
+                Ok(record) => record,
+                Err(err) => {
+                    let line = err.position().map_or(0, |position| position.line());
+                    errors.push(format!("line {}: {}", line, err));
+                    continue;
+                }
+            };
+            let line = record.position().map_or(0, |position| position.line());
+            let row = Row {
+                record: &record,
+                columns: &columns,
+            };
+
+            match self.parse_row(kind, &row, &mut mentee_ids) {
+                Ok(parsed) => records.push(parsed),
+                Err(err) => errors.push(format!("line {}: {}", line, err)),
+            }
+        }
+
+        let total = records.len() + errors.len();
+
+        if !errors.is_empty() {
+            return Err(MenteeError::InvalidInput(format!(
+                "{} of {} rows can't be imported, nothing was added\n{}",
+                errors.len(),
+                total,
+                errors.join("\n")
+            )));
+        }
+
+        if dry_run {
+            return Ok(format!(
+                "Checked {} rows without errors, run again without --dry-run to import them",
+                total
+            ));
+        }
+
+        // every row or none of them
+        self.mentee_repo.in_transaction(&mut || {
+            for record in &records {
+                match record {
+                    ImportRecord::Call(call) => {
+                        self.call_repo.add_call(call.clone())?;
+                    }
+                    ImportRecord::Payment(payment) => {
+                        self.payment_repo.add_payment(payment)?;
+                    }
+                    ImportRecord::Video(video) => {
+                        self.video_repo.add_video(video.clone())?;
+                    }
+                }
+            }
+
+            Ok(())
+        })?;
+
+        Ok(format!("Imported {} {}", total, kind.noun()))
+    }
+
+    /// Finds the column of each field, by the header named in `mappings` or
+    /// else the header with the field's own name
+    fn map_columns(
+        kind: ImportKind,
+        headers: &csv::StringRecord,
+        mappings: &[(String, String)],
+    ) -> Result<HashMap<&'static str, usize>, MenteeError> {
+        let position = |column: &str| {
+            headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(column))
+        };
+
+        for (field, _) in mappings {
+            if !kind.fields().contains(&field.as_str()) {
+                return Err(MenteeError::InvalidInput(format!(
+                    "'{}' isn't a field of {}, use one of {}",
+                    field,
+                    kind.noun(),
+                    kind.fields().join(", ")
+                )));
+            }
+        }
+
+        let mut columns = HashMap::new();
+
+        for field in kind.fields() {
+            let mapped = mappings.iter().rev().find(|(name, _)| name == field);
+
+            let index = match mapped {
+                Some((_, column)) => Some(position(column).ok_or_else(|| {
+                    MenteeError::InvalidInput(format!("the CSV has no '{}' column", column))
+                })?),
+                None => position(field),
+            };
+
+            match index {
+                Some(index) => {
+                    columns.insert(*field, index);
+                }
+                None if kind.required().contains(field) => {
+                    return Err(MenteeError::InvalidInput(format!(
+                        "the CSV needs a '{}' column, or map one with --map {}=<column>",
+                        field, field
+                    )))
+                }
+                None => {}
+            }
+        }
+
+        Ok(columns)
+    }
+
+    fn parse_row(
+        &self,
+        kind: ImportKind,
+        row: &Row,
+        mentee_ids: &mut HashMap<String, Option<i64>>,
+    ) -> Result<ImportRecord, String> {
+        let name = row.required("mentee")?.to_lowercase();
+        let mentee_id = match mentee_ids.get(&name) {
+            Some(id) => *id,
+            None => {
+                let id = self.mentee_repo.get_mentee_id(&name).map_err(row_error)?;
+                mentee_ids.insert(name.clone(), id);
+                id
+            }
+        }
+        .ok_or_else(|| format!("no mentee named '{}'", name))?;
+
+        let date = normalize_date(row.required("date")?)?;
+
+        match kind {
+            ImportKind::Calls => {
+                let free_call = match row.get("free_call").map(str::to_lowercase).as_deref() {
+                    None | Some("false" | "no" | "0") => false,
+                    Some("true" | "yes" | "1") => true,
+                    Some(other) => {
+                        return Err(format!("free_call must be true or false, got '{}'", other))
+                    }
+                };
+
+                Ok(ImportRecord::Call(Call {
+                    id: 0,
+                    mentee_id,
+                    date,
+                    notes: row.get("notes").map(str::to_string),
+                    free_call,
+                    duration: row.number("duration")?,
+                }))
+            }
+            ImportKind::Payments => {
+                let amount = row.number("amount")?.unwrap_or_default();
+                if amount == 0 {
+                    return Err("amount must be more than 0".to_string());
+                }
+
+                let input = PaymentInput {
+                    date,
+                    amount,
+                    currency: row.get("currency").map(str::to_string),
+                    fee: row.number("fee")?.unwrap_or_default(),
+                    source: row.get("source").map(str::to_string),
+                };
+
+                PaymentService::new(self.conn, self.config)
+                    .payment_from_input(mentee_id as u32, input)
+                    .map(ImportRecord::Payment)
+                    .map_err(row_error)
+            }
+            ImportKind::Videos => {
+                let status = match row.get("status") {
+                    None => VideoStatus::Delivered,
+                    Some(status) => VideoStatus::from_str(status).ok_or_else(|| {
+                        format!(
+                            "status must be requested, in_progress or delivered, got '{}'",
+                            status
+                        )
+                    })?,
+                };
+
+                let input = VideoInput {
+                    date,
+                    length: row.number("length")?.unwrap_or_default(),
+                    notes: row.get("notes").unwrap_or_default().to_string(),
+                    status,
+                    received_date: row.get("received_date").map(str::to_string),
+                    due_date: row.get("due_date").map(str::to_string),
+                    source_url: row.get("source_url").map(str::to_string),
+                    file_path: row.get("file_path").map(str::to_string),
+                };
+
+                VideoService::video_from_input(mentee_id, input, None)
+                    .map(ImportRecord::Video)
+                    .map_err(row_error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::mentee::{NewMentee, Status},
+        Store,
+    };
+
+    fn store() -> Store {
+        let store = Store::open(":memory:").unwrap();
+        store
+            .mentees()
+            .create_mentee(NewMentee {
+                name: "Dan Page".to_string(),
+                calls: 2,
+                gross: 200,
+                net: 180,
+                status: Status::Hot,
+                payment_day: 5,
+                notes: None,
+                source: None,
+                referrer: None,
+            })
+            .unwrap();
+        store
+    }
+
+    #[test]
+    fn imports_calls_with_mapped_columns() {
+        let store = store();
+        let service = ImportService::new(store.connection(), store.config());
+        let csv = "Who,When,Notes\nDan Page,2026-01-05,Mock interview\ndan page,2026-1-12,\n";
+        let mappings = vec![
+            ("mentee".to_string(), "Who".to_string()),
+            ("date".to_string(), "When".to_string()),
+        ];
+
+        let message = service
+            .import_from_reader(ImportKind::Calls, csv.as_bytes(), &mappings, false)
+            .unwrap();
+
+        assert_eq!(message, "Imported 2 calls");
+        let calls = store.calls().get_all_calls(None).unwrap();
+        assert_eq!(calls.len(), 2);
+        assert!(calls
+            .iter()
+            .any(|call| call.notes.as_deref() == Some("Mock interview")));
+        assert!(calls.iter().any(|call| call.date == "2026-01-12"));
+    }
+
+    #[test]
+    fn reports_every_bad_row_and_adds_nothing() {
+        let store = store();
+        let service = ImportService::new(store.connection(), store.config());
+        let csv = "mentee,date,amount\n\
+                   dan page,2026-01-05,100\n\
+                   amy,2026-01-05,100\n\
+                   dan page,05/01/2026,100\n\
+                   dan page,2026-02-05,0\n";
+
+        let err = service
+            .import_from_reader(ImportKind::Payments, csv.as_bytes(), &[], false)
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("3 of 4 rows can't be imported"));
+        assert!(err.contains("line 3: no mentee named 'amy'"));
+        assert!(err.contains("line 4: '05/01/2026' is not a date"));
+        assert!(err.contains("line 5: amount must be more than 0"));
+        assert!(store.payments().get_all_payments(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn dry_run_checks_without_writing() {
+        let store = store();
+        let service = ImportService::new(store.connection(), store.config());
+        let csv = "mentee,date,status,length\ndan page,2026-01-05,requested,\n";
+
+        let message = service
+            .import_from_reader(ImportKind::Videos, csv.as_bytes(), &[], true)
+            .unwrap();
+
+        assert!(message.starts_with("Checked 1 rows without errors"));
+        assert!(store.videos().get_all_videos(None).unwrap().is_empty());
+
+        let err = service
+            .import_from_reader(
+                ImportKind::Videos,
+                csv.as_bytes(),
+                &[("length".to_string(), "Minutes".to_string())],
+                true,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("the CSV has no 'Minutes' column"));
+    }
+}
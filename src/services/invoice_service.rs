@@ -0,0 +1,152 @@
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::{
+    config::{Config, InvoiceConfig, IssuerConfig},
+    error::MenteeError,
+    models::invoice::Invoice,
+    repositories::{InvoiceRepository, PaymentRepository, SqlitePaymentRepository},
+    utils::dates::today,
+    InvoiceFormat,
+};
+
+const DEFAULT_HTML_TEMPLATE: &str = include_str!("../templates/invoice.html");
+const DEFAULT_TEXT_TEMPLATE: &str = include_str!("../templates/invoice.txt");
+
+pub struct InvoiceService<'a> {
+    invoice_repo: InvoiceRepository<'a>,
+    payment_repo: Box<dyn PaymentRepository + 'a>,
+    issuer: &'a IssuerConfig,
+    settings: &'a InvoiceConfig,
+    default_currency: &'a str,
+    config_path: String,
+}
+
+impl<'a> InvoiceService<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self {
+            invoice_repo: InvoiceRepository::new(conn),
+            payment_repo: Box::new(SqlitePaymentRepository::new(conn)),
+            issuer: &config.issuer,
+            settings: &config.invoice,
+            default_currency: &config.payments.currency,
+            config_path: config.config_path.display().to_string(),
+        }
+    }
+
+    pub fn number_prefix(&self) -> &str {
+        &self.settings.number_prefix
+    }
+
+    fn ensure_issuer_configured(&self) -> Result<(), MenteeError> {
+        if self.issuer.name.trim().is_empty() {
+            return Err(MenteeError::ConfigError(format!(
+                "set your name, address and email under [issuer] in {} before creating invoices",
+                self.config_path
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn describe_created(&self, numbers: &[u32]) -> String {
+        let label = |number: u32| format!("{}{:04}", self.settings.number_prefix, number);
+
+        match numbers {
+            [number] => format!("Created invoice {}", label(*number)),
+            [first, .., last] => format!(
+                "Created {} invoices, {} to {}",
+                numbers.len(),
+                label(*first),
+                label(*last)
+            ),
+            [] => "No invoices created".to_string(),
+        }
+    }
+
+    pub fn create_invoice(&self, payment_id: u32) -> Result<String, MenteeError> {
+        self.ensure_issuer_configured()?;
+
+        self.payment_repo
+            .get_payment_by_id(payment_id)
+            .map_err(|_| MenteeError::NotFound(format!("Payment with id {}", payment_id)))?;
+
+        if let Some(invoice) = self.invoice_repo.get_invoice_by_payment_id(payment_id)? {
+            return Err(MenteeError::InvalidInput(format!(
+                "payment {} is already on invoice {}",
+                payment_id,
+                invoice.display_number(&self.settings.number_prefix)
+            )));
+        }
+
+        let numbers = self.invoice_repo.create_invoices(
+            &[payment_id],
+            &today(),
+            self.default_currency,
+            self.issuer,
+        )?;
+
+        Ok(self.describe_created(&numbers).green().to_string())
+    }
+
+    /// Invoices every payment in the month (YYYY-MM) that doesn't have one yet
+    pub fn create_month_invoices(&self, month: String) -> Result<String, MenteeError> {
+        self.ensure_issuer_configured()?;
+
+        let payment_ids = self.invoice_repo.get_uninvoiced_payment_ids(&month)?;
+
+        if payment_ids.is_empty() {
+            return Ok(format!("No payments without an invoice in {}", month));
+        }
+
+        let numbers = self.invoice_repo.create_invoices(
+            &payment_ids,
+            &today(),
+            self.default_currency,
+            self.issuer,
+        )?;
+
+        Ok(self.describe_created(&numbers).green().to_string())
+    }
+
+    pub fn get_invoices(&self) -> Result<Vec<Invoice>, MenteeError> {
+        self.invoice_repo
+            .get_all_invoices()
+            .map_err(MenteeError::DatabaseError)
+    }
+
+    /// Accepts the number as printed (`INV-0007`) or just the digits (`7`)
+    pub fn get_invoice(&self, number: &str) -> Result<Invoice, MenteeError> {
+        let digits = number
+            .trim()
+            .strip_prefix(self.settings.number_prefix.as_str())
+            .unwrap_or(number.trim());
+
+        let parsed: u32 = digits.parse().map_err(|_| {
+            MenteeError::InvalidInput(format!("'{}' is not an invoice number", number))
+        })?;
+
+        self.invoice_repo
+            .get_invoice_by_number(parsed)?
+            .ok_or_else(|| MenteeError::NotFound(format!("Invoice {}", number)))
+    }
+
+    /// The template from config when one is set, otherwise the built in one
+    pub fn get_template(&self, format: InvoiceFormat) -> Result<String, MenteeError> {
+        let (custom, default) = match format {
+            InvoiceFormat::Html => (&self.settings.html_template, DEFAULT_HTML_TEMPLATE),
+            InvoiceFormat::Text => (&self.settings.text_template, DEFAULT_TEXT_TEMPLATE),
+        };
+
+        match custom {
+            Some(path) => std::fs::read_to_string(path).map_err(|err| {
+                MenteeError::ConfigError(format!(
+                    "could not read invoice template {}: {}",
+                    path.display(),
+                    err
+                ))
+            }),
+            None => Ok(default.to_string()),
+        }
+    }
+}
@@ -1,34 +1,109 @@
+use chrono::NaiveDate;
 use colored::Colorize;
 use inquire::{CustomType, Text};
 use rusqlite::Connection;
 
 use crate::{
+    config::{CapacityConfig, Config},
     error::MenteeError,
-    models::mentee::{Mentee, MenteeSummary, MenteeWithCounts},
-    repositories::MenteeRepository,
+    hooks::{HookEvent, Hooks},
+    models::{
+        list::ListOptions,
+        mentee::{
+            Mentee, MenteeChanges, MenteeSummary, MenteeWithCounts, NewMentee, Source, Status,
+            StatusChange,
+        },
+    },
+    repositories::{ExpenseRepository, MenteeRepository, SqliteMenteeRepository},
     utils::{
-        ui::select_status,
-        validation::{inquire_validate_day, inquire_validate_name},
+        dates::today,
+        ui::{prompt_notes, select_source, select_status},
+        validation::{
+            clap_validate_date, inquire_validate_day, inquire_validate_name, validate_day_core,
+            validate_name_core,
+        },
     },
-    CountOptions, UpdateMentee,
+    CountOptions, MenteeSort, UpdateMentee,
 };
 
 pub struct MenteeService<'a> {
-    mentee_repo: MenteeRepository<'a>,
+    /// Expenses only live in SQLite, without them they can't be counted
+    expense_repo: Option<ExpenseRepository<'a>>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    hooks: Hooks<'a>,
+    capacity: &'a CapacityConfig,
+    /// Mentees pay in this currency, so only expenses in it come off their net
+    currency: &'a str,
+    /// Minutes each committed call is expected to take
+    minutes_per_call: u32,
 }
 
 impl<'a> MenteeService<'a> {
-    pub fn new(conn: &'a Connection) -> Self {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self {
+            expense_repo: Some(ExpenseRepository::new(conn)),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            hooks: Hooks::new(&config.hooks),
+            capacity: &config.capacity,
+            currency: &config.payments.currency,
+            minutes_per_call: config.calls.default_minutes,
+        }
+    }
+
+    /// Builds the service on another storage backend
+    pub fn with_repositories(mentee_repo: impl MenteeRepository + 'a, config: &'a Config) -> Self {
         Self {
-            mentee_repo: MenteeRepository::new(conn),
+            expense_repo: None,
+            mentee_repo: Box::new(mentee_repo),
+            hooks: Hooks::new(&config.hooks),
+            capacity: &config.capacity,
+            currency: &config.payments.currency,
+            minutes_per_call: config.calls.default_minutes,
+        }
+    }
+
+    /// Checks the calls a month committed to once `name`, or a new mentee when
+    /// `None`, commits to `calls` a month. Going over capacity is refused when
+    /// `strict`, otherwise it's returned as a warning. Commitments that don't
+    /// grow are never refused.
+    fn check_capacity(
+        &self,
+        name: Option<&str>,
+        calls: u32,
+        strict: bool,
+    ) -> Result<Option<String>, MenteeError> {
+        let current = match name {
+            Some(name) => self
+                .mentee_repo
+                .get_mentee(name)?
+                .filter(|mentee| mentee.status != Status::Archived)
+                .map_or(0, |mentee| mentee.calls),
+            None => 0,
+        };
+
+        if calls <= current {
+            return Ok(None);
+        }
+
+        let committed = self
+            .mentee_repo
+            .get_mentee_count(Some(CountOptions::Calls), None)?;
+        let total = (committed - current as i64 + calls as i64).max(0) as u32;
+
+        match self.capacity.exceeded_by(total, self.minutes_per_call) {
+            Some(reason) if strict => Err(MenteeError::InvalidInput(format!(
+                "{}, see `mentees capacity`",
+                reason
+            ))),
+            Some(reason) => Ok(Some(format!("Warning: {}", reason).yellow().to_string())),
+            None => Ok(None),
         }
     }
 
-    pub fn add_mentee(&self) -> Result<String, MenteeError> {
+    pub(crate) fn add_mentee(&self, strict: bool) -> Result<String, MenteeError> {
         let name = Text::new("What is their name?")
             .with_validator(inquire_validate_name)
-            .prompt()?
-            .to_lowercase();
+            .prompt()?;
 
         let calls = inquire::prompt_u32("How many calls per month do they have?")?;
         let gross = inquire::prompt_u32("What is the gross payment?")?;
@@ -37,37 +112,144 @@ impl<'a> MenteeService<'a> {
         let payment_day: u32 = CustomType::new("Which day of the month do they pay?")
             .with_validator(inquire_validate_day)
             .prompt()?;
-        let notes = Text::new("Any notes about them?").prompt()?;
+        let notes = prompt_notes("Any notes about them?", None)?;
+        let source = select_source()?;
+        let referrer = match source {
+            Some(Source::Referral) => Text::new("Who referred them?")
+                .with_help_message("esc to skip")
+                .prompt_skippable()?
+                .filter(|referrer| !referrer.trim().is_empty()),
+            _ => None,
+        };
 
-        let mentee = Mentee {
-            id: 0,
-            name: name.clone(),
+        let warning = match status {
+            Status::Archived => None,
+            _ => self.check_capacity(None, calls, strict)?,
+        };
+
+        let mentee = self.create_mentee(NewMentee {
+            name,
             calls,
             gross,
             net,
             status,
             payment_day,
             notes: Some(notes),
+            source,
+            referrer,
+        })?;
+
+        let message = format!("Added Mentee: {}", mentee.name);
+        Ok(match warning {
+            Some(warning) => format!("{}\n{}", message, warning),
+            None => message,
+        })
+    }
+
+    /// Adds a mentee without prompting, recording their starting status
+    pub fn create_mentee(&self, new_mentee: NewMentee) -> Result<Mentee, MenteeError> {
+        validate_name_core(&new_mentee.name).map_err(MenteeError::InvalidInput)?;
+        validate_day_core(&new_mentee.payment_day).map_err(MenteeError::InvalidInput)?;
+
+        let name = new_mentee.name.to_lowercase();
+        let referrer = new_mentee
+            .referrer
+            .as_deref()
+            .map(|referrer| self.find_referrer(referrer, &name))
+            .transpose()?;
+        let source = match (new_mentee.source, &referrer) {
+            (None, Some(_)) => Some(Source::Referral),
+            (source, _) => source,
+        };
+
+        let mentee = Mentee {
+            id: 0,
+            name: name.clone(),
+            calls: new_mentee.calls,
+            gross: new_mentee.gross,
+            net: new_mentee.net,
+            status: new_mentee.status.clone(),
+            payment_day: new_mentee.payment_day,
+            notes: new_mentee.notes,
+            source,
+            referrer,
         };
 
-        let result = self.mentee_repo.add_mentee(mentee);
+        let data = serde_json::to_value(&mentee)?;
 
-        match result {
-            Ok(_) => Ok(name),
-            Err(rusqlite::Error::SqliteFailure(ref err, _)) if err.extended_code == 2067 => {
-                Err(MenteeError::UniqueViolation(name))
-            }
-            Err(err) => Err(MenteeError::from(err)),
-        }
+        // the starting status is where the history behind --as-of begins
+        self.mentee_repo.in_transaction(&mut || {
+            self.mentee_repo.add_mentee(mentee.clone())?;
+
+            let mentee_id = self
+                .mentee_repo
+                .get_mentee_id(&name)?
+                .ok_or_else(|| MenteeError::NotFound(format!("Mentee with name {}", name)))?;
+
+            self.mentee_repo.record_status_change(StatusChange {
+                id: 0,
+                mentee_id,
+                date: today(),
+                from_status: None,
+                to_status: new_mentee.status.clone(),
+                reason: None,
+            })?;
+
+            Ok(())
+        })?;
+
+        self.hooks.fire(HookEvent::MenteeAdded, &name, data);
+
+        self.get_mentee(&name)
     }
 
-    pub fn get_mentees_summaries(&self, show_all: bool) -> Result<Vec<MenteeSummary>, MenteeError> {
-        match self.mentee_repo.get_all_mentees(show_all) {
-            Ok(mentees) => Ok(mentees),
-            Err(err) => Err(MenteeError::DatabaseError(err)),
+    /// The stored name of the mentee who referred `name`, who has to exist
+    /// and can't be `name` themselves
+    fn find_referrer(&self, referrer: &str, name: &str) -> Result<String, MenteeError> {
+        let referrer = referrer.trim().to_lowercase();
+
+        if referrer == name {
+            return Err(MenteeError::InvalidInput(
+                "a mentee can't refer themselves".to_string(),
+            ));
+        }
+
+        match self.mentee_repo.get_mentee_id(&referrer)? {
+            Some(_) => Ok(referrer),
+            None => Err(MenteeError::NotFound(format!(
+                "Mentee with name {}",
+                referrer
+            ))),
         }
     }
 
+    pub fn get_mentee(&self, name: &str) -> Result<Mentee, MenteeError> {
+        self.mentee_repo
+            .get_mentee(&name.to_lowercase())?
+            .ok_or_else(|| MenteeError::NotFound(format!("Mentee with name {}", name)))
+    }
+
+    /// Full records of mentees, archived ones only when `show_all` is set
+    pub fn get_mentees(&self, show_all: bool) -> Result<Vec<Mentee>, MenteeError> {
+        self.mentee_repo.get_mentees(show_all)
+    }
+
+    /// Summaries of mentees filtered, sorted and paged as the options say.
+    /// `since` and `until` apply to the date of their last call, and their
+    /// status is reconstructed from the history when `as_of` is given.
+    pub fn get_mentees_summaries(
+        &self,
+        show_all: bool,
+        as_of: Option<NaiveDate>,
+        status: Option<Status>,
+        options: &ListOptions<MenteeSort>,
+    ) -> Result<Vec<MenteeSummary>, MenteeError> {
+        let as_of = as_of.map(|date| date.format("%Y-%m-%d").to_string());
+
+        self.mentee_repo
+            .get_all_mentees(show_all, as_of.as_deref(), status, options)
+    }
+
     pub fn get_mentee_with_counts(&self, name: String) -> Result<MenteeWithCounts, MenteeError> {
         let normalised_name = name.to_lowercase();
 
@@ -81,27 +263,80 @@ impl<'a> MenteeService<'a> {
             MenteeError::NotFound(format!("No mentee found with name '{}'", name))
         })?;
 
-        match self.mentee_repo.delete_mentee_by_id(mentee_id) {
-            Ok(_) => Ok(format!("Deleted mentee {}", name)),
-            Err(err) => Err(MenteeError::DatabaseError(err)),
-        }
+        self.mentee_repo.delete_mentee_by_id(mentee_id)?;
+
+        Ok(format!("Deleted mentee {}", name))
+    }
+
+    pub fn get_status_history(&self, name: String) -> Result<Vec<StatusChange>, MenteeError> {
+        let mentee_id = self
+            .mentee_repo
+            .get_mentee_id(&name.to_lowercase())?
+            .ok_or_else(|| {
+                MenteeError::NotFound(format!("No mentee found with name '{}'", name))
+            })?;
+
+        self.mentee_repo.get_status_history(mentee_id)
     }
 
-    pub fn get_mentee_count(&self, count: Option<CountOptions>) -> Result<String, MenteeError> {
+    pub fn get_mentee_count(
+        &self,
+        count: Option<CountOptions>,
+        as_of: Option<NaiveDate>,
+    ) -> Result<String, MenteeError> {
+        // only who was active is kept for past dates, not what they paid or
+        // signed up for then, so sums would mix today's values into the past
+        if as_of.is_some()
+            && !matches!(
+                count,
+                None | Some(CountOptions::Mentees) | Some(CountOptions::Expenses)
+            )
+        {
+            return Err(MenteeError::InvalidInput(
+                "--as-of only counts mentees or expenses".to_string(),
+            ));
+        }
+
+        let as_of = as_of.map(|date| date.format("%Y-%m-%d").to_string());
+        // expenses are dated so they're counted for the month of --as-of, or this month
+        let month = as_of.clone().unwrap_or_else(today)[..7].to_string();
+
         let message = match count {
-            Some(CountOptions::Calls) => "Number of calls: ",
-            Some(CountOptions::Gross) => "Gross $",
-            Some(CountOptions::Net) => "Net $",
-            Some(CountOptions::NetPerCall) => "Average net amount per call $",
-            _ => "Number of mentees: ",
+            Some(CountOptions::Calls) => "Number of calls: ".to_string(),
+            Some(CountOptions::Gross) => "Gross $".to_string(),
+            Some(CountOptions::Net) => "Net $".to_string(),
+            Some(CountOptions::NetPerCall) => "Average net amount per call $".to_string(),
+            Some(CountOptions::Expenses) => {
+                format!("{} expenses in {} $", self.currency, month)
+            }
+            Some(CountOptions::Profit) => format!("Profit in {} $", month),
+            _ => "Number of mentees: ".to_string(),
         };
 
-        let count_value = self.mentee_repo.get_mentee_count(count)?;
+        let count_value = match count {
+            Some(CountOptions::Expenses) => self.expense_total(&month)?,
+            Some(CountOptions::Profit) => {
+                self.mentee_repo
+                    .get_mentee_count(Some(CountOptions::Net), None)?
+                    - self.expense_total(&month)?
+            }
+            _ => self.mentee_repo.get_mentee_count(count, as_of.as_deref())?,
+        };
 
         Ok(format!("{}{}", message, count_value))
     }
 
-    pub fn update_mentee(&self, update_args: UpdateMentee) -> Result<String, MenteeError> {
+    fn expense_total(&self, month: &str) -> Result<i64, MenteeError> {
+        match &self.expense_repo {
+            Some(expense_repo) => Ok(expense_repo.get_expense_total(month, self.currency)?),
+            // nothing would look like no spending, so say so instead
+            None => Err(MenteeError::InvalidInput(
+                "expenses are only kept in the SQLite database".to_string(),
+            )),
+        }
+    }
+
+    pub(crate) fn update_mentee(&self, update_args: UpdateMentee) -> Result<String, MenteeError> {
         if update_args.new_name.is_none()
             && update_args.calls.is_none()
             && update_args.gross.is_none()
@@ -109,6 +344,9 @@ impl<'a> MenteeService<'a> {
             && update_args.status.is_none()
             && update_args.payment_day.is_none()
             && update_args.notes.is_none()
+            && update_args.source.is_none()
+            && update_args.referrer.is_none()
+            && !update_args.edit_notes
         {
             return Err(MenteeError::InvalidInput(format!(
                 "{}",
@@ -116,15 +354,418 @@ impl<'a> MenteeService<'a> {
             )));
         }
 
-        let rows_affected = self.mentee_repo.update_mentee(&update_args)?;
+        if let Some(new_name) = update_args.new_name.as_deref() {
+            validate_name_core(new_name).map_err(MenteeError::InvalidInput)?;
+        }
+
+        if let Some(payment_day) = update_args.payment_day {
+            let day = u32::try_from(payment_day).unwrap_or_default();
+            validate_day_core(&day).map_err(MenteeError::InvalidInput)?;
+        }
+
+        // names are stored lowercase, as change_mentee passes them
+        let mut update_args = UpdateMentee {
+            name: update_args.name.to_lowercase(),
+            new_name: update_args.new_name.map(|name| name.to_lowercase()),
+            ..update_args
+        };
+
+        if let Some(referrer) = update_args.referrer.as_deref() {
+            update_args.referrer = Some(self.find_referrer(referrer, &update_args.name)?);
+        }
+
+        if update_args.edit_notes {
+            let mentee = self
+                .mentee_repo
+                .get_mentee(&update_args.name)?
+                .ok_or_else(|| MenteeError::NotFound(update_args.name.clone()))?;
+
+            let current = update_args.notes.as_deref().or(mentee.notes.as_deref());
+            update_args.notes = Some(prompt_notes("Any notes about them?", current)?);
+        }
+
+        // fetched before updating so the transition is recorded against the old status
+        let previous = match (&update_args.status, update_args.calls) {
+            (None, None) => None,
+            _ => self.mentee_repo.get_mentee(&update_args.name)?,
+        };
+
+        // more calls or coming back from archived adds to what's committed
+        let warning = match &previous {
+            Some(previous) => {
+                let status = update_args.status.as_ref().unwrap_or(&previous.status);
+                let calls = match status {
+                    Status::Archived => 0,
+                    _ => update_args
+                        .calls
+                        .map_or(previous.calls, |calls| calls.max(0) as u32),
+                };
+                self.check_capacity(Some(&previous.name), calls, update_args.strict)?
+            }
+            None => None,
+        };
+
+        let status_change = match (&previous, update_args.status.as_ref()) {
+            (Some(previous), Some(status)) if previous.status != *status => Some(StatusChange {
+                id: 0,
+                mentee_id: previous.id as i64,
+                date: update_args
+                    .status_date
+                    .map(|date| date.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(today),
+                from_status: Some(previous.status.clone()),
+                to_status: status.clone(),
+                reason: update_args.reason.clone(),
+            }),
+            _ => None,
+        };
+
+        // the history has to agree with the mentee for --as-of
+        let mut rows_affected = 0;
+        self.mentee_repo.in_transaction(&mut || {
+            rows_affected = self.mentee_repo.update_mentee(&update_args)?;
+            if let Some(change) = &status_change {
+                self.mentee_repo.record_status_change(change.clone())?;
+            }
+            Ok(())
+        })?;
+
+        if let Some(change) = status_change {
+            let data = serde_json::to_value(&change)?;
+            let name = update_args.new_name.as_deref().unwrap_or(&update_args.name);
+            self.hooks.fire(HookEvent::MenteeStatusChanged, name, data);
+        }
 
         if rows_affected == 0 {
-            Err(MenteeError::NotFound(update_args.name))
-        } else {
-            Ok(format!(
-                "{} was updated",
-                update_args.new_name.as_deref().unwrap_or(&update_args.name)
-            ))
+            return Err(MenteeError::NotFound(update_args.name));
+        }
+
+        let message = format!(
+            "{} was updated",
+            update_args.new_name.as_deref().unwrap_or(&update_args.name)
+        );
+
+        Ok(match warning {
+            Some(warning) => format!("{}\n{}", message, warning),
+            None => message,
+        })
+    }
+
+    /// Changes a mentee without prompting, returning them as they are now
+    pub fn change_mentee(&self, name: &str, changes: MenteeChanges) -> Result<Mentee, MenteeError> {
+        if changes.status.is_none() && (changes.reason.is_some() || changes.status_date.is_some()) {
+            return Err(MenteeError::InvalidInput(
+                "a reason or status date needs a status".to_string(),
+            ));
+        }
+
+        let status_date = changes
+            .status_date
+            .as_deref()
+            .map(clap_validate_date)
+            .transpose()
+            .map_err(MenteeError::InvalidInput)?;
+
+        let update = UpdateMentee {
+            name: name.to_lowercase(),
+            new_name: changes.name.map(|name| name.to_lowercase()),
+            calls: changes.calls,
+            status: changes.status,
+            reason: changes.reason,
+            status_date,
+            payment_day: changes.payment_day,
+            gross: changes.gross,
+            net: changes.net,
+            notes: changes.notes,
+            edit_notes: false,
+            source: changes.source,
+            referrer: changes.referrer,
+            strict: false,
+        };
+
+        if update.new_name.is_none()
+            && update.calls.is_none()
+            && update.gross.is_none()
+            && update.net.is_none()
+            && update.status.is_none()
+            && update.payment_day.is_none()
+            && update.notes.is_none()
+            && update.source.is_none()
+            && update.referrer.is_none()
+        {
+            return Err(MenteeError::InvalidInput(
+                "pass at least one field to change".to_string(),
+            ));
+        }
+
+        let name = update
+            .new_name
+            .clone()
+            .unwrap_or_else(|| update.name.clone());
+        self.update_mentee(update)?;
+
+        self.get_mentee(&name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::{models::mentee::Status, repositories::InMemoryRepository};
+
+    fn new_mentee(name: &str) -> NewMentee {
+        NewMentee {
+            name: name.to_string(),
+            calls: 2,
+            gross: 200,
+            net: 180,
+            status: Status::Warm,
+            payment_day: 5,
+            notes: None,
+            source: None,
+            referrer: None,
         }
     }
+
+    #[test]
+    fn records_status_changes() {
+        let config = Config::for_database(PathBuf::new());
+        let service = MenteeService::with_repositories(InMemoryRepository::new(), &config);
+
+        service.create_mentee(new_mentee("Dan Page")).unwrap();
+        let mentee = service
+            .change_mentee(
+                "dan page",
+                MenteeChanges {
+                    status: Some(Status::Hot),
+                    reason: Some("Started interviewing".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        assert_eq!(mentee.status, Status::Hot);
+        let history = service.get_status_history("dan page".to_string()).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].from_status, Some(Status::Warm));
+        assert_eq!(
+            service.get_mentee_count(None, None).unwrap(),
+            "Number of mentees: 1"
+        );
+    }
+
+    #[test]
+    fn rejects_taken_names() {
+        let config = Config::for_database(PathBuf::new());
+        let service = MenteeService::with_repositories(InMemoryRepository::new(), &config);
+
+        service.create_mentee(new_mentee("dan page")).unwrap();
+        service.create_mentee(new_mentee("amy")).unwrap();
+
+        assert!(matches!(
+            service.create_mentee(new_mentee("Dan Page")),
+            Err(MenteeError::UniqueViolation(_))
+        ));
+        assert!(matches!(
+            service.change_mentee(
+                "amy",
+                MenteeChanges {
+                    name: Some("dan page".to_string()),
+                    ..Default::default()
+                },
+            ),
+            Err(MenteeError::UniqueViolation(_))
+        ));
+    }
+
+    #[test]
+    fn takes_only_expenses_in_the_payment_currency_off_profit() {
+        use crate::{
+            models::expense::{Expense, ExpenseCategory},
+            Store,
+        };
+
+        let store = Store::open(":memory:").unwrap();
+        let service = store.mentees();
+        service.create_mentee(new_mentee("dan")).unwrap();
+
+        let month = today()[..7].to_string();
+        let expenses = ExpenseRepository::new(store.connection());
+        for (amount, currency) in [(30, "USD"), (50, "EUR")] {
+            expenses
+                .add_expense(&Expense {
+                    id: 0,
+                    date: format!("{}-01", month),
+                    category: ExpenseCategory::Course,
+                    description: "Course".to_string(),
+                    amount,
+                    currency: currency.to_string(),
+                    mentee_id: None,
+                    receipt_path: None,
+                })
+                .unwrap();
+        }
+
+        assert_eq!(
+            service
+                .get_mentee_count(Some(CountOptions::Expenses), None)
+                .unwrap(),
+            format!("USD expenses in {} $30", month)
+        );
+        assert_eq!(
+            service
+                .get_mentee_count(Some(CountOptions::Profit), None)
+                .unwrap(),
+            format!("Profit in {} $150", month)
+        );
+    }
+
+    #[test]
+    fn updates_mentees_whatever_the_case_of_their_name() {
+        let config = Config::for_database(PathBuf::new());
+        let service = MenteeService::with_repositories(InMemoryRepository::new(), &config);
+        service.create_mentee(new_mentee("dan page")).unwrap();
+
+        let message = service
+            .update_mentee(UpdateMentee {
+                name: "Dan Page".to_string(),
+                new_name: None,
+                calls: Some(4),
+                status: Some(Status::Hot),
+                reason: None,
+                status_date: None,
+                payment_day: None,
+                gross: None,
+                net: None,
+                notes: None,
+                edit_notes: false,
+                source: None,
+                referrer: None,
+                strict: false,
+            })
+            .unwrap();
+
+        assert_eq!(message, "dan page was updated");
+        assert_eq!(service.get_mentee("dan page").unwrap().calls, 4);
+        assert_eq!(
+            service
+                .get_status_history("dan page".to_string())
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn refuses_to_count_expenses_without_them() {
+        let config = Config::for_database(PathBuf::new());
+        let service = MenteeService::with_repositories(InMemoryRepository::new(), &config);
+        service.create_mentee(new_mentee("dan")).unwrap();
+
+        for count in [CountOptions::Expenses, CountOptions::Profit] {
+            assert!(matches!(
+                service.get_mentee_count(Some(count), None),
+                Err(MenteeError::InvalidInput(_))
+            ));
+        }
+        assert!(service
+            .get_mentee_count(Some(CountOptions::Net), None)
+            .is_ok());
+    }
+
+    #[test]
+    fn counts_only_mentees_as_of_a_date() {
+        let config = Config::for_database(PathBuf::new());
+        let service = MenteeService::with_repositories(InMemoryRepository::new(), &config);
+        service.create_mentee(new_mentee("dan")).unwrap();
+        let as_of = NaiveDate::parse_from_str(&today(), "%Y-%m-%d").ok();
+
+        assert_eq!(
+            service.get_mentee_count(None, as_of).unwrap(),
+            "Number of mentees: 1"
+        );
+        for count in [
+            CountOptions::Calls,
+            CountOptions::Gross,
+            CountOptions::Net,
+            CountOptions::NetPerCall,
+            CountOptions::Profit,
+        ] {
+            assert!(matches!(
+                service.get_mentee_count(Some(count), as_of),
+                Err(MenteeError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn warns_about_calls_over_capacity() {
+        let mut config = Config::for_database(PathBuf::new());
+        config.capacity.calls_per_month = Some(5);
+        let service = MenteeService::with_repositories(InMemoryRepository::new(), &config);
+
+        service.create_mentee(new_mentee("amy")).unwrap();
+        service.create_mentee(new_mentee("dan")).unwrap();
+
+        assert_eq!(service.check_capacity(Some("dan"), 3, true).unwrap(), None);
+        assert_eq!(service.check_capacity(Some("dan"), 1, true).unwrap(), None);
+        assert!(service
+            .check_capacity(None, 2, false)
+            .unwrap()
+            .unwrap()
+            .contains("6 calls a month is more than the capacity of 5"));
+        assert!(matches!(
+            service.check_capacity(Some("amy"), 4, true),
+            Err(MenteeError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn records_who_referred_a_mentee() {
+        let config = Config::for_database(PathBuf::new());
+        let service = MenteeService::with_repositories(InMemoryRepository::new(), &config);
+
+        service.create_mentee(new_mentee("amy")).unwrap();
+        let dan = service
+            .create_mentee(NewMentee {
+                referrer: Some("Amy".to_string()),
+                ..new_mentee("dan")
+            })
+            .unwrap();
+
+        assert_eq!(dan.source, Some(Source::Referral));
+        assert_eq!(dan.referrer.as_deref(), Some("amy"));
+
+        assert!(matches!(
+            service.create_mentee(NewMentee {
+                referrer: Some("nobody".to_string()),
+                ..new_mentee("eve")
+            }),
+            Err(MenteeError::NotFound(_))
+        ));
+        assert!(matches!(
+            service.change_mentee(
+                "amy",
+                MenteeChanges {
+                    referrer: Some("amy".to_string()),
+                    ..Default::default()
+                },
+            ),
+            Err(MenteeError::InvalidInput(_))
+        ));
+
+        let amy = service
+            .change_mentee(
+                "amy",
+                MenteeChanges {
+                    source: Some(Source::Website),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(amy.source, Some(Source::Website));
+        assert_eq!(amy.referrer, None);
+    }
 }
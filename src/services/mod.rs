@@ -1,9 +1,33 @@
+pub mod agenda_service;
 pub mod call_service;
+pub mod capacity_service;
+pub mod contact_service;
+pub mod expense_service;
+pub mod export_service;
+pub mod goal_service;
+pub mod import_service;
+pub mod invoice_service;
 pub mod mentee_service;
 pub mod payment_service;
+pub mod report_service;
+pub mod seed_service;
+pub mod statement_service;
+pub mod timeline_service;
 pub mod video_service;
 
+pub use agenda_service::AgendaService;
 pub use call_service::CallService;
+pub use capacity_service::CapacityService;
+pub use contact_service::ContactService;
+pub use expense_service::ExpenseService;
+pub use export_service::ExportService;
+pub use goal_service::GoalService;
+pub use import_service::ImportService;
+pub use invoice_service::InvoiceService;
 pub use mentee_service::MenteeService;
 pub use payment_service::PaymentService;
+pub use report_service::ReportService;
+pub use seed_service::SeedService;
+pub use statement_service::StatementService;
+pub use timeline_service::TimelineService;
 pub use video_service::VideoService;
@@ -1,30 +1,87 @@
 use colored::Colorize;
-use inquire::{CustomType, DateSelect};
+use inquire::{CustomType, DateSelect, Text};
 use rusqlite::Connection;
+use serde_json::json;
 
 use crate::{
+    config::{Config, PaymentsConfig},
     error::MenteeError,
-    models::payment::PaymentWithMenteeName,
-    repositories::{MenteeRepository, PaymentRepository},
-    utils::validation::parse_date_from_db,
+    hooks::{HookEvent, Hooks},
+    models::{
+        list::ListOptions,
+        mentee::Status,
+        payment::{Payment, PaymentInput, PaymentWithMenteeName},
+    },
+    repositories::{
+        MenteeRepository, PaymentRepository, SqliteMenteeRepository, SqlitePaymentRepository,
+    },
+    utils::{
+        ui::prompt_currency,
+        validation::{normalize_date, parse_date_from_db, validate_currency_core},
+    },
+    PaymentSort,
 };
 
 pub struct PaymentService<'a> {
-    payment_repo: PaymentRepository<'a>,
-    mentee_repo: MenteeRepository<'a>,
+    payment_repo: Box<dyn PaymentRepository + 'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    settings: &'a PaymentsConfig,
+    hooks: Hooks<'a>,
 }
 
 impl<'a> PaymentService<'a> {
-    pub fn new(conn: &'a Connection) -> Self {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
         Self {
-            payment_repo: PaymentRepository::new(conn),
-            mentee_repo: MenteeRepository::new(conn),
+            payment_repo: Box::new(SqlitePaymentRepository::new(conn)),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            settings: &config.payments,
+            hooks: Hooks::new(&config.hooks),
         }
     }
 
+    /// Builds the service on another storage backend
+    pub fn with_repositories(
+        payment_repo: impl PaymentRepository + 'a,
+        mentee_repo: impl MenteeRepository + 'a,
+        config: &'a Config,
+    ) -> Self {
+        Self {
+            payment_repo: Box::new(payment_repo),
+            mentee_repo: Box::new(mentee_repo),
+            settings: &config.payments,
+            hooks: Hooks::new(&config.hooks),
+        }
+    }
+
+    pub fn default_currency(&self) -> &str {
+        &self.settings.currency
+    }
+
+    fn prompt_source(current: Option<&str>) -> Result<Option<String>, MenteeError> {
+        let source = Text::new("Where did the payment come from?")
+            .with_placeholder("e.g., mentorcruise, bank transfer")
+            .with_initial_value(current.unwrap_or(""))
+            .prompt()?;
+
+        let source = source.trim().to_lowercase();
+
+        Ok((!source.is_empty()).then_some(source))
+    }
+
     pub fn get_all_payments(
         &self,
         name: Option<String>,
+    ) -> Result<Vec<PaymentWithMenteeName>, MenteeError> {
+        self.list_payments(name, None, &ListOptions::default())
+    }
+
+    /// Payments filtered, sorted and paged as the options say. `status` is the
+    /// mentee's current status.
+    pub fn list_payments(
+        &self,
+        name: Option<String>,
+        status: Option<Status>,
+        options: &ListOptions<PaymentSort>,
     ) -> Result<Vec<PaymentWithMenteeName>, MenteeError> {
         let mentee_id = if let Some(name) = name {
             match self.mentee_repo.get_mentee_id(&name)? {
@@ -41,11 +98,10 @@ impl<'a> PaymentService<'a> {
         };
 
         self.payment_repo
-            .get_all_payments(mentee_id)
-            .map_err(MenteeError::DatabaseError)
+            .get_all_payments(mentee_id, status, options)
     }
 
-    pub fn add_payment(&self, name: String) -> Result<String, MenteeError> {
+    pub(crate) fn add_payment(&self, name: String) -> Result<String, MenteeError> {
         let mentee = self
             .mentee_repo
             .get_mentee(&name)?
@@ -63,15 +119,111 @@ impl<'a> PaymentService<'a> {
             .prompt()
             .expect("Failed to read amount");
 
-        match self.payment_repo.add_payment(mentee.id, date, amount) {
-            Ok(..) => Ok(format!("Payment of {amount} added for {name}")
-                .green()
-                .to_string()),
-            Err(err) => Err(MenteeError::DatabaseError(err)),
+        let currency = prompt_currency(&self.settings.currency)?;
+
+        let fee: u32 = CustomType::new("How much was taken in fees?")
+            .with_default(mentee.gross.saturating_sub(mentee.net).min(amount))
+            .prompt()?;
+
+        if fee > amount {
+            return Err(MenteeError::InvalidInput(
+                "the fee can't be more than the payment".to_string(),
+            ));
         }
+
+        let source = Self::prompt_source(None)?;
+
+        self.create_payment(
+            &name,
+            PaymentInput {
+                date,
+                amount,
+                currency: Some(currency),
+                fee,
+                source,
+            },
+        )?;
+
+        Ok(format!("Payment of {amount} added for {name}")
+            .green()
+            .to_string())
     }
 
-    pub fn update_payment(&self, payment_id: u32) -> Result<String, MenteeError> {
+    /// Checks a payment given without prompting, the same as the prompts do
+    pub(crate) fn payment_from_input(
+        &self,
+        mentee_id: u32,
+        input: PaymentInput,
+    ) -> Result<Payment, MenteeError> {
+        let date = normalize_date(&input.date).map_err(MenteeError::InvalidInput)?;
+
+        let currency = match input.currency {
+            Some(currency) => {
+                validate_currency_core(&currency).map_err(MenteeError::InvalidInput)?;
+                currency.trim().to_uppercase()
+            }
+            None => self.settings.currency.clone(),
+        };
+
+        if input.fee > input.amount {
+            return Err(MenteeError::InvalidInput(
+                "the fee can't be more than the payment".to_string(),
+            ));
+        }
+
+        let source = input
+            .source
+            .map(|source| source.trim().to_lowercase())
+            .filter(|source| !source.is_empty());
+
+        Ok(Payment {
+            id: 0,
+            mentee_id,
+            date,
+            amount: input.amount,
+            currency: Some(currency),
+            fee: input.fee,
+            source,
+        })
+    }
+
+    /// Records a payment without prompting
+    pub fn create_payment(
+        &self,
+        name: &str,
+        input: PaymentInput,
+    ) -> Result<PaymentWithMenteeName, MenteeError> {
+        let mentee = self
+            .mentee_repo
+            .get_mentee(name)?
+            .ok_or_else(|| MenteeError::NotFound(format!("Mentee with name {}", name)))?;
+
+        let payment = self.payment_from_input(mentee.id, input)?;
+        let payment_id = self.payment_repo.add_payment(&payment)?;
+
+        self.hooks.fire(
+            HookEvent::PaymentAdded,
+            &mentee.name,
+            json!({
+                "id": payment_id,
+                "date": payment.date,
+                "amount": payment.amount,
+                "currency": payment.currency,
+                "fee": payment.fee,
+                "source": payment.source,
+            }),
+        );
+
+        self.get_payment(payment_id as u32)
+    }
+
+    pub fn get_payment(&self, payment_id: u32) -> Result<PaymentWithMenteeName, MenteeError> {
+        self.payment_repo
+            .get_payment_with_mentee_name(payment_id)
+            .map_err(|_| MenteeError::NotFound(format!("Payment with id {}", payment_id)))
+    }
+
+    pub(crate) fn update_payment(&self, payment_id: u32) -> Result<String, MenteeError> {
         let payment = self
             .payment_repo
             .get_payment_by_id(payment_id)
@@ -91,20 +243,66 @@ impl<'a> PaymentService<'a> {
             .prompt()
             .expect("Failed to read amount");
 
-        match self.payment_repo.update_payment(&date, amount, payment_id) {
-            Ok(_) => Ok(format!("Payment updated to {amount} on {date}")),
-            Err(err) => Err(MenteeError::DatabaseError(err)),
+        let currency = prompt_currency(
+            payment
+                .currency
+                .as_deref()
+                .unwrap_or(&self.settings.currency),
+        )?;
+
+        let fee: u32 = CustomType::new("How much was taken in fees?")
+            .with_starting_input(&payment.fee.to_string())
+            .prompt()?;
+
+        if fee > amount {
+            return Err(MenteeError::InvalidInput(
+                "the fee can't be more than the payment".to_string(),
+            ));
         }
+
+        let source = Self::prompt_source(payment.source.as_deref())?;
+
+        self.replace_payment(
+            payment_id,
+            PaymentInput {
+                date: date.clone(),
+                amount,
+                currency: Some(currency),
+                fee,
+                source,
+            },
+        )?;
+
+        Ok(format!("Payment updated to {amount} on {date}"))
+    }
+
+    /// Replaces every field of a payment without prompting
+    pub fn replace_payment(
+        &self,
+        payment_id: u32,
+        input: PaymentInput,
+    ) -> Result<PaymentWithMenteeName, MenteeError> {
+        let payment = self
+            .payment_repo
+            .get_payment_by_id(payment_id)
+            .map_err(|_| MenteeError::NotFound(format!("Payment with id {}", payment_id)))?;
+
+        let updated = Payment {
+            id: payment.id,
+            ..self.payment_from_input(payment.mentee_id, input)?
+        };
+        self.payment_repo.update_payment(&updated)?;
+
+        self.get_payment(payment_id)
     }
 
     pub fn delete_payment(&self, payment_id: u32) -> Result<String, MenteeError> {
-        match self.payment_repo.delete_payment(payment_id) {
-            Ok(0) => Err(MenteeError::NotFound(format!(
+        match self.payment_repo.delete_payment(payment_id)? {
+            0 => Err(MenteeError::NotFound(format!(
                 "Payment with id {}",
                 payment_id
             ))),
-            Ok(_) => Ok(format!("Payment with id {} deleted", payment_id)),
-            Err(err) => Err(MenteeError::DatabaseError(err)),
+            _ => Ok(format!("Payment with id {} deleted", payment_id)),
         }
     }
 }
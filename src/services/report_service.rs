@@ -0,0 +1,1008 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{Local, NaiveDate};
+use rusqlite::Connection;
+
+use crate::{
+    config::{CallsConfig, Config, TaxConfig},
+    error::MenteeError,
+    models::{
+        call::CallWithMenteeName,
+        expense::{ExpenseCategory, ExpenseWithMenteeName},
+        list::ListOptions,
+        mentee::Source,
+        payment::PaymentWithMenteeName,
+        report::{
+            ChurnRow, CohortRow, ExpenseRow, IncomeRow, RateRow, RatesReport, RetentionReport,
+            SourceRow, TaxReport, TurnaroundReport, TurnaroundRow,
+        },
+        video::{VideoStatus, VideoWithMenteeName},
+    },
+    repositories::{
+        CallRepository, ExpenseRepository, PaymentRepository, ReportRepository,
+        SqliteCallRepository, SqlitePaymentRepository, SqliteVideoRepository, VideoRepository,
+    },
+    utils::{
+        dates::{fiscal_year, month_index, month_index_of, month_label, today},
+        validation::parse_date_from_db,
+    },
+};
+
+fn paid_months_by_mentee(payment_months: &[(i64, String)]) -> BTreeMap<i64, BTreeSet<i32>> {
+    let mut paid_months: BTreeMap<i64, BTreeSet<i32>> = BTreeMap::new();
+    for (mentee_id, month) in payment_months {
+        if let Some(index) = month_index(month) {
+            paid_months.entry(*mentee_id).or_default().insert(index);
+        }
+    }
+
+    paid_months
+}
+
+fn archive_months(archived: &[(i64, Option<String>)]) -> HashMap<i64, Option<i32>> {
+    archived
+        .iter()
+        .map(|(mentee_id, date)| (*mentee_id, date.as_deref().and_then(month_index)))
+        .collect()
+}
+
+/// The month a mentee who paid from `first` to `last` churned in, `None` while
+/// they're still active. `archived` is their archive month when they're archived.
+fn churned_in(
+    first: i32,
+    last: i32,
+    archived: Option<&Option<i32>>,
+    current_month: i32,
+) -> Option<i32> {
+    match archived {
+        Some(Some(archived_month)) => Some((*archived_month).max(first + 1)),
+        Some(None) => Some(last + 1),
+        None if last < current_month - 1 => Some(last + 1),
+        None => None,
+    }
+}
+
+/// Builds the cohort triangle, churn and tenure from the months each mentee paid in.
+///
+/// A mentee has churned when they are archived, or when they paid in neither the
+/// current nor the previous month. They churn in the month they were archived, or
+/// the month after their last payment when the archive date wasn't recorded or
+/// they simply stopped paying.
+pub fn build_retention(
+    payment_months: &[(i64, String)],
+    archived: &[(i64, Option<String>)],
+    current_month: i32,
+) -> RetentionReport {
+    let paid_months = paid_months_by_mentee(payment_months);
+    let archived = archive_months(archived);
+
+    let mut cohorts: BTreeMap<i32, Vec<&BTreeSet<i32>>> = BTreeMap::new();
+    let mut churn_months: Vec<(i32, Option<i32>)> = Vec::new();
+    let mut tenure_total = 0;
+
+    for (mentee_id, months) in &paid_months {
+        let (Some(&first), Some(&last)) = (months.first(), months.last()) else {
+            continue;
+        };
+
+        cohorts.entry(first).or_default().push(months);
+
+        let churned_in = churned_in(first, last, archived.get(mentee_id), current_month);
+
+        tenure_total += churned_in.unwrap_or(current_month + 1) - first;
+        churn_months.push((first, churned_in));
+    }
+
+    let cohort_rows = cohorts
+        .iter()
+        .map(|(cohort, members)| CohortRow {
+            cohort: month_label(*cohort),
+            size: members.len() as u32,
+            retained: (*cohort..=current_month.max(*cohort))
+                .map(|month| {
+                    members
+                        .iter()
+                        .filter(|months| months.contains(&month))
+                        .count() as u32
+                })
+                .collect(),
+        })
+        .collect();
+
+    let churn = match cohorts.keys().next() {
+        Some(first_cohort) => (first_cohort + 1..=current_month)
+            .map(|month| ChurnRow {
+                month: month_label(month),
+                active_at_start: churn_months
+                    .iter()
+                    .filter(|(first, churned_in)| {
+                        *first < month && churned_in.is_none_or(|churned| churned >= month)
+                    })
+                    .count() as u32,
+                churned: churn_months
+                    .iter()
+                    .filter(|(_, churned_in)| *churned_in == Some(month))
+                    .count() as u32,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let churned_mentees = churn_months
+        .iter()
+        .filter(|(_, churned_in)| churned_in.is_some())
+        .count() as u32;
+
+    RetentionReport {
+        cohorts: cohort_rows,
+        churn,
+        average_tenure_months: if churn_months.is_empty() {
+            0.0
+        } else {
+            tenure_total as f64 / churn_months.len() as f64
+        },
+        active_mentees: churn_months.len() as u32 - churned_mentees,
+        churned_mentees,
+    }
+}
+
+#[derive(Default)]
+struct SourceTally {
+    mentees: u32,
+    paying_mentees: u32,
+    revenue: BTreeMap<String, i64>,
+    tenure_total: i32,
+}
+
+/// Groups mentees by where they found us, with how many went on to pay, what
+/// they paid net of fees per currency and how long they stayed. Mentees without
+/// a recorded source come last.
+pub fn build_sources(
+    mentee_sources: &[(i64, Option<Source>)],
+    net_payments: &[(i64, Option<String>, i64)],
+    payment_months: &[(i64, String)],
+    archived: &[(i64, Option<String>)],
+    current_month: i32,
+    default_currency: &str,
+) -> Vec<SourceRow> {
+    let paid_months = paid_months_by_mentee(payment_months);
+    let archived = archive_months(archived);
+
+    let mut revenue: HashMap<i64, BTreeMap<String, i64>> = HashMap::new();
+    for (mentee_id, currency, net) in net_payments {
+        let currency = currency.as_deref().unwrap_or(default_currency).to_string();
+        *revenue
+            .entry(*mentee_id)
+            .or_default()
+            .entry(currency)
+            .or_default() += net;
+    }
+
+    // `None` sorts first, flip it so unrecorded sources come last
+    let mut tallies: BTreeMap<(bool, Option<Source>), SourceTally> = BTreeMap::new();
+
+    for (mentee_id, source) in mentee_sources {
+        let tally = tallies.entry((source.is_none(), *source)).or_default();
+        tally.mentees += 1;
+
+        if let Some(paid) = revenue.get(mentee_id) {
+            for (currency, net) in paid {
+                *tally.revenue.entry(currency.clone()).or_default() += net;
+            }
+        }
+
+        let Some(months) = paid_months.get(mentee_id) else {
+            continue;
+        };
+        let (Some(&first), Some(&last)) = (months.first(), months.last()) else {
+            continue;
+        };
+
+        tally.paying_mentees += 1;
+        tally.tenure_total += churned_in(first, last, archived.get(mentee_id), current_month)
+            .unwrap_or(current_month + 1)
+            - first;
+    }
+
+    tallies
+        .into_iter()
+        .map(|((_, source), tally)| SourceRow {
+            source: source
+                .map_or("not recorded", |source| source.as_str())
+                .to_string(),
+            mentees: tally.mentees,
+            paying_mentees: tally.paying_mentees,
+            revenue: tally.revenue,
+            average_tenure_months: if tally.paying_mentees == 0 {
+                0.0
+            } else {
+                tally.tenure_total as f64 / tally.paying_mentees as f64
+            },
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct RateTally {
+    minutes: u64,
+    untimed_calls: u32,
+    earned: BTreeMap<String, i64>,
+}
+
+impl RateTally {
+    fn into_row(self, label: String) -> RateRow {
+        let hours = self.minutes as f64 / 60.0;
+
+        RateRow {
+            label,
+            hours,
+            untimed_calls: self.untimed_calls,
+            hourly_rate: if self.minutes == 0 {
+                BTreeMap::new()
+            } else {
+                self.earned
+                    .iter()
+                    .map(|(currency, net)| (currency.clone(), *net as f64 / hours))
+                    .collect()
+            },
+            earned: self.earned,
+        }
+    }
+}
+
+/// Effective hourly rate per mentee, per month and overall. Time is what past
+/// calls took, or `default_call_minutes` when a call has no duration, plus the
+/// length of delivered video reviews. Mentees are sorted by their rate in
+/// `default_currency`, or their best rate when they never paid in it, and
+/// mentees who took no time come last.
+pub fn build_rates(
+    calls: &[CallWithMenteeName],
+    videos: &[VideoWithMenteeName],
+    payments: &[PaymentWithMenteeName],
+    today: &str,
+    default_call_minutes: u32,
+    default_currency: &str,
+) -> RatesReport {
+    let mut mentees: BTreeMap<&str, RateTally> = BTreeMap::new();
+    let mut months: BTreeMap<&str, RateTally> = BTreeMap::new();
+    let mut overall = RateTally::default();
+
+    fn month_of(date: &str) -> &str {
+        date.get(..7).unwrap_or(date)
+    }
+
+    // scheduled calls haven't taken any time yet
+    for call in calls.iter().filter(|call| call.date.as_str() <= today) {
+        let minutes = call.duration.unwrap_or(default_call_minutes) as u64;
+
+        for tally in [
+            mentees.entry(call.mentee_name.as_str()).or_default(),
+            months.entry(month_of(&call.date)).or_default(),
+            &mut overall,
+        ] {
+            tally.minutes += minutes;
+            if call.duration.is_none() {
+                tally.untimed_calls += 1;
+            }
+        }
+    }
+
+    for video in videos
+        .iter()
+        .filter(|video| video.status == VideoStatus::Delivered)
+    {
+        for tally in [
+            mentees.entry(video.mentee_name.as_str()).or_default(),
+            months.entry(month_of(&video.date)).or_default(),
+            &mut overall,
+        ] {
+            tally.minutes += video.length as u64;
+        }
+    }
+
+    for payment in payments {
+        let currency = payment.currency_or(default_currency);
+
+        for tally in [
+            mentees.entry(payment.mentee_name.as_str()).or_default(),
+            months.entry(month_of(&payment.date)).or_default(),
+            &mut overall,
+        ] {
+            *tally.earned.entry(currency.to_string()).or_default() += payment.net();
+        }
+    }
+
+    let mut mentee_rows: Vec<RateRow> = mentees
+        .into_iter()
+        .map(|(name, tally)| tally.into_row(name.to_string()))
+        .collect();
+
+    let sort_rate = |row: &RateRow| {
+        row.hourly_rate
+            .get(default_currency)
+            .copied()
+            .or_else(|| row.hourly_rate.values().copied().reduce(f64::max))
+    };
+    // stable, so equal rates stay in name order
+    mentee_rows.sort_by(|a, b| match (sort_rate(a), sort_rate(b)) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+
+    RatesReport {
+        mentees: mentee_rows,
+        months: months
+            .into_iter()
+            .map(|(month, tally)| tally.into_row(month.to_string()))
+            .collect(),
+        overall: overall.into_row("All mentees".to_string()),
+    }
+}
+
+type IncomeGroups = BTreeMap<(String, String), IncomeRow>;
+
+fn income_row<'a>(
+    groups: &'a mut IncomeGroups,
+    label: String,
+    currency: &str,
+) -> &'a mut IncomeRow {
+    groups
+        .entry((label.clone(), currency.to_string()))
+        .or_insert_with(|| IncomeRow {
+            label,
+            currency: currency.to_string(),
+            payments: 0,
+            gross: 0,
+            fees: 0,
+            net: 0,
+            expenses: 0,
+            profit: 0,
+        })
+}
+
+/// Totals payments and expenses by month, payments by source and expenses by
+/// category, keeping each currency separate
+pub fn build_tax_report(
+    year: i32,
+    start: NaiveDate,
+    end: NaiveDate,
+    payments: &[PaymentWithMenteeName],
+    expenses: &[ExpenseWithMenteeName],
+    default_currency: &str,
+) -> TaxReport {
+    let mut months = IncomeGroups::new();
+    let mut sources = IncomeGroups::new();
+    let mut totals = IncomeGroups::new();
+    let mut categories: BTreeMap<(ExpenseCategory, String), ExpenseRow> = BTreeMap::new();
+
+    let month_of = |date: &str| date.get(..7).unwrap_or(date).to_string();
+
+    for payment in payments {
+        let currency = payment.currency_or(default_currency);
+        let source = payment
+            .source
+            .clone()
+            .unwrap_or_else(|| "not recorded".to_string());
+
+        for row in [
+            income_row(&mut months, month_of(&payment.date), currency),
+            income_row(&mut sources, source, currency),
+            income_row(&mut totals, "Total".to_string(), currency),
+        ] {
+            row.payments += 1;
+            row.gross += payment.amount as u64;
+            row.fees += payment.fee as u64;
+            row.net += payment.net();
+            row.profit += payment.net();
+        }
+    }
+
+    for expense in expenses {
+        for row in [
+            income_row(&mut months, month_of(&expense.date), &expense.currency),
+            income_row(&mut totals, "Total".to_string(), &expense.currency),
+        ] {
+            row.expenses += expense.amount as u64;
+            row.profit -= expense.amount as i64;
+        }
+
+        let row = categories
+            .entry((expense.category, expense.currency.clone()))
+            .or_insert_with(|| ExpenseRow {
+                category: expense.category.as_str().to_string(),
+                currency: expense.currency.clone(),
+                count: 0,
+                amount: 0,
+            });
+        row.count += 1;
+        row.amount += expense.amount as u64;
+    }
+
+    TaxReport {
+        year,
+        start: start.format("%Y-%m-%d").to_string(),
+        end: end.format("%Y-%m-%d").to_string(),
+        months: months.into_values().collect(),
+        sources: sources.into_values().collect(),
+        expense_categories: categories.into_values().collect(),
+        totals: totals.into_values().collect(),
+    }
+}
+
+#[derive(Default)]
+struct TurnaroundTally {
+    delivered: u32,
+    total_days: i64,
+    timed: u32,
+    longest_days: i64,
+    with_due_date: u32,
+    on_time: u32,
+    outstanding: u32,
+}
+
+impl TurnaroundTally {
+    fn add(&mut self, video: &VideoWithMenteeName) {
+        if video.status != VideoStatus::Delivered {
+            self.outstanding += 1;
+            return;
+        }
+
+        self.delivered += 1;
+
+        let delivered = parse_date_from_db(&video.date).ok();
+        let received = video
+            .received_date
+            .as_deref()
+            .and_then(|date| parse_date_from_db(date).ok());
+        if let (Some(received), Some(delivered)) = (received, delivered) {
+            let days = (delivered - received).num_days();
+            self.timed += 1;
+            self.total_days += days;
+            self.longest_days = self.longest_days.max(days);
+        }
+
+        if let Some(due_date) = video.due_date.as_deref() {
+            self.with_due_date += 1;
+            // dates are stored as YYYY-MM-DD so they compare as strings
+            if video.date.as_str() <= due_date {
+                self.on_time += 1;
+            }
+        }
+    }
+
+    fn into_row(self, mentee_name: String) -> TurnaroundRow {
+        TurnaroundRow {
+            mentee_name,
+            delivered: self.delivered,
+            average_days: if self.timed == 0 {
+                0.0
+            } else {
+                self.total_days as f64 / self.timed as f64
+            },
+            longest_days: self.longest_days,
+            with_due_date: self.with_due_date,
+            on_time: self.on_time,
+            outstanding: self.outstanding,
+        }
+    }
+}
+
+/// Days from receiving each video to delivering its review, per mentee and overall
+pub fn build_turnaround(videos: &[VideoWithMenteeName]) -> TurnaroundReport {
+    let mut mentees: BTreeMap<&str, TurnaroundTally> = BTreeMap::new();
+    let mut overall = TurnaroundTally::default();
+
+    for video in videos {
+        mentees
+            .entry(video.mentee_name.as_str())
+            .or_default()
+            .add(video);
+        overall.add(video);
+    }
+
+    TurnaroundReport {
+        mentees: mentees
+            .into_iter()
+            .map(|(name, tally)| tally.into_row(name.to_string()))
+            .collect(),
+        overall: overall.into_row("All mentees".to_string()),
+    }
+}
+
+pub struct ReportService<'a> {
+    report_repo: ReportRepository<'a>,
+    call_repo: Box<dyn CallRepository + 'a>,
+    expense_repo: ExpenseRepository<'a>,
+    payment_repo: Box<dyn PaymentRepository + 'a>,
+    video_repo: Box<dyn VideoRepository + 'a>,
+    default_currency: &'a str,
+    tax: &'a TaxConfig,
+    calls: &'a CallsConfig,
+}
+
+impl<'a> ReportService<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self {
+            report_repo: ReportRepository::new(conn),
+            call_repo: Box::new(SqliteCallRepository::new(conn)),
+            expense_repo: ExpenseRepository::new(conn),
+            payment_repo: Box::new(SqlitePaymentRepository::new(conn)),
+            video_repo: Box::new(SqliteVideoRepository::new(conn)),
+            default_currency: &config.payments.currency,
+            tax: &config.tax,
+            calls: &config.calls,
+        }
+    }
+
+    /// Income for the fiscal year that starts in `year`
+    pub fn get_tax_report(&self, year: i32) -> Result<TaxReport, MenteeError> {
+        let (month, day) = self.tax.year_start()?;
+        let (start, end) = fiscal_year(year, month, day).ok_or_else(|| {
+            MenteeError::InvalidInput(format!("{} is not a valid fiscal year", year))
+        })?;
+
+        let from = start.format("%Y-%m-%d").to_string();
+        let to = end.format("%Y-%m-%d").to_string();
+
+        let payments = self.payment_repo.get_payments_between(&from, &to)?;
+        let expenses = self.expense_repo.get_expenses_between(&from, &to)?;
+
+        Ok(build_tax_report(
+            year,
+            start,
+            end,
+            &payments,
+            &expenses,
+            self.default_currency,
+        ))
+    }
+
+    /// Hourly rates across everyone, or for the one mentee with `mentee_id`
+    pub fn get_rates(&self, mentee_id: Option<i64>) -> Result<RatesReport, MenteeError> {
+        let calls = self
+            .call_repo
+            .get_all_calls(mentee_id, None, &ListOptions::default())?;
+        let videos = self
+            .video_repo
+            .get_all_videos(mentee_id, None, &ListOptions::default())?;
+        let payments =
+            self.payment_repo
+                .get_all_payments(mentee_id, None, &ListOptions::default())?;
+
+        Ok(build_rates(
+            &calls,
+            &videos,
+            &payments,
+            &today(),
+            self.calls.default_minutes,
+            self.default_currency,
+        ))
+    }
+
+    pub fn get_turnaround(&self) -> Result<TurnaroundReport, MenteeError> {
+        let videos = self
+            .video_repo
+            .get_all_videos(None, None, &ListOptions::default())?;
+
+        Ok(build_turnaround(&videos))
+    }
+
+    pub fn get_sources(&self) -> Result<Vec<SourceRow>, MenteeError> {
+        let mentee_sources: Vec<(i64, Option<Source>)> = self
+            .report_repo
+            .get_mentee_sources()?
+            .into_iter()
+            .map(|(mentee_id, source)| (mentee_id, source.as_deref().and_then(Source::from_str)))
+            .collect();
+        let net_payments = self.report_repo.get_net_payments()?;
+        let payment_months = self.report_repo.get_payment_months()?;
+        let archived = self.report_repo.get_archive_dates()?;
+
+        Ok(build_sources(
+            &mentee_sources,
+            &net_payments,
+            &payment_months,
+            &archived,
+            month_index_of(Local::now().date_naive()),
+            self.default_currency,
+        ))
+    }
+
+    pub fn get_retention(&self) -> Result<RetentionReport, MenteeError> {
+        let payment_months = self.report_repo.get_payment_months()?;
+        let archived = self.report_repo.get_archive_dates()?;
+
+        Ok(build_retention(
+            &payment_months,
+            &archived,
+            month_index_of(Local::now().date_naive()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn payments(rows: &[(i64, &str)]) -> Vec<(i64, String)> {
+        rows.iter()
+            .map(|(id, month)| (*id, month.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn builds_cohort_triangle() {
+        let payment_months = payments(&[
+            (1, "2026-01"),
+            (1, "2026-02"),
+            (1, "2026-03"),
+            (2, "2026-01"),
+            (3, "2026-02"),
+            (3, "2026-03"),
+        ]);
+
+        let report = build_retention(&payment_months, &[], month_index("2026-03").unwrap());
+
+        assert_eq!(
+            report.cohorts,
+            vec![
+                CohortRow {
+                    cohort: "2026-01".to_string(),
+                    size: 2,
+                    retained: vec![2, 1, 1],
+                },
+                CohortRow {
+                    cohort: "2026-02".to_string(),
+                    size: 1,
+                    retained: vec![1, 1],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn churn_uses_archive_dates_and_lapsed_payments() {
+        let payment_months = payments(&[
+            (1, "2026-01"),
+            (1, "2026-02"),
+            (2, "2026-01"),
+            (3, "2026-01"),
+            (3, "2026-02"),
+            (3, "2026-03"),
+            (3, "2026-04"),
+        ]);
+        // mentee 1 archived in March, mentee 2 stopped paying after January
+        let archived = vec![(1, Some("2026-03-10".to_string()))];
+
+        let report = build_retention(&payment_months, &archived, month_index("2026-04").unwrap());
+
+        let churn: Vec<(&str, u32, u32)> = report
+            .churn
+            .iter()
+            .map(|row| (row.month.as_str(), row.active_at_start, row.churned))
+            .collect();
+
+        assert_eq!(
+            churn,
+            vec![("2026-02", 3, 1), ("2026-03", 2, 1), ("2026-04", 1, 0)]
+        );
+        assert_eq!(report.churned_mentees, 2);
+        assert_eq!(report.active_mentees, 1);
+        // tenures of 2, 1 and 4 months
+        assert!((report.average_tenure_months - 7.0 / 3.0).abs() < f64::EPSILON);
+        assert!((report.churn[0].churn_rate() - 100.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sources_compare_conversion_revenue_and_tenure() {
+        let mentee_sources = vec![
+            (1, Some(Source::Referral)),
+            (2, Some(Source::MentorCruise)),
+            (3, Some(Source::Referral)),
+            (4, None),
+            (5, Some(Source::MentorCruise)),
+        ];
+        let net_payments = vec![
+            (1, Some("USD".to_string()), 300),
+            (1, Some("GBP".to_string()), 100),
+            (2, None, 180),
+            (3, Some("USD".to_string()), 200),
+            (5, Some("USD".to_string()), 90),
+        ];
+        let payment_months = payments(&[
+            (1, "2026-01"),
+            (1, "2026-02"),
+            (2, "2026-03"),
+            (3, "2026-02"),
+            (3, "2026-03"),
+            (3, "2026-04"),
+            (5, "2026-01"),
+        ]);
+        // mentee 5 was archived in February
+        let archived = vec![(5, Some("2026-02-10".to_string()))];
+
+        let sources = build_sources(
+            &mentee_sources,
+            &net_payments,
+            &payment_months,
+            &archived,
+            month_index("2026-04").unwrap(),
+            "USD",
+        );
+
+        let labels: Vec<&str> = sources.iter().map(|row| row.source.as_str()).collect();
+        assert_eq!(labels, vec!["mentorcruise", "referral", "not recorded"]);
+
+        let mentorcruise = &sources[0];
+        assert_eq!(mentorcruise.mentees, 2);
+        assert_eq!(mentorcruise.revenue.get("USD"), Some(&270));
+        // still paying for 2 months, archived after 1
+        assert_eq!(mentorcruise.average_tenure_months, 1.5);
+
+        let referral = &sources[1];
+        assert_eq!(referral.revenue.get("USD"), Some(&500));
+        assert_eq!(referral.revenue.get("GBP"), Some(&100));
+        // stopped paying after 2 months, still paying for 3
+        assert_eq!(referral.average_tenure_months, 2.5);
+
+        let unrecorded = &sources[2];
+        assert_eq!(unrecorded.paying_mentees, 0);
+        assert_eq!(unrecorded.conversion_rate(), 0.0);
+        assert!(unrecorded.revenue.is_empty());
+    }
+
+    fn payment(
+        date: &str,
+        amount: u32,
+        fee: u32,
+        currency: Option<&str>,
+        source: Option<&str>,
+    ) -> PaymentWithMenteeName {
+        PaymentWithMenteeName {
+            amount,
+            currency: currency.map(str::to_string),
+            fee,
+            source: source.map(str::to_string),
+            ..fixtures::payment(0, date)
+        }
+    }
+
+    fn expense(date: &str, category: ExpenseCategory, amount: u32) -> ExpenseWithMenteeName {
+        ExpenseWithMenteeName {
+            id: 0,
+            date: date.to_string(),
+            category,
+            description: String::new(),
+            amount,
+            currency: "USD".to_string(),
+            mentee_name: None,
+            receipt_path: None,
+        }
+    }
+
+    #[test]
+    fn tax_report_groups_by_month_source_and_currency() {
+        let start = NaiveDate::from_ymd_opt(2026, 4, 6).unwrap();
+        let end = NaiveDate::from_ymd_opt(2027, 4, 5).unwrap();
+        let payments = vec![
+            payment("2026-04-10", 200, 20, None, Some("mentorcruise")),
+            payment("2026-04-20", 300, 30, Some("USD"), Some("mentorcruise")),
+            payment("2026-04-21", 100, 0, Some("GBP"), None),
+            payment("2026-05-01", 200, 20, Some("USD"), Some("mentorcruise")),
+        ];
+
+        let expenses = vec![
+            expense("2026-04-15", ExpenseCategory::Software, 50),
+            expense("2026-06-01", ExpenseCategory::Course, 100),
+            expense("2026-06-02", ExpenseCategory::Software, 10),
+        ];
+
+        let report = build_tax_report(2026, start, end, &payments, &expenses, "USD");
+
+        let months: Vec<_> = report
+            .months
+            .iter()
+            .map(|row| {
+                (
+                    row.label.as_str(),
+                    row.currency.as_str(),
+                    row.payments,
+                    row.gross,
+                    row.fees,
+                    row.net,
+                    row.expenses,
+                    row.profit,
+                )
+            })
+            .collect();
+        assert_eq!(
+            months,
+            vec![
+                ("2026-04", "GBP", 1, 100, 0, 100, 0, 100),
+                ("2026-04", "USD", 2, 500, 50, 450, 50, 400),
+                ("2026-05", "USD", 1, 200, 20, 180, 0, 180),
+                ("2026-06", "USD", 0, 0, 0, 0, 110, -110),
+            ]
+        );
+
+        let categories: Vec<(&str, u32, u64)> = report
+            .expense_categories
+            .iter()
+            .map(|row| (row.category.as_str(), row.count, row.amount))
+            .collect();
+        assert_eq!(categories, vec![("software", 2, 60), ("course", 1, 100)]);
+
+        let sources: Vec<(&str, &str, i64)> = report
+            .sources
+            .iter()
+            .map(|row| (row.label.as_str(), row.currency.as_str(), row.net))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![("mentorcruise", "USD", 630), ("not recorded", "GBP", 100)]
+        );
+
+        let totals: Vec<(&str, i64, i64)> = report
+            .totals
+            .iter()
+            .map(|row| (row.currency.as_str(), row.net, row.profit))
+            .collect();
+        assert_eq!(totals, vec![("GBP", 100, 100), ("USD", 630, 470)]);
+        assert_eq!(report.start, "2026-04-06");
+        assert_eq!(report.end, "2027-04-05");
+    }
+
+    fn video(
+        name: &str,
+        status: VideoStatus,
+        date: &str,
+        received: Option<&str>,
+        due: Option<&str>,
+    ) -> VideoWithMenteeName {
+        VideoWithMenteeName {
+            id: 0,
+            mentee_name: name.to_string(),
+            date: date.to_string(),
+            length: 0,
+            notes: String::new(),
+            status,
+            received_date: received.map(str::to_string),
+            due_date: due.map(str::to_string),
+            source_url: None,
+            file_path: None,
+        }
+    }
+
+    fn call(name: &str, date: &str, duration: Option<u32>) -> CallWithMenteeName {
+        CallWithMenteeName {
+            mentee_name: name.to_string(),
+            duration,
+            ..fixtures::call(0, date, false)
+        }
+    }
+
+    #[test]
+    fn rates_per_mentee_month_and_overall() {
+        let calls = vec![
+            call("amy", "2026-03-02", Some(60)),
+            call("amy", "2026-04-02", Some(30)),
+            // counted at the default of 60 minutes
+            call("dan", "2026-03-09", None),
+            call("dan", "2026-04-09", Some(90)),
+            // scheduled, so no time spent yet
+            call("dan", "2026-05-09", Some(60)),
+            call("eve", "2026-04-01", Some(30)),
+        ];
+        let mut videos = vec![
+            video("amy", VideoStatus::Delivered, "2026-03-20", None, None),
+            video("dan", VideoStatus::InProgress, "2026-04-20", None, None),
+        ];
+        videos[0].length = 30;
+        videos[1].length = 45;
+
+        let mut amy = payment("2026-03-01", 200, 20, None, None);
+        amy.mentee_name = "amy".to_string();
+        let mut dan = payment("2026-03-05", 300, 0, Some("USD"), None);
+        dan.mentee_name = "dan".to_string();
+        let mut dan_gbp = payment("2026-04-05", 100, 0, Some("GBP"), None);
+        dan_gbp.mentee_name = "dan".to_string();
+
+        let report = build_rates(
+            &calls,
+            &videos,
+            &[amy, dan, dan_gbp],
+            "2026-04-30",
+            60,
+            "USD",
+        );
+
+        let mentees: Vec<(&str, f64, Option<f64>)> = report
+            .mentees
+            .iter()
+            .map(|row| {
+                (
+                    row.label.as_str(),
+                    row.hours,
+                    row.hourly_rate.get("USD").copied(),
+                )
+            })
+            .collect();
+        // amy earned 180 in 2 hours, dan 300 in 2.5 and eve nothing
+        assert_eq!(
+            mentees,
+            vec![
+                ("dan", 2.5, Some(120.0)),
+                ("amy", 2.0, Some(90.0)),
+                ("eve", 0.5, None),
+            ]
+        );
+        assert_eq!(report.mentees[0].hourly_rate.get("GBP"), Some(&40.0));
+        assert_eq!(report.mentees[0].untimed_calls, 1);
+
+        let months: Vec<(&str, f64)> = report
+            .months
+            .iter()
+            .map(|row| (row.label.as_str(), row.hours))
+            .collect();
+        assert_eq!(months, vec![("2026-03", 2.5), ("2026-04", 2.5)]);
+
+        assert_eq!(report.overall.hours, 5.0);
+        assert_eq!(report.overall.earned.get("USD"), Some(&480));
+        assert_eq!(report.overall.hourly_rate.get("USD"), Some(&96.0));
+    }
+
+    #[test]
+    fn turnaround_per_mentee_and_overall() {
+        use VideoStatus::*;
+
+        let videos = vec![
+            video(
+                "amy",
+                Delivered,
+                "2026-03-05",
+                Some("2026-03-01"),
+                Some("2026-03-08"),
+            ),
+            video(
+                "amy",
+                Delivered,
+                "2026-03-20",
+                Some("2026-03-10"),
+                Some("2026-03-17"),
+            ),
+            video("amy", Requested, "2026-04-01", Some("2026-04-01"), None),
+            // logged before the workflow, no received date
+            video("dan", Delivered, "2026-02-01", None, None),
+            video(
+                "dan",
+                InProgress,
+                "2026-04-02",
+                Some("2026-04-02"),
+                Some("2026-04-09"),
+            ),
+        ];
+
+        let report = build_turnaround(&videos);
+
+        let amy = &report.mentees[0];
+        assert_eq!(amy.mentee_name, "amy");
+        assert_eq!(amy.delivered, 2);
+        assert_eq!(amy.average_days, 7.0);
+        assert_eq!(amy.longest_days, 10);
+        assert_eq!(amy.on_time_rate(), Some(50.0));
+        assert_eq!(amy.outstanding, 1);
+
+        let dan = &report.mentees[1];
+        assert_eq!(dan.delivered, 1);
+        assert_eq!(dan.average_days, 0.0);
+        assert_eq!(dan.on_time_rate(), None);
+        assert_eq!(dan.outstanding, 1);
+
+        assert_eq!(report.overall.delivered, 3);
+        assert_eq!(report.overall.average_days, 7.0);
+        assert_eq!(report.overall.outstanding, 2);
+    }
+}
@@ -0,0 +1,570 @@
+use chrono::{Days, NaiveDate};
+use rusqlite::Connection;
+
+use crate::{
+    config::Config,
+    constants,
+    error::MenteeError,
+    models::{
+        call::Call,
+        mentee::{Mentee, Source, Status, StatusChange},
+        payment::Payment,
+        video::{Video, VideoStatus},
+    },
+    repositories::{
+        CallRepository, MenteeRepository, PaymentRepository, SqliteCallRepository,
+        SqliteMenteeRepository, SqlitePaymentRepository, SqliteVideoRepository, VideoRepository,
+    },
+    utils::dates::{month_index_of, month_label},
+};
+
+/// Every table with records in it, children before the tables they belong to
+const TABLES: [&str; 11] = [
+    constants::CALL_GOALS_TABLE,
+    constants::MILESTONES_TABLE,
+    constants::GOALS_TABLE,
+    constants::CONTACTS_TABLE,
+    constants::STATUS_CHANGES_TABLE,
+    constants::INVOICES_TABLE,
+    constants::EXPENSES_TABLE,
+    constants::VIDEOS_TABLE,
+    constants::PAYMENTS_TABLE,
+    constants::CALLS_TABLE,
+    constants::MENTEES_TABLE,
+];
+
+const FIRST_NAMES: [&str; 25] = [
+    "amy", "ben", "chloe", "dan", "ella", "finn", "grace", "harry", "isla", "jack", "kate", "leo",
+    "maya", "noah", "olivia", "priya", "quinn", "ravi", "sofia", "tom", "uma", "victor", "will",
+    "yara", "zoe",
+];
+
+const LAST_NAMES: [&str; 20] = [
+    "adams", "brown", "clarke", "davies", "evans", "foster", "green", "hughes", "khan", "lewis",
+    "morgan", "nguyen", "owen", "patel", "reid", "shah", "turner", "walsh", "young", "zhang",
+];
+
+/// Most mentees that can be generated before the names run out
+pub const MAX_SEED_MENTEES: u32 = (FIRST_NAMES.len() * LAST_NAMES.len()) as u32;
+
+const MENTEE_NOTES: [&str; 5] = [
+    "Preparing for senior interviews",
+    "Switching from backend to frontend",
+    "Wants to move into management",
+    "Referred by a former mentee",
+    "Working on system design",
+];
+
+const CALL_NOTES: [&str; 6] = [
+    "Mock interview",
+    "Reviewed their CV",
+    "System design practice",
+    "Talked through a job offer",
+    "Code review of their side project",
+    "Planned the next month",
+];
+
+/// Minutes a call can take
+const CALL_DURATIONS: [u32; 5] = [30, 45, 60, 60, 90];
+
+const VIDEO_NOTES: [&str; 4] = [
+    "Recorded mock interview",
+    "Conference talk rehearsal",
+    "Live coding session",
+    "Pairing session",
+];
+
+const PAYMENT_SOURCES: [&str; 3] = ["bank transfer", "paypal", "stripe"];
+
+/// SplitMix64. Small and with no dependencies, so a seed gives the same data
+/// on every platform and release.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number between `low` and `high` inclusive
+    fn range(&mut self, low: u32, high: u32) -> u32 {
+        low + (self.next() % (high - low + 1) as u64) as u32
+    }
+
+    fn chance(&mut self, percent: u32) -> bool {
+        self.range(1, 100) <= percent
+    }
+
+    fn pick<'t, T>(&mut self, items: &'t [T]) -> &'t T {
+        &items[self.range(0, items.len() as u32 - 1) as usize]
+    }
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// The given day of a month index, clamped to the end of shorter months
+fn day_of_month(month: i32, day: u32) -> NaiveDate {
+    let first = NaiveDate::parse_from_str(&format!("{}-01", month_label(month)), "%Y-%m-%d")
+        .expect("month labels are valid dates");
+    let last = NaiveDate::parse_from_str(&format!("{}-01", month_label(month + 1)), "%Y-%m-%d")
+        .expect("month labels are valid dates")
+        .pred_opt()
+        .expect("a month has a previous day");
+
+    first
+        .checked_add_days(Days::new(day.saturating_sub(1) as u64))
+        .filter(|date| *date <= last)
+        .unwrap_or(last)
+}
+
+fn add_days(date: NaiveDate, days: u32) -> NaiveDate {
+    date.checked_add_days(Days::new(days as u64))
+        .expect("seed dates stay within range")
+}
+
+/// One generated mentee and their records, ids are filled in when saved
+struct SeedMentee {
+    mentee: Mentee,
+    history: Vec<StatusChange>,
+    calls: Vec<Call>,
+    payments: Vec<Payment>,
+    videos: Vec<Video>,
+}
+
+fn status_change(date: NaiveDate, from_status: Option<Status>, to_status: Status) -> StatusChange {
+    StatusChange {
+        id: 0,
+        mentee_id: 0,
+        date: format_date(date),
+        from_status,
+        to_status,
+        reason: None,
+    }
+}
+
+/// Generates `count` mentees whose calls, payments and videos run over the
+/// `months` up to `until`. The same arguments always give the same data.
+fn generate(
+    count: u32,
+    months: u32,
+    seed: u64,
+    until: NaiveDate,
+    currency: &str,
+) -> Vec<SeedMentee> {
+    let mut random = Random(seed);
+    let last_month = month_index_of(until);
+    let first_month = last_month - months as i32 + 1;
+
+    let mut names: Vec<String> = Vec::new();
+    let mut mentees = Vec::new();
+
+    for index in 0..count.min(MAX_SEED_MENTEES) {
+        let name = loop {
+            let name = format!("{} {}", random.pick(&FIRST_NAMES), random.pick(&LAST_NAMES));
+            if !names.contains(&name) {
+                break name;
+            }
+        };
+        names.push(name.clone());
+
+        // the first few cover every status, the rest are mostly active
+        let status = match index {
+            0 => Status::Hot,
+            1 => Status::Warm,
+            2 => Status::Cold,
+            3 => Status::Archived,
+            _ => match random.range(1, 100) {
+                1..=35 => Status::Hot,
+                36..=65 => Status::Warm,
+                66..=80 => Status::Cold,
+                _ => Status::Archived,
+            },
+        };
+
+        // mentees who've gone quiet joined before this month
+        let latest_start = match status {
+            Status::Cold | Status::Archived => (last_month - 1).max(first_month),
+            _ => last_month,
+        };
+        let start_month = random.range(first_month as u32, latest_start as u32) as i32;
+        let end_month = match status {
+            Status::Hot | Status::Warm => last_month,
+            Status::Cold => (last_month - random.range(1, 3) as i32).max(start_month),
+            Status::Archived => random.range(
+                start_month as u32,
+                last_month.max(start_month + 1) as u32 - 1,
+            ) as i32,
+        };
+
+        let calls_per_month = random.range(1, 4);
+        let gross = *random.pick(&[150, 200, 250, 300, 400, 500]);
+        let fee = gross * random.pick(&[0, 3, 5, 10]) / 100;
+        let payment_day = random.range(1, 28);
+        let source = random.pick(&PAYMENT_SOURCES).to_string();
+        let found_us_through = match random.range(1, 100) {
+            1..=45 => Source::MentorCruise,
+            46..=70 => Source::Referral,
+            71..=90 => Source::Website,
+            _ => Source::Other,
+        };
+        // referred by someone seeded before them, who's already been added
+        let earlier_names = &names[..names.len() - 1];
+        let referrer = (found_us_through == Source::Referral && !earlier_names.is_empty())
+            .then(|| random.pick(earlier_names).clone());
+
+        let mut seeded = SeedMentee {
+            mentee: Mentee {
+                id: 0,
+                name,
+                calls: calls_per_month,
+                gross,
+                net: gross - fee,
+                status: status.clone(),
+                payment_day,
+                notes: random
+                    .chance(50)
+                    .then(|| random.pick(&MENTEE_NOTES).to_string()),
+                source: Some(found_us_through),
+                referrer,
+            },
+            history: Vec::new(),
+            calls: Vec::new(),
+            payments: Vec::new(),
+            videos: Vec::new(),
+        };
+
+        let started = day_of_month(start_month, random.range(1, payment_day)).min(until);
+        let first_status = match status {
+            Status::Warm if random.chance(50) => Status::Warm,
+            _ => Status::Hot,
+        };
+        seeded
+            .history
+            .push(status_change(started, None, first_status.clone()));
+
+        for month in start_month..=end_month {
+            let paid_on = day_of_month(month, payment_day);
+            if paid_on >= started && paid_on <= until {
+                seeded.payments.push(Payment {
+                    id: 0,
+                    mentee_id: 0,
+                    date: format_date(paid_on),
+                    amount: gross,
+                    currency: Some(currency.to_string()),
+                    fee,
+                    source: Some(source.clone()),
+                });
+            }
+
+            // some calls get skipped, so not every payment is used up
+            let mut days: Vec<u32> = (0..calls_per_month)
+                .filter_map(|_| {
+                    if random.chance(85) {
+                        Some(random.range(1, 28))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            days.sort_unstable();
+
+            for day in days {
+                let date = day_of_month(month, day);
+                if date >= started && date <= until {
+                    seeded.calls.push(Call {
+                        id: 0,
+                        mentee_id: 0,
+                        date: format_date(date),
+                        notes: Some(random.pick(&CALL_NOTES).to_string()),
+                        free_call: random.chance(10),
+                        duration: Some(*random.pick(&CALL_DURATIONS)),
+                    });
+                }
+            }
+
+            if random.chance(35) {
+                let received = day_of_month(month, random.range(1, 28)).max(started);
+                let delivered = add_days(received, random.range(1, 10));
+
+                if received <= until {
+                    let (date, status) = if delivered <= until {
+                        (delivered, VideoStatus::Delivered)
+                    } else if random.chance(50) {
+                        (received, VideoStatus::InProgress)
+                    } else {
+                        (received, VideoStatus::Requested)
+                    };
+
+                    seeded.videos.push(Video {
+                        id: 0,
+                        mentee_id: 0,
+                        date: format_date(date),
+                        length: random.range(5, 45),
+                        notes: random.pick(&VIDEO_NOTES).to_string(),
+                        status,
+                        received_date: Some(format_date(received)),
+                        due_date: Some(format_date(add_days(received, 7))),
+                        source_url: None,
+                        file_path: None,
+                        file_size: None,
+                        file_sha256: None,
+                    });
+                }
+            }
+        }
+
+        // active mentees have their next call booked
+        if status == Status::Hot {
+            seeded.calls.push(Call {
+                id: 0,
+                mentee_id: 0,
+                date: format_date(add_days(until, random.range(1, 7))),
+                notes: Some(random.pick(&CALL_NOTES).to_string()),
+                free_call: false,
+                duration: None,
+            });
+        }
+
+        // calls and payments fall on or before the 28th, so the change comes after them
+        if status != first_status {
+            let changed_on = add_days(day_of_month(end_month, 28), random.range(1, 14))
+                .min(until)
+                .max(started);
+            seeded
+                .history
+                .push(status_change(changed_on, Some(first_status), status));
+        }
+
+        mentees.push(seeded);
+    }
+
+    mentees
+}
+
+pub struct SeedService<'a> {
+    conn: &'a Connection,
+    currency: &'a str,
+    call_repo: Box<dyn CallRepository + 'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    payment_repo: Box<dyn PaymentRepository + 'a>,
+    video_repo: Box<dyn VideoRepository + 'a>,
+}
+
+impl<'a> SeedService<'a> {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self {
+            conn,
+            currency: &config.payments.currency,
+            call_repo: Box::new(SqliteCallRepository::new(conn)),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            payment_repo: Box::new(SqlitePaymentRepository::new(conn)),
+            video_repo: Box::new(SqliteVideoRepository::new(conn)),
+        }
+    }
+
+    /// Tables that have records, with how many
+    fn existing_records(&self) -> Result<Vec<(&'static str, i64)>, MenteeError> {
+        let mut existing = Vec::new();
+        for table in TABLES {
+            let count: i64 =
+                self.conn
+                    .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                        row.get(0)
+                    })?;
+            if count > 0 {
+                existing.push((table, count));
+            }
+        }
+
+        Ok(existing)
+    }
+
+    /// Fills the database with generated mentees. Refuses when it already has
+    /// records of any kind unless `force` is set, in which case everything is
+    /// deleted first. Hooks don't run for seeded records.
+    pub(crate) fn seed(
+        &self,
+        count: u32,
+        months: u32,
+        seed: u64,
+        until: NaiveDate,
+        force: bool,
+    ) -> Result<String, MenteeError> {
+        let existing = self.existing_records()?;
+        if !existing.is_empty() && !force {
+            // mentees first, then what belongs to them
+            let records: Vec<String> = existing
+                .iter()
+                .rev()
+                .map(|(table, count)| format!("{} {}", count, table.replace('_', " ")))
+                .collect();
+            return Err(MenteeError::InvalidInput(format!(
+                "the database already has {}, pass --force to replace them",
+                records.join(", ")
+            )));
+        }
+
+        let mentees = generate(count, months, seed, until, self.currency);
+
+        let transaction = self.conn.unchecked_transaction()?;
+
+        for table in TABLES {
+            self.conn.execute(&format!("DELETE FROM {}", table), [])?;
+        }
+
+        let (mut calls, mut payments, mut videos) = (0, 0, 0);
+
+        for seeded in mentees.iter() {
+            self.mentee_repo.add_mentee(seeded.mentee.clone())?;
+            let mentee_id = self
+                .mentee_repo
+                .get_mentee_id(&seeded.mentee.name)?
+                .ok_or_else(|| MenteeError::NotFound(seeded.mentee.name.clone()))?;
+
+            for change in &seeded.history {
+                self.mentee_repo.record_status_change(StatusChange {
+                    mentee_id,
+                    ..change.clone()
+                })?;
+            }
+
+            for call in &seeded.calls {
+                self.call_repo.add_call(Call {
+                    mentee_id,
+                    ..call.clone()
+                })?;
+            }
+
+            for payment in &seeded.payments {
+                self.payment_repo.add_payment(&Payment {
+                    mentee_id: mentee_id as u32,
+                    ..payment.clone()
+                })?;
+            }
+
+            for video in &seeded.videos {
+                self.video_repo.add_video(Video {
+                    mentee_id,
+                    ..video.clone()
+                })?;
+            }
+
+            calls += seeded.calls.len();
+            payments += seeded.payments.len();
+            videos += seeded.videos.len();
+        }
+
+        transaction.commit()?;
+
+        Ok(format!(
+            "Seeded {} mentees with {} calls, {} payments and {} videos",
+            mentees.len(),
+            calls,
+            payments,
+            videos
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn until() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 15).unwrap()
+    }
+
+    #[test]
+    fn same_seed_gives_same_data() {
+        let summary = |mentees: Vec<SeedMentee>| -> Vec<(String, usize, usize, usize)> {
+            mentees
+                .into_iter()
+                .map(|m| {
+                    (
+                        m.mentee.name,
+                        m.calls.len(),
+                        m.payments.len(),
+                        m.videos.len(),
+                    )
+                })
+                .collect()
+        };
+
+        let first = summary(generate(30, 12, 42, until(), "USD"));
+
+        assert_eq!(first, summary(generate(30, 12, 42, until(), "USD")));
+        assert_ne!(first, summary(generate(30, 12, 7, until(), "USD")));
+    }
+
+    #[test]
+    fn generates_plausible_records() {
+        let mentees = generate(30, 12, 42, until(), "USD");
+        let until = format_date(until());
+
+        assert_eq!(mentees.len(), 30);
+        for status in [Status::Hot, Status::Warm, Status::Cold, Status::Archived] {
+            assert!(mentees.iter().any(|m| m.mentee.status == status));
+        }
+
+        let mut names: Vec<&str> = mentees.iter().map(|m| m.mentee.name.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), 30);
+
+        for seeded in &mentees {
+            let last_change = seeded.history.last().unwrap();
+            assert_eq!(last_change.to_status, seeded.mentee.status);
+            assert!(seeded.history[0].from_status.is_none());
+
+            // only hot mentees have a call booked after the end date
+            let booked = seeded.calls.iter().filter(|call| call.date > until).count();
+            let expected = usize::from(seeded.mentee.status == Status::Hot);
+            assert_eq!(booked, expected);
+
+            assert!(seeded.payments.iter().all(|payment| payment.date <= until
+                && payment.amount - payment.fee == seeded.mentee.net));
+            assert!(seeded.videos.iter().all(|video| video.date <= until
+                && video.received_date.as_deref() <= Some(video.date.as_str())));
+        }
+    }
+
+    #[test]
+    fn replaces_records_of_any_kind_only_when_forced() {
+        use crate::{
+            models::expense::{Expense, ExpenseCategory},
+            repositories::ExpenseRepository,
+            Store,
+        };
+
+        let store = Store::open(":memory:").unwrap();
+        let expenses = ExpenseRepository::new(store.connection());
+        expenses
+            .add_expense(&Expense {
+                id: 0,
+                date: "2026-03-01".to_string(),
+                category: ExpenseCategory::Software,
+                description: "Calendar".to_string(),
+                amount: 10,
+                currency: "USD".to_string(),
+                mentee_id: None,
+                receipt_path: None,
+            })
+            .unwrap();
+        let service = SeedService::new(store.connection(), store.config());
+
+        let refused = service.seed(3, 2, 42, until(), false).unwrap_err();
+        assert!(refused.to_string().contains("1 expenses"));
+
+        service.seed(3, 2, 42, until(), true).unwrap();
+        assert!(expenses
+            .get_expenses_between("2026-01-01", "2026-12-31")
+            .unwrap()
+            .is_empty());
+        assert_eq!(store.mentees().get_mentees(true).unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,217 @@
+use chrono::NaiveDate;
+use rusqlite::Connection;
+
+use crate::{
+    error::MenteeError,
+    models::{
+        call::CallWithMenteeName,
+        list::ListOptions,
+        payment::PaymentWithMenteeName,
+        statement::{Statement, StatementEntry},
+    },
+    repositories::{
+        CallRepository, MenteeRepository, PaymentRepository, SqliteCallRepository,
+        SqliteMenteeRepository, SqlitePaymentRepository,
+    },
+};
+
+/// Builds a statement between `from` and `to` inclusive. Everything before
+/// `from` is rolled into the opening balance. On the same day payments are
+/// listed before calls, matching the timeline.
+pub fn build_statement(
+    mentee_name: String,
+    calls_per_month: u32,
+    calls: Vec<CallWithMenteeName>,
+    payments: Vec<PaymentWithMenteeName>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Statement {
+    // (date, payments first, id, entry)
+    let mut records: Vec<(String, u8, u32, StatementEntry)> = Vec::new();
+
+    records.extend(payments.into_iter().map(|payment| {
+        let entry = StatementEntry {
+            date: payment.date.clone(),
+            description: "Payment".to_string(),
+            amount: Some(payment.amount),
+            credit: calls_per_month,
+            debit: 0,
+            balance: 0,
+        };
+        (payment.date, 0, payment.id, entry)
+    }));
+
+    records.extend(calls.into_iter().map(|call| {
+        let entry = StatementEntry {
+            date: call.date.clone(),
+            description: if call.free_call {
+                "Free call (not charged)".to_string()
+            } else {
+                "Call".to_string()
+            },
+            amount: None,
+            credit: 0,
+            debit: if call.free_call { 0 } else { 1 },
+            balance: 0,
+        };
+        (call.date, 1, call.id, entry)
+    }));
+
+    records.sort_by(|a, b| (&a.0, a.1, a.2).cmp(&(&b.0, b.1, b.2)));
+
+    let from = from.map(|date| date.format("%Y-%m-%d").to_string());
+    let to = to.map(|date| date.format("%Y-%m-%d").to_string());
+
+    let mut opening_balance = 0;
+    let mut balance = 0;
+    let mut entries = Vec::new();
+
+    for (date, _, _, mut entry) in records {
+        if to.as_ref().is_some_and(|to| &date > to) {
+            break;
+        }
+
+        balance += entry.credit as i64 - entry.debit as i64;
+
+        if from.as_ref().is_some_and(|from| &date < from) {
+            opening_balance = balance;
+            continue;
+        }
+
+        entry.balance = balance;
+        entries.push(entry);
+    }
+
+    Statement {
+        mentee_name,
+        from,
+        to,
+        opening_balance,
+        closing_balance: balance,
+        total_paid: entries.iter().filter_map(|entry| entry.amount).sum(),
+        calls_credited: entries.iter().map(|entry| entry.credit).sum(),
+        calls_used: entries.iter().map(|entry| entry.debit).sum(),
+        entries,
+    }
+}
+
+pub struct StatementService<'a> {
+    call_repo: Box<dyn CallRepository + 'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    payment_repo: Box<dyn PaymentRepository + 'a>,
+}
+
+impl<'a> StatementService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self {
+            call_repo: Box::new(SqliteCallRepository::new(conn)),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            payment_repo: Box::new(SqlitePaymentRepository::new(conn)),
+        }
+    }
+
+    pub fn get_statement(
+        &self,
+        name: String,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Statement, MenteeError> {
+        if let (Some(from), Some(to)) = (from, to) {
+            if from > to {
+                return Err(MenteeError::InvalidInput(
+                    "--from must be on or before --to".to_string(),
+                ));
+            }
+        }
+
+        let mentee = self
+            .mentee_repo
+            .get_mentee(&name.to_lowercase())?
+            .ok_or_else(|| {
+                MenteeError::NotFound(format!("No mentee found with name '{}'", name))
+            })?;
+
+        let mentee_id = Some(mentee.id as i64);
+
+        Ok(build_statement(
+            mentee.name,
+            mentee.calls,
+            self.call_repo
+                .get_all_calls(mentee_id, None, &ListOptions::default())?,
+            self.payment_repo
+                .get_all_payments(mentee_id, None, &ListOptions::default())?,
+            from,
+            to,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{call, payment};
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn rolls_earlier_records_into_opening_balance() {
+        let statement = build_statement(
+            "dan".to_string(),
+            2,
+            vec![
+                call(1, "2026-01-05", false),
+                call(2, "2026-02-01", false),
+                call(3, "2026-02-10", true),
+                call(4, "2026-03-02", false),
+            ],
+            vec![payment(1, "2026-01-01"), payment(2, "2026-02-01")],
+            Some(date("2026-02-01")),
+            Some(date("2026-02-28")),
+        );
+
+        assert_eq!(statement.opening_balance, 1);
+        assert_eq!(statement.closing_balance, 2);
+        assert_eq!(statement.total_paid, 200);
+        assert_eq!(statement.calls_credited, 2);
+        assert_eq!(statement.calls_used, 1);
+
+        let lines: Vec<(&str, &str, i64)> = statement
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.date.as_str(),
+                    entry.description.as_str(),
+                    entry.balance,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                ("2026-02-01", "Payment", 3),
+                ("2026-02-01", "Call", 2),
+                ("2026-02-10", "Free call (not charged)", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn covers_everything_without_a_period() {
+        let statement = build_statement(
+            "dan".to_string(),
+            4,
+            vec![call(1, "2026-01-05", false)],
+            vec![payment(1, "2026-01-01")],
+            None,
+            None,
+        );
+
+        assert_eq!(statement.opening_balance, 0);
+        assert_eq!(statement.closing_balance, 3);
+        assert_eq!(statement.entries.len(), 2);
+    }
+}
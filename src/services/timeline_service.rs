@@ -0,0 +1,240 @@
+use chrono::NaiveDate;
+use rusqlite::Connection;
+
+use crate::{
+    error::MenteeError,
+    models::{
+        call::CallWithMenteeName,
+        list::ListOptions,
+        mentee::StatusChange,
+        payment::PaymentWithMenteeName,
+        timeline::{TimelineEvent, TimelineEventKind},
+        video::{VideoStatus, VideoWithMenteeName},
+    },
+    repositories::{
+        CallRepository, MenteeRepository, PaymentRepository, SqliteCallRepository,
+        SqliteMenteeRepository, SqlitePaymentRepository, SqliteVideoRepository, VideoRepository,
+    },
+    utils::validation::parse_date_from_db,
+};
+
+/// Merges a mentee's records into one date ordered feed. Each payment credits
+/// `calls_per_month` calls and each call that wasn't free uses one.
+pub fn build_timeline(
+    calls_per_month: u32,
+    calls: Vec<CallWithMenteeName>,
+    videos: Vec<VideoWithMenteeName>,
+    payments: Vec<PaymentWithMenteeName>,
+    status_changes: Vec<StatusChange>,
+) -> Vec<TimelineEvent> {
+    let mut events: Vec<TimelineEvent> = Vec::new();
+
+    events.extend(status_changes.into_iter().map(|change| {
+        let transition = match change.from_status {
+            Some(from_status) => format!(
+                "Status changed from {} to {}",
+                from_status.as_str(),
+                change.to_status.as_str()
+            ),
+            None => format!("Added as {}", change.to_status.as_str()),
+        };
+
+        TimelineEvent {
+            date: change.date,
+            kind: TimelineEventKind::Status,
+            record_id: change.id as i64,
+            description: match change.reason {
+                Some(reason) if !reason.is_empty() => format!("{}: {}", transition, reason),
+                _ => transition,
+            },
+            balance_change: 0,
+            balance: 0,
+        }
+    }));
+
+    events.extend(payments.into_iter().map(|payment| TimelineEvent {
+        date: payment.date,
+        kind: TimelineEventKind::Payment,
+        record_id: payment.id as i64,
+        description: format!("Payment of ${}", payment.amount),
+        balance_change: calls_per_month as i64,
+        balance: 0,
+    }));
+
+    events.extend(calls.into_iter().map(|call| {
+        let first_line = call
+            .notes
+            .as_deref()
+            .and_then(|notes| notes.lines().find(|line| !line.trim().is_empty()))
+            .map(|line| format!(": {}", line.trim_start_matches(['#', '-', '*', '>', ' '])))
+            .unwrap_or_default();
+
+        TimelineEvent {
+            date: call.date,
+            kind: TimelineEventKind::Call,
+            record_id: call.id as i64,
+            description: format!(
+                "{}{}",
+                if call.free_call { "Free call" } else { "Call" },
+                first_line
+            ),
+            balance_change: if call.free_call { 0 } else { -1 },
+            balance: 0,
+        }
+    }));
+
+    events.extend(videos.into_iter().map(|video| TimelineEvent {
+        date: video.date,
+        kind: TimelineEventKind::Video,
+        record_id: video.id,
+        description: match video.status {
+            VideoStatus::Delivered => format!("Video analysis ({} mins)", video.length),
+            status => format!("Video received ({})", status.label().to_lowercase()),
+        },
+        balance_change: 0,
+        balance: 0,
+    }));
+
+    events.sort_by(|a, b| (&a.date, a.kind, a.record_id).cmp(&(&b.date, b.kind, b.record_id)));
+
+    let mut balance = 0;
+    for event in events.iter_mut() {
+        balance += event.balance_change;
+        event.balance = balance;
+    }
+
+    events
+}
+
+pub struct TimelineService<'a> {
+    call_repo: Box<dyn CallRepository + 'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    payment_repo: Box<dyn PaymentRepository + 'a>,
+    video_repo: Box<dyn VideoRepository + 'a>,
+}
+
+impl<'a> TimelineService<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self {
+            call_repo: Box::new(SqliteCallRepository::new(conn)),
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            payment_repo: Box::new(SqlitePaymentRepository::new(conn)),
+            video_repo: Box::new(SqliteVideoRepository::new(conn)),
+        }
+    }
+
+    /// Builds the whole timeline so balances are correct, then keeps the events
+    /// between `since` and `until` inclusive
+    pub fn get_timeline(
+        &self,
+        name: String,
+        since: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+    ) -> Result<Vec<TimelineEvent>, MenteeError> {
+        let mentee = self
+            .mentee_repo
+            .get_mentee(&name.to_lowercase())?
+            .ok_or_else(|| {
+                MenteeError::NotFound(format!("No mentee found with name '{}'", name))
+            })?;
+
+        let mentee_id = Some(mentee.id as i64);
+
+        let events = build_timeline(
+            mentee.calls,
+            self.call_repo
+                .get_all_calls(mentee_id, None, &ListOptions::default())?,
+            self.video_repo
+                .get_all_videos(mentee_id, None, &ListOptions::default())?,
+            self.payment_repo
+                .get_all_payments(mentee_id, None, &ListOptions::default())?,
+            self.mentee_repo.get_status_history(mentee.id as i64)?,
+        );
+
+        Ok(events
+            .into_iter()
+            .filter(|event| {
+                let Ok(date) = parse_date_from_db(&event.date) else {
+                    return true;
+                };
+
+                since.is_none_or(|since| date >= since) && until.is_none_or(|until| date <= until)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{self, payment},
+        models::mentee::Status,
+    };
+
+    fn call(id: u32, date: &str, free_call: bool) -> CallWithMenteeName {
+        CallWithMenteeName {
+            notes: Some("\n## CV review\nmore".to_string()),
+            ..fixtures::call(id, date, free_call)
+        }
+    }
+
+    #[test]
+    fn orders_events_and_tracks_balance() {
+        let videos = vec![VideoWithMenteeName {
+            id: 1,
+            mentee_name: "dan".to_string(),
+            date: "2026-01-10".to_string(),
+            length: 15,
+            notes: String::new(),
+            status: VideoStatus::Delivered,
+            received_date: None,
+            due_date: None,
+            source_url: None,
+            file_path: None,
+        }];
+
+        let events = build_timeline(
+            2,
+            vec![
+                call(2, "2026-01-20", false),
+                call(1, "2026-01-01", true),
+                call(3, "2026-02-01", false),
+            ],
+            videos,
+            vec![payment(1, "2026-01-01"), payment(2, "2026-02-01")],
+            vec![StatusChange {
+                id: 1,
+                mentee_id: 1,
+                date: "2026-01-20".to_string(),
+                from_status: Some(Status::Hot),
+                to_status: Status::Cold,
+                reason: Some("Exams".to_string()),
+            }],
+        );
+
+        let summary: Vec<(&str, TimelineEventKind, i64)> = events
+            .iter()
+            .map(|event| (event.date.as_str(), event.kind, event.balance))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("2026-01-01", TimelineEventKind::Payment, 2),
+                ("2026-01-01", TimelineEventKind::Call, 2),
+                ("2026-01-10", TimelineEventKind::Video, 2),
+                ("2026-01-20", TimelineEventKind::Status, 2),
+                ("2026-01-20", TimelineEventKind::Call, 1),
+                ("2026-02-01", TimelineEventKind::Payment, 3),
+                ("2026-02-01", TimelineEventKind::Call, 2),
+            ]
+        );
+        assert_eq!(events[1].description, "Free call: CV review");
+        assert_eq!(events[2].description, "Video analysis (15 mins)");
+        assert_eq!(
+            events[3].description,
+            "Status changed from hot to cold: Exams"
+        );
+    }
+}
@@ -1,65 +1,433 @@
+use std::path::Path;
+
+use chrono::{Days, Local};
 use colored::Colorize;
-use inquire::{CustomType, DateSelect, Text};
+use inquire::{validator::Validation, CustomType, DateSelect, Text};
 use rusqlite::Connection;
+use serde_json::json;
 
 use crate::{
+    config::{Config, VideosConfig},
     error::MenteeError,
-    models::video::{Video, VideoWithMenteeName},
-    repositories::{MenteeRepository, VideoRepository},
-    utils::validation::parse_date_from_db,
+    hooks::{HookEvent, Hooks},
+    models::{
+        list::ListOptions,
+        video::{Video, VideoInput, VideoStatus, VideoWithMenteeName},
+    },
+    repositories::{
+        MenteeRepository, SqliteMenteeRepository, SqliteVideoRepository, VideoRepository,
+    },
+    utils::{
+        files::{file_details, open_with_default_app},
+        ui::prompt_notes,
+        validation::{normalize_date, parse_date_from_db, validate_url_core},
+    },
+    VideoSort,
 };
 
 pub struct VideoService<'a> {
-    mentee_repo: MenteeRepository<'a>,
-    video_repo: VideoRepository<'a>,
+    mentee_repo: Box<dyn MenteeRepository + 'a>,
+    video_repo: Box<dyn VideoRepository + 'a>,
+    settings: &'a VideosConfig,
+    hooks: Hooks<'a>,
 }
 
 impl<'a> VideoService<'a> {
-    pub fn new(conn: &'a Connection) -> Self {
+    pub fn new(conn: &'a Connection, config: &'a Config) -> Self {
+        Self {
+            mentee_repo: Box::new(SqliteMenteeRepository::new(conn)),
+            video_repo: Box::new(SqliteVideoRepository::new(conn)),
+            settings: &config.videos,
+            hooks: Hooks::new(&config.hooks),
+        }
+    }
+
+    /// Builds the service on another storage backend
+    pub fn with_repositories(
+        video_repo: impl VideoRepository + 'a,
+        mentee_repo: impl MenteeRepository + 'a,
+        config: &'a Config,
+    ) -> Self {
         Self {
-            mentee_repo: MenteeRepository::new(conn),
-            video_repo: VideoRepository::new(conn),
+            mentee_repo: Box::new(mentee_repo),
+            video_repo: Box::new(video_repo),
+            settings: &config.videos,
+            hooks: Hooks::new(&config.hooks),
+        }
+    }
+
+    fn get_video(&self, video_id: u32) -> Result<Video, MenteeError> {
+        self.video_repo
+            .get_video_by_id(video_id)
+            .map_err(|_| MenteeError::NotFound(format!("Video with id of {}", video_id)))
+    }
+
+    fn get_mentee_id(&self, name: &str) -> Result<i64, MenteeError> {
+        self.mentee_repo
+            .get_mentee_id(name)?
+            .ok_or_else(|| MenteeError::NotFound(format!("No mentee with name '{}'", name)))
+    }
+
+    /// Asks for the due date, suggesting `due_in_days` after the received date
+    fn prompt_due_date(
+        &self,
+        received_date: &str,
+        current: Option<&str>,
+    ) -> Result<Option<String>, MenteeError> {
+        let received = parse_date_from_db(received_date).map_err(|_| {
+            MenteeError::InvalidInput(format!("'{}' is not a valid date", received_date))
+        })?;
+        let default = current
+            .and_then(|due_date| parse_date_from_db(due_date).ok())
+            .or_else(|| received.checked_add_days(Days::new(self.settings.due_in_days.into())))
+            .unwrap_or(received);
+
+        let due_date = DateSelect::new("When is the review due? (Esc to skip)")
+            .with_default(default)
+            .with_min_date(received)
+            .prompt_skippable()?;
+
+        Ok(due_date.map(|date| date.format("%Y-%m-%d").to_string()))
+    }
+
+    fn prompt_length(current: Option<u32>) -> Result<u32, MenteeError> {
+        let mut length_prompt = CustomType::<u32>::new("Roughly how long was the video?");
+        let starting_length = current.map(|length| length.to_string());
+        if let Some(starting_length) = starting_length.as_deref() {
+            length_prompt = length_prompt.with_starting_input(starting_length);
+        }
+
+        Ok(length_prompt.prompt()?)
+    }
+
+    /// Asks where the video can be found, online and/or on disk, returning
+    /// the link and the path of a local copy
+    fn prompt_sources(
+        current_url: Option<&str>,
+        current_path: Option<&str>,
+    ) -> Result<(Option<String>, Option<String>), MenteeError> {
+        let source_url = Text::new("Link to the video (optional):")
+            .with_initial_value(current_url.unwrap_or(""))
+            .with_validator(|input: &str| {
+                let input = input.trim();
+                match validate_url_core(input) {
+                    Err(message) if !input.is_empty() => Ok(Validation::Invalid(message.into())),
+                    _ => Ok(Validation::Valid),
+                }
+            })
+            .prompt()?;
+
+        let file_path = Text::new("Path to a local copy (optional):")
+            .with_initial_value(current_path.unwrap_or(""))
+            .with_validator(|input: &str| {
+                let input = input.trim();
+                if input.is_empty() || Path::new(input).is_file() {
+                    Ok(Validation::Valid)
+                } else {
+                    Ok(Validation::Invalid(
+                        format!("No file found at '{}'", input).into(),
+                    ))
+                }
+            })
+            .prompt()?;
+
+        Ok((Some(source_url), Some(file_path)))
+    }
+
+    /// Checks a video given without prompting, the same as the prompts do. A newly
+    /// attached file is checked to exist and its size and checksum recorded, the
+    /// details of one already attached to `previous` are kept.
+    pub(crate) fn video_from_input(
+        mentee_id: i64,
+        input: VideoInput,
+        previous: Option<&Video>,
+    ) -> Result<Video, MenteeError> {
+        let date = normalize_date(&input.date).map_err(MenteeError::InvalidInput)?;
+
+        // before delivery the video's date is when it was received
+        let received_date = match input.received_date {
+            None if input.status != VideoStatus::Delivered => Some(date.clone()),
+            received_date => received_date
+                .as_deref()
+                .map(normalize_date)
+                .transpose()
+                .map_err(MenteeError::InvalidInput)?,
+        };
+        let due_date = input
+            .due_date
+            .as_deref()
+            .map(normalize_date)
+            .transpose()
+            .map_err(MenteeError::InvalidInput)?;
+
+        // zero padded dates compare the same as the days they're on
+        let received = received_date.as_deref();
+        let due = due_date.as_deref();
+
+        if let (Some(received), Some(due)) = (received, due) {
+            if due < received {
+                return Err(MenteeError::InvalidInput(
+                    "the due date can't be before the video was received".to_string(),
+                ));
+            }
+        }
+
+        if let Some(received) = received {
+            if input.status == VideoStatus::Delivered && date.as_str() < received {
+                return Err(MenteeError::InvalidInput(
+                    "the video can't be delivered before it was received".to_string(),
+                ));
+            }
+        }
+
+        let source_url = input
+            .source_url
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+        if let Some(url) = source_url.as_deref() {
+            validate_url_core(url).map_err(MenteeError::InvalidInput)?;
+        }
+
+        let file_path = input
+            .file_path
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty());
+        let attached = previous.filter(|previous| previous.file_path == file_path);
+        let (file_path, file_size, file_sha256) = match (file_path, attached) {
+            (None, _) => (None, None, None),
+            (Some(path), Some(previous)) => {
+                (Some(path), previous.file_size, previous.file_sha256.clone())
+            }
+            (Some(path), None) if !Path::new(&path).is_file() => {
+                return Err(MenteeError::InvalidInput(format!(
+                    "No file found at '{}'",
+                    path
+                )))
+            }
+            (Some(path), None) => {
+                let details = file_details(Path::new(&path))?;
+                (Some(details.path), Some(details.size), Some(details.sha256))
+            }
+        };
+
+        Ok(Video {
+            id: 0,
+            mentee_id,
+            date,
+            length: input.length,
+            notes: input.notes,
+            status: input.status,
+            received_date,
+            due_date,
+            source_url,
+            file_path,
+            file_size,
+            file_sha256,
+        })
+    }
+
+    /// The fields of a stored video, to be changed and saved with `replace_video`
+    fn video_to_input(video: &Video) -> VideoInput {
+        VideoInput {
+            date: video.date.clone(),
+            length: video.length,
+            notes: video.notes.clone(),
+            status: video.status,
+            received_date: video.received_date.clone(),
+            due_date: video.due_date.clone(),
+            source_url: video.source_url.clone(),
+            file_path: video.file_path.clone(),
         }
     }
 
-    pub fn add_video(&self, name: String) -> Result<String, MenteeError> {
-        let mentee_id = self
-            .mentee_repo
-            .get_mentee_id(&name)?
-            .ok_or_else(|| MenteeError::NotFound(format!("No mentee with name '{}'", name)))?;
+    /// Logs a video that has already been delivered
+    pub(crate) fn add_video(&self, name: String) -> Result<String, MenteeError> {
+        self.get_mentee_id(&name)?;
 
         let date = DateSelect::new("Enter the date of the video:")
-            .prompt()
-            .expect("Failed to read date")
+            .prompt()?
             .format("%Y-%m-%d")
             .to_string();
 
-        let length = inquire::prompt_u32("Roughly how long was the video?")?;
+        let length = Self::prompt_length(None)?;
 
-        let notes = Text::new("Enter any notes for the video:")
-            .with_placeholder("e.g. Discussed project progress ")
-            .prompt()
-            .expect("Failed to read notes");
+        let notes = prompt_notes("Enter any notes for the video:", None)?;
 
-        let result = self.video_repo.add_video(Video {
-            id: 0,
-            mentee_id,
-            date: date.clone(),
-            length,
-            notes,
+        let (source_url, file_path) = Self::prompt_sources(None, None)?;
+
+        self.create_video(
+            &name,
+            VideoInput {
+                date: date.clone(),
+                length,
+                notes,
+                status: VideoStatus::Delivered,
+                received_date: None,
+                due_date: None,
+                source_url,
+                file_path,
+            },
+        )?;
+
+        Ok(format!("Video log with {name} on {date} added.")
+            .green()
+            .to_string())
+    }
+
+    /// Records a video received from a mentee that is waiting for review
+    pub(crate) fn request_video(&self, name: String) -> Result<String, MenteeError> {
+        self.get_mentee_id(&name)?;
+
+        let received_date = DateSelect::new("When was the video received?")
+            .prompt()?
+            .format("%Y-%m-%d")
+            .to_string();
+
+        let due_date = self.prompt_due_date(&received_date, None)?;
+
+        let notes = prompt_notes("Enter any notes for the video:", None)?;
+
+        let (source_url, file_path) = Self::prompt_sources(None, None)?;
+
+        self.create_video(
+            &name,
+            VideoInput {
+                date: received_date.clone(),
+                length: 0,
+                notes,
+                status: VideoStatus::Requested,
+                received_date: Some(received_date),
+                due_date: due_date.clone(),
+                source_url,
+                file_path,
+            },
+        )?;
+
+        let due = due_date.map_or(String::new(), |due_date| format!(", due {due_date}"));
+
+        Ok(format!("Video review for {name} requested{due}.")
+            .green()
+            .to_string())
+    }
+
+    /// Records a video without prompting. One that isn't delivered yet is
+    /// announced as requested.
+    pub fn create_video(
+        &self,
+        name: &str,
+        input: VideoInput,
+    ) -> Result<VideoWithMenteeName, MenteeError> {
+        let mentee_id = self.get_mentee_id(name)?;
+        let video = Self::video_from_input(mentee_id, input, None)?;
+
+        let data = json!({
+            "received_date": video.received_date,
+            "due_date": video.due_date,
+            "notes": video.notes,
+            "source_url": video.source_url,
+            "file_path": video.file_path,
         });
+        let delivered = video.status == VideoStatus::Delivered;
 
-        match result {
-            Ok(_) => Ok(format!("Video log with {name} on {date} added.")
-                .green()
-                .to_string()),
-            Err(err) => Err(MenteeError::from(err)),
+        let video_id = self.video_repo.add_video(video)?;
+
+        if !delivered {
+            let mut data = data;
+            data["id"] = json!(video_id);
+            self.hooks.fire(HookEvent::VideoRequested, name, data);
+        }
+
+        self.get_video_with_mentee_name(video_id as u32)
+    }
+
+    pub fn get_video_with_mentee_name(
+        &self,
+        video_id: u32,
+    ) -> Result<VideoWithMenteeName, MenteeError> {
+        self.video_repo
+            .get_video_with_mentee_name(video_id)
+            .map_err(|_| MenteeError::NotFound(format!("Video with id of {}", video_id)))
+    }
+
+    pub fn start_video(&self, video_id: u32) -> Result<String, MenteeError> {
+        let mut video = self.get_video(video_id)?;
+
+        match video.status {
+            VideoStatus::Requested => {}
+            VideoStatus::InProgress => {
+                return Err(MenteeError::InvalidInput(format!(
+                    "Video with id of {} is already in progress",
+                    video_id
+                )))
+            }
+            VideoStatus::Delivered => {
+                return Err(MenteeError::InvalidInput(format!(
+                    "Video with id of {} has already been delivered",
+                    video_id
+                )))
+            }
         }
+
+        video.status = VideoStatus::InProgress;
+        self.video_repo.update_video(&video)?;
+
+        Ok(format!("Video with id of {} is in progress", video_id))
+    }
+
+    pub(crate) fn deliver_video(&self, video_id: u32) -> Result<String, MenteeError> {
+        let video = self.get_video(video_id)?;
+
+        if video.status == VideoStatus::Delivered {
+            return Err(MenteeError::InvalidInput(format!(
+                "Video with id of {} has already been delivered",
+                video_id
+            )));
+        }
+
+        let mut date_select = DateSelect::new("When was the analysis delivered?")
+            .with_default(Local::now().date_naive());
+        if let Some(received) = video
+            .received_date
+            .as_deref()
+            .and_then(|date| parse_date_from_db(date).ok())
+        {
+            date_select = date_select.with_min_date(received);
+        }
+        let date = date_select.prompt()?.format("%Y-%m-%d").to_string();
+
+        let mut input = Self::video_to_input(&video);
+        input.length = Self::prompt_length(None)?;
+        input.notes = prompt_notes("Enter any notes for the video:", Some(&video.notes))?;
+        input.date = date.clone();
+        input.status = VideoStatus::Delivered;
+
+        self.replace_video(video_id, input)?;
+
+        Ok(
+            format!("Video with id of {} delivered on {}", video_id, date)
+                .green()
+                .to_string(),
+        )
+    }
+
+    /// Outstanding reviews, soonest due first
+    pub fn get_queue(&self) -> Result<Vec<VideoWithMenteeName>, MenteeError> {
+        self.video_repo.get_outstanding_videos()
     }
 
     pub fn get_all_videos(
         &self,
         name: Option<String>,
+    ) -> Result<Vec<VideoWithMenteeName>, MenteeError> {
+        self.list_videos(name, None, &ListOptions::default())
+    }
+
+    /// Videos filtered, sorted and paged as the options say
+    pub fn list_videos(
+        &self,
+        name: Option<String>,
+        status: Option<VideoStatus>,
+        options: &ListOptions<VideoSort>,
     ) -> Result<Vec<VideoWithMenteeName>, MenteeError> {
         let mentee_id = if let Some(name) = name {
             match self.mentee_repo.get_mentee_id(&name)? {
@@ -75,51 +443,163 @@ impl<'a> VideoService<'a> {
             None
         };
 
-        self.video_repo
-            .get_all_videos(mentee_id)
-            .map_err(MenteeError::DatabaseError)
+        self.video_repo.get_all_videos(mentee_id, status, options)
     }
 
-    pub fn update_video(&self, video_id: u32) -> Result<String, MenteeError> {
-        let video = self
-            .video_repo
-            .get_video_by_id(video_id)
-            .map_err(|_| MenteeError::NotFound(format!("Video with id of {}", video_id)))?;
+    pub(crate) fn update_video(&self, video_id: u32) -> Result<String, MenteeError> {
+        let video = self.get_video(video_id)?;
+        let mut input = Self::video_to_input(&video);
 
-        let parsed_date = parse_date_from_db(&video.date).unwrap();
+        let parsed_date = parse_date_from_db(&video.date).map_err(|_| {
+            MenteeError::InvalidInput(format!("'{}' is not a valid date", video.date))
+        })?;
 
-        let date = DateSelect::new("Enter the date of the video:")
+        let message = match video.status {
+            VideoStatus::Delivered => "Enter the date of the video:",
+            _ => "When was the video received?",
+        };
+
+        input.date = DateSelect::new(message)
             .with_default(parsed_date)
-            .prompt()
-            .expect("Failed to read date")
+            .prompt()?
             .format("%Y-%m-%d")
             .to_string();
 
-        let length: u32 = CustomType::new("Roughly how long was the video?")
-            .with_starting_input(&video.length.to_string())
-            .prompt()
-            .expect("Failed to read length");
+        if video.status == VideoStatus::Delivered {
+            input.length = Self::prompt_length(Some(video.length))?;
+        } else {
+            input.received_date = Some(input.date.clone());
+            input.due_date = self.prompt_due_date(&input.date, video.due_date.as_deref())?;
+        }
+
+        input.notes = prompt_notes("Enter any notes for the video:", Some(&video.notes))?;
+
+        (input.source_url, input.file_path) =
+            Self::prompt_sources(video.source_url.as_deref(), video.file_path.as_deref())?;
+
+        self.replace_video(video_id, input)?;
+
+        Ok("1 video record updated".to_string())
+    }
+
+    /// Replaces every field of a video without prompting. Moving it to
+    /// delivered announces the delivery.
+    pub fn replace_video(
+        &self,
+        video_id: u32,
+        input: VideoInput,
+    ) -> Result<VideoWithMenteeName, MenteeError> {
+        let previous = self.get_video(video_id)?;
+
+        let video = Video {
+            id: previous.id,
+            ..Self::video_from_input(previous.mentee_id, input, Some(&previous))?
+        };
+        self.video_repo.update_video(&video)?;
+
+        let updated = self.get_video_with_mentee_name(video_id)?;
+
+        if previous.status != VideoStatus::Delivered && video.status == VideoStatus::Delivered {
+            self.hooks.fire(
+                HookEvent::VideoDelivered,
+                &updated.mentee_name,
+                json!({
+                    "id": video_id,
+                    "received_date": video.received_date,
+                    "due_date": video.due_date,
+                    "delivered_date": video.date,
+                    "length": video.length,
+                    "notes": video.notes,
+                }),
+            );
+        }
+
+        Ok(updated)
+    }
+
+    /// Opens the video's local copy, or its link when there isn't one.
+    /// With `print` the references are only listed.
+    pub(crate) fn open_video(&self, video_id: u32, print: bool) -> Result<String, MenteeError> {
+        let video = self.get_video(video_id)?;
 
-        let notes = Text::new("Enter any notes for the video:")
-            .with_placeholder("e.g. Discussed project progress ")
-            .with_initial_value(&video.notes)
-            .prompt()
-            .expect("Failed to read notes");
+        if video.file_path.is_none() && video.source_url.is_none() {
+            return Err(MenteeError::InvalidInput(format!(
+                "Video with id of {} has no link or local file",
+                video_id
+            )));
+        }
+
+        let mut warning = None;
+        let mut local_file = None;
+        if let Some(file_path) = video.file_path.as_deref() {
+            match std::fs::metadata(file_path)
+                .ok()
+                .filter(|meta| meta.is_file())
+            {
+                None if video.source_url.is_none() => {
+                    return Err(MenteeError::NotFound(format!(
+                        "File '{}' for video with id of {}",
+                        file_path, video_id
+                    )))
+                }
+                None => warning = Some(format!("File '{}' no longer exists", file_path)),
+                Some(meta) => {
+                    if video
+                        .file_size
+                        .is_some_and(|size| size != meta.len() as i64)
+                    {
+                        warning = Some(format!(
+                            "File '{}' has changed size since it was attached",
+                            file_path
+                        ));
+                    }
+                    local_file = Some(file_path);
+                }
+            }
+        }
+
+        let mut lines = Vec::new();
+        if let Some(warning) = warning.as_ref() {
+            lines.push(warning.yellow().to_string());
+        }
+
+        if print {
+            if let Some(source_url) = video.source_url.as_deref() {
+                lines.push(format!("Link: {}", source_url));
+            }
+            if let Some(file_path) = video.file_path.as_deref() {
+                lines.push(format!("File: {}", file_path));
+            }
+            if let Some(sha256) = video.file_sha256.as_deref() {
+                lines.push(format!("SHA-256: {}", sha256));
+            }
+            return Ok(lines.join("\n"));
+        }
+
+        let Some(target) = local_file.or(video.source_url.as_deref()) else {
+            return Err(MenteeError::InvalidInput(format!(
+                "Video with id of {} has no link or local file",
+                video_id
+            )));
+        };
 
-        let updated = self
-            .video_repo
-            .update_video(date, length, notes, video_id)?;
+        open_with_default_app(target).map_err(|err| {
+            MenteeError::InvalidInput(format!(
+                "Could not open '{}' ({}), use --print to show it instead",
+                target, err
+            ))
+        })?;
+        lines.push(format!("Opened {}", target));
 
-        Ok(format!("{updated} video record updated"))
+        Ok(lines.join("\n"))
     }
 
     pub fn delete_video(&self, video_id: u32) -> Result<String, MenteeError> {
         let result = self.video_repo.delete_video(video_id);
 
-        match result {
-            Ok(0) => Err(MenteeError::NotFound(format!("Video with Id {}", video_id))),
-            Ok(_) => Ok(format!("Video with Id of {} deleted.", video_id)),
-            Err(err) => Err(MenteeError::DatabaseError(err)),
+        match result? {
+            0 => Err(MenteeError::NotFound(format!("Video with Id {}", video_id))),
+            _ => Ok(format!("Video with Id of {} deleted.", video_id)),
         }
     }
 }
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use crate::{
+    config::Config,
+    db::{connection, migrations},
+    error::MenteeError,
+    services::{CallService, MenteeService, PaymentService, VideoService},
+};
+
+/// An open mentees database, where using the crate as a library starts.
+///
+/// The services it hands out borrow the store and never prompt, they take the
+/// input structs from [`crate::models`] and return the records as saved.
+pub struct Store {
+    conn: Connection,
+    config: Config,
+}
+
+impl Store {
+    /// Opens the database at `path`, creating it if needed and bringing its
+    /// tables up to date. Settings are left at their defaults and no hooks run.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, MenteeError> {
+        Self::with_config(Config::for_database(path.as_ref().to_path_buf()))
+    }
+
+    /// Opens the database and settings in `~/.mentees`, the same ones the CLI uses
+    pub fn open_default() -> Result<Self, MenteeError> {
+        Self::with_config(Config::new()?)
+    }
+
+    fn with_config(config: Config) -> Result<Self, MenteeError> {
+        let conn = connection::establish_connection(&config)?;
+        migrations::run_migrations(&conn)?;
+
+        Ok(Self { conn, config })
+    }
+
+    pub(crate) fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub(crate) fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn mentees(&self) -> MenteeService<'_> {
+        MenteeService::new(&self.conn, &self.config)
+    }
+
+    pub fn calls(&self) -> CallService<'_> {
+        CallService::new(&self.conn, &self.config)
+    }
+
+    pub fn videos(&self) -> VideoService<'_> {
+        VideoService::new(&self.conn, &self.config)
+    }
+
+    pub fn payments(&self) -> PaymentService<'_> {
+        PaymentService::new(&self.conn, &self.config)
+    }
+}
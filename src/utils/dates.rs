@@ -0,0 +1,99 @@
+use chrono::{Datelike, Local, NaiveDate};
+
+/// Today's date in the format dates are stored in
+pub fn today() -> String {
+    Local::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+/// Counts months since year zero so month arithmetic is plain integer maths.
+/// Accepts both `YYYY-MM` and `YYYY-MM-DD`.
+pub fn month_index(date: &str) -> Option<i32> {
+    let mut parts = date.split('-');
+    let year: i32 = parts.next()?.parse().ok()?;
+    let month: i32 = parts.next()?.parse().ok()?;
+
+    if (1..=12).contains(&month) {
+        Some(year * 12 + month - 1)
+    } else {
+        None
+    }
+}
+
+pub fn month_index_of(date: NaiveDate) -> i32 {
+    date.year() * 12 + date.month0() as i32
+}
+
+/// Formats a month index back to `YYYY-MM`
+pub fn month_label(index: i32) -> String {
+    format!(
+        "{:04}-{:02}",
+        index.div_euclid(12),
+        index.rem_euclid(12) + 1
+    )
+}
+
+/// First and last day of the fiscal year starting on `month`-`day` of `year`.
+/// Returns `None` when the start date doesn't exist that year.
+pub fn fiscal_year(year: i32, month: u32, day: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let start = NaiveDate::from_ymd_opt(year, month, day)?;
+    let next_start = NaiveDate::from_ymd_opt(year + 1, month, day)?;
+
+    Some((start, next_start.pred_opt()?))
+}
+
+/// The date `day` falls on in a month, moved back to the last day of the month
+/// for days that month doesn't have
+pub fn day_in_month(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    (1..=day.min(31))
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn month_index_round_trips() {
+        let index = month_index("2026-03-14").unwrap();
+        assert_eq!(month_label(index), "2026-03");
+        assert_eq!(month_index("2026-03"), Some(index));
+        assert_eq!(month_label(index - 3), "2025-12");
+    }
+
+    #[test]
+    fn invalid_months() {
+        assert_eq!(month_index("2026-13"), None);
+        assert_eq!(month_index("march"), None);
+    }
+
+    #[test]
+    fn month_index_of_date() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+        assert_eq!(month_index_of(date), month_index("2026-01").unwrap());
+    }
+
+    #[test]
+    fn fiscal_year_bounds() {
+        let (start, end) = fiscal_year(2026, 4, 6).unwrap();
+        assert_eq!(start, NaiveDate::from_ymd_opt(2026, 4, 6).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2027, 4, 5).unwrap());
+
+        let (start, end) = fiscal_year(2026, 1, 1).unwrap();
+        assert_eq!(start, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2026, 12, 31).unwrap());
+    }
+
+    #[test]
+    fn day_in_month_clamps_to_month_end() {
+        assert_eq!(
+            day_in_month(2026, 2, 31),
+            NaiveDate::from_ymd_opt(2026, 2, 28)
+        );
+        assert_eq!(
+            day_in_month(2026, 3, 15),
+            NaiveDate::from_ymd_opt(2026, 3, 15)
+        );
+        assert_eq!(day_in_month(2026, 13, 1), None);
+    }
+}
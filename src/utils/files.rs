@@ -0,0 +1,85 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+    process::Command,
+};
+
+use sha2::{Digest, Sha256};
+
+/// Where a local file lives, how big it is and its SHA-256, so a moved or
+/// changed file can be spotted later
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileDetails {
+    pub path: String,
+    pub size: i64,
+    pub sha256: String,
+}
+
+/// Hex encoded SHA-256 of everything `reader` yields
+pub fn sha256_hex(mut reader: impl Read) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 64 * 1024];
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Reads the file at `path`, stored as an absolute path so it resolves from anywhere
+pub fn file_details(path: &Path) -> io::Result<FileDetails> {
+    let path = std::fs::canonicalize(path)?;
+    let size = std::fs::metadata(&path)?.len();
+    let sha256 = sha256_hex(File::open(&path)?)?;
+
+    Ok(FileDetails {
+        path: path.display().to_string(),
+        size: size as i64,
+        sha256,
+    })
+}
+
+/// Opens a file or URL with the desktop's default application
+pub fn open_with_default_app(target: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        // not `cmd /C start`, which would run anything after a `&` in a URL
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+
+    let status = command.arg(target).status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("could not open '{}'", target)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex("abc".as_bytes()).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -1,2 +1,5 @@
+pub mod dates;
+pub mod files;
+pub mod template;
 pub mod ui;
 pub mod validation;
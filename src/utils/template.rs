@@ -0,0 +1,77 @@
+/// Replaces each `{{name}}` in the template with its value. Whitespace inside
+/// the braces is ignored and unknown placeholders are left as they are, so a
+/// typo in a custom template shows up in the output rather than vanishing.
+pub fn render_template(template: &str, values: &[(&str, String)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start..].find("}}") else {
+            break;
+        };
+
+        let placeholder = &rest[start..start + length + 2];
+        let key = placeholder[2..placeholder.len() - 2].trim();
+
+        output.push_str(&rest[..start]);
+        match values.iter().find(|(name, _)| *name == key) {
+            Some((_, value)) => output.push_str(value),
+            None => output.push_str(placeholder),
+        }
+
+        rest = &rest[start + length + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template_substitutes_values() {
+        let values = [("name", "Dan".to_string()), ("total", "400".to_string())];
+
+        assert_eq!(
+            render_template("Hi {{name}}, you owe {{ total }}.", &values),
+            "Hi Dan, you owe 400."
+        );
+    }
+
+    #[test]
+    fn test_render_template_keeps_unknown_and_unclosed_placeholders() {
+        let values = [("name", "Dan".to_string())];
+
+        assert_eq!(
+            render_template("{{nme}} {{name}} {{name", &values),
+            "{{nme}} Dan {{name"
+        );
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+    }
+}
@@ -1,11 +1,71 @@
-use inquire::Select;
+use std::{env, ffi::OsStr};
 
-use crate::{error::MenteeError, models::mentee::Status};
+use inquire::{Editor, Select, Text};
+
+use crate::{
+    error::MenteeError,
+    models::mentee::{Source, Status},
+    utils::validation::inquire_validate_currency,
+};
 
 pub fn select_status() -> Result<Status, MenteeError> {
     // generate options from enum variants
     let options = Status::variants();
     let selected = Select::new("Select the mentee's status", options).prompt()?;
 
-    Status::from_str(&selected).ok_or_else(|| "Invalid status selected".into())
+    Status::from_str(selected).ok_or_else(|| "Invalid status selected".into())
+}
+
+/// Asks where the mentee found us, pressing escape leaves it unrecorded
+pub fn select_source() -> Result<Option<Source>, MenteeError> {
+    let selected = Select::new("Where did they find you?", Source::variants())
+        .with_help_message("esc to skip")
+        .prompt_skippable()?;
+
+    Ok(selected.and_then(Source::from_str))
+}
+
+/// The editor configured through `$VISUAL` or `$EDITOR`, if any
+fn configured_editor() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|key| env::var(key).ok())
+        .find(|editor| !editor.trim().is_empty())
+}
+
+/// Prompts for long-form notes. Opens `$VISUAL`/`$EDITOR` with the current value
+/// pre-filled when one is configured, otherwise falls back to a one-line prompt.
+pub fn prompt_notes(message: &str, current: Option<&str>) -> Result<String, MenteeError> {
+    let current = current.unwrap_or("");
+
+    let Some(editor) = configured_editor() else {
+        return Ok(Text::new(message)
+            .with_placeholder("e.g. Discussed project progress ")
+            .with_initial_value(current)
+            .prompt()?);
+    };
+
+    // editors such as `code --wait` need their arguments passed separately
+    let mut parts = editor.split_whitespace();
+    let command = parts.next().unwrap_or_default();
+    let args: Vec<&OsStr> = parts.map(OsStr::new).collect();
+
+    let notes = Editor::new(message)
+        .with_editor_command(OsStr::new(command))
+        .with_args(&args)
+        .with_file_extension(".md")
+        .with_predefined_text(current)
+        .prompt()?;
+
+    Ok(notes.trim_end().to_string())
+}
+
+/// Prompts for a three letter currency code, returned in upper case
+pub fn prompt_currency(default: &str) -> Result<String, MenteeError> {
+    let currency = Text::new("Which currency?")
+        .with_default(default)
+        .with_validator(inquire_validate_currency)
+        .prompt()?;
+
+    Ok(currency.trim().to_uppercase())
 }
@@ -3,10 +3,62 @@ use std::error::Error;
 
 use chrono::{NaiveDate, ParseError};
 
+use crate::{models::contact::ContactKind, utils::dates::month_index};
+
 pub fn parse_date_from_db(date_str: &str) -> Result<NaiveDate, ParseError> {
     NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
 }
 
+pub fn clap_validate_date(s: &str) -> Result<NaiveDate, String> {
+    parse_date_from_db(s).map_err(|_| format!("'{}' is not a date in the format YYYY-MM-DD.", s))
+}
+
+/// Checks a YYYY-MM-DD date and writes it zero padded, the way dates are stored
+/// and compared in the database
+pub fn normalize_date(s: &str) -> Result<String, String> {
+    clap_validate_date(s.trim()).map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+pub fn clap_validate_month(s: &str) -> Result<String, String> {
+    match s.split_once('-') {
+        Some((year, month)) if year.len() == 4 && month.len() == 2 && month_index(s).is_some() => {
+            Ok(s.to_string())
+        }
+        _ => Err(format!("'{}' is not a month in the format YYYY-MM.", s)),
+    }
+}
+
+/// A CSV column mapping like `date=When`, split into the field and the column
+pub fn clap_validate_mapping(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((field, column)) if !field.trim().is_empty() && !column.trim().is_empty() => {
+            Ok((field.trim().to_lowercase(), column.trim().to_string()))
+        }
+        _ => Err(format!(
+            "'{}' is not a mapping in the format FIELD=COLUMN.",
+            s
+        )),
+    }
+}
+
+/// Currencies are three letter ISO 4217 codes like USD or GBP
+pub fn validate_currency_core(s: &str) -> Result<(), String> {
+    let code = s.trim();
+
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a three letter currency code.", s))
+    }
+}
+
+pub fn inquire_validate_currency(s: &str) -> Result<Validation, Box<dyn Error + Send + Sync>> {
+    match validate_currency_core(s) {
+        Ok(()) => Ok(Validation::Valid),
+        Err(err) => Ok(Validation::Invalid(err.into())),
+    }
+}
+
 pub fn validate_name_core(s: &str) -> Result<(), String> {
     if s.trim().is_empty() {
         Err("Name cannot be empty or just whitespace.".to_string())
@@ -31,7 +83,7 @@ pub fn clap_validate_name(s: &str) -> Result<String, String> {
     }
 }
 
-fn validate_day_core(input: &u32) -> Result<(), String> {
+pub fn validate_day_core(input: &u32) -> Result<(), String> {
     if *input >= 1 && *input <= 31 {
         Ok(())
     } else {
@@ -55,10 +107,107 @@ pub fn clap_validate_day(input: &str) -> Result<String, String> {
     }
 }
 
+fn validate_email_core(s: &str) -> Result<(), String> {
+    let error = || Err(format!("'{}' is not a valid email address.", s));
+
+    if s.chars().any(char::is_whitespace) {
+        return error();
+    }
+
+    match s.split_once('@') {
+        Some((local, domain))
+            if !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.') =>
+        {
+            Ok(())
+        }
+        _ => error(),
+    }
+}
+
+pub fn validate_url_core(s: &str) -> Result<(), String> {
+    let rest = s
+        .strip_prefix("https://")
+        .or_else(|| s.strip_prefix("http://"))
+        .ok_or_else(|| format!("'{}' must start with http:// or https://", s))?;
+
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+
+    if host.contains('.') && !rest.chars().any(char::is_whitespace) {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a valid URL.", s))
+    }
+}
+
+fn validate_phone_core(s: &str) -> Result<(), String> {
+    let digits = s.chars().filter(|c| c.is_ascii_digit()).count();
+    let allowed = s
+        .chars()
+        .enumerate()
+        .all(|(i, c)| c.is_ascii_digit() || " -().".contains(c) || (i == 0 && c == '+'));
+
+    if allowed && (7..=15).contains(&digits) {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a valid phone number.", s))
+    }
+}
+
+fn validate_handle_core(s: &str) -> Result<(), String> {
+    if s.is_empty() || s.chars().any(char::is_whitespace) {
+        Err(format!("'{}' is not a valid handle.", s))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates a contact value against the rules for its kind
+pub fn validate_contact_core(kind: ContactKind, value: &str) -> Result<(), String> {
+    match kind {
+        ContactKind::Email => validate_email_core(value),
+        ContactKind::Phone => validate_phone_core(value),
+        ContactKind::Slack | ContactKind::Discord => validate_handle_core(value),
+        ContactKind::Linkedin | ContactKind::Github | ContactKind::Website => {
+            validate_url_core(value)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_currency_core() {
+        assert_eq!(validate_currency_core("gbp"), Ok(()));
+        assert_eq!(validate_currency_core(" USD "), Ok(()));
+        assert!(validate_currency_core("US$").is_err());
+        assert!(validate_currency_core("EURO").is_err());
+    }
+
+    #[test]
+    fn test_clap_validate_month() {
+        assert_eq!(clap_validate_month("2026-03"), Ok("2026-03".to_string()));
+        assert!(clap_validate_month("2026-13").is_err());
+        assert!(clap_validate_month("2026-3").is_err());
+        assert!(clap_validate_month("2026-03-01").is_err());
+        assert!(clap_validate_month("march").is_err());
+    }
+
+    #[test]
+    fn test_clap_validate_mapping() {
+        assert_eq!(
+            clap_validate_mapping("Date = When paid"),
+            Ok(("date".to_string(), "When paid".to_string()))
+        );
+        assert!(clap_validate_mapping("date").is_err());
+        assert!(clap_validate_mapping("date=").is_err());
+    }
+
     #[test]
     fn ok_for_valid_name() {
         let result = validate_name_core("dan");
@@ -168,4 +317,81 @@ mod tests {
             "The day must be between 1 and 31."
         )
     }
+
+    #[test]
+    fn clap_valid_date() {
+        let result = clap_validate_date("2026-03-01");
+        assert_eq!(result, Ok(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()));
+    }
+
+    #[test]
+    fn clap_invalid_date() {
+        let result = clap_validate_date("01/03/2026");
+        assert_eq!(
+            result,
+            Err(String::from(
+                "'01/03/2026' is not a date in the format YYYY-MM-DD."
+            ))
+        );
+    }
+
+    #[test]
+    fn normalizes_dates() {
+        assert_eq!(normalize_date(" 2026-1-5"), Ok("2026-01-05".to_string()));
+        assert!(normalize_date("2026-13-01").is_err());
+    }
+
+    #[test]
+    fn valid_emails() {
+        assert!(validate_contact_core(ContactKind::Email, "dan@example.com").is_ok());
+        assert!(validate_contact_core(ContactKind::Email, "dan.page+work@mail.co.uk").is_ok());
+    }
+
+    #[test]
+    fn invalid_emails() {
+        for email in [
+            "dan",
+            "dan@",
+            "@example.com",
+            "dan@example",
+            "d an@example.com",
+        ] {
+            assert!(
+                validate_contact_core(ContactKind::Email, email).is_err(),
+                "Expected error for: {}",
+                email
+            );
+        }
+    }
+
+    #[test]
+    fn valid_urls() {
+        assert!(validate_contact_core(ContactKind::Github, "https://github.com/d-pagey").is_ok());
+        assert!(validate_contact_core(ContactKind::Website, "http://example.com").is_ok());
+    }
+
+    #[test]
+    fn invalid_urls() {
+        let result = validate_contact_core(ContactKind::Linkedin, "linkedin.com/in/dan");
+        assert_eq!(
+            result,
+            Err(String::from(
+                "'linkedin.com/in/dan' must start with http:// or https://"
+            ))
+        );
+        assert!(validate_contact_core(ContactKind::Website, "https://localhost").is_err());
+    }
+
+    #[test]
+    fn valid_and_invalid_phones() {
+        assert!(validate_contact_core(ContactKind::Phone, "+44 (0) 7700 900123").is_ok());
+        assert!(validate_contact_core(ContactKind::Phone, "123").is_err());
+        assert!(validate_contact_core(ContactKind::Phone, "0770 ABC 123").is_err());
+    }
+
+    #[test]
+    fn handles_cannot_contain_whitespace() {
+        assert!(validate_contact_core(ContactKind::Slack, "dan.page").is_ok());
+        assert!(validate_contact_core(ContactKind::Discord, "dan page").is_err());
+    }
 }
@@ -1,28 +1,353 @@
 use assert_cmd::Command;
-use mentees::mentee_service::MenteeService;
+use chrono::NaiveDate;
+use mentees::{
+    models::{
+        call::CallInput,
+        list::ListOptions,
+        mentee::{MenteeChanges, NewMentee, Source, Status},
+        payment::PaymentInput,
+        video::{VideoInput, VideoStatus},
+    },
+    MenteeError, MenteeSort, PaymentSort, Store,
+};
 use predicates::prelude::predicate;
-use std::{env, fs, path::Path};
+use std::{env, fs, path::PathBuf};
 
 #[test]
 fn test_empty_mentees() {
-    setup_test_database();
+    let home = setup_test_dir("home");
 
     Command::cargo_bin("mentees")
         .unwrap()
-        .args(&["list"])
+        .env("HOME", &home)
+        .args(["list"])
         .assert()
         .success()
         .stdout(predicate::str::contains("Name")); // check table renders header
 }
 
-fn setup_test_database() {
-    let mut db_path = env::temp_dir();
-    db_path.push("test_mentees.db");
+#[test]
+fn test_completes_mentee_names() {
+    let home = setup_test_dir("completions");
+    fs::create_dir_all(home.join(".mentees")).unwrap();
+    let store = Store::open(home.join(".mentees").join("mentees_dev.db")).unwrap();
+    store
+        .mentees()
+        .create_mentee(NewMentee {
+            name: "Dan Page".to_string(),
+            calls: 2,
+            gross: 200,
+            net: 180,
+            status: Status::Hot,
+            payment_day: 5,
+            notes: None,
+            source: None,
+            referrer: None,
+        })
+        .unwrap();
+
+    Command::cargo_bin("mentees")
+        .unwrap()
+        .env("HOME", &home)
+        .env("COMPLETE", "fish")
+        .args(["--", "mentees", "view", "d"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dan page\thot"));
+
+    Command::cargo_bin("mentees")
+        .unwrap()
+        .env("HOME", &home)
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("COMPLETE=\"bash\""));
+}
+
+#[test]
+fn test_store_records_without_prompting() {
+    let store = Store::open(setup_test_dir("store").join("mentees.db")).unwrap();
+
+    let mentee = store
+        .mentees()
+        .create_mentee(NewMentee {
+            name: "Dan Page".to_string(),
+            calls: 2,
+            gross: 200,
+            net: 180,
+            status: Status::Hot,
+            payment_day: 5,
+            notes: None,
+            source: None,
+            referrer: None,
+        })
+        .unwrap();
+    assert_eq!(mentee.name, "dan page");
+
+    let call = store
+        .calls()
+        .create_call(
+            "dan page",
+            CallInput {
+                date: "2026-03-02".to_string(),
+                notes: Some("Mock interview".to_string()),
+                free_call: false,
+                duration: Some(60),
+                goal_ids: vec![],
+            },
+        )
+        .unwrap();
+    assert_eq!(store.calls().get_call(call.id).unwrap().date, "2026-03-02");
+
+    let payment = store
+        .payments()
+        .create_payment(
+            "dan page",
+            PaymentInput {
+                date: "2026-03-05".to_string(),
+                amount: 200,
+                currency: Some("gbp".to_string()),
+                fee: 20,
+                source: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(payment.currency.as_deref(), Some("GBP"));
+    assert_eq!(payment.net(), 180);
+
+    let video = store
+        .videos()
+        .create_video(
+            "dan page",
+            VideoInput {
+                date: "2026-03-06".to_string(),
+                length: 0,
+                notes: String::new(),
+                status: VideoStatus::Requested,
+                received_date: None,
+                due_date: Some("2026-03-13".to_string()),
+                source_url: None,
+                file_path: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(video.received_date.as_deref(), Some("2026-03-06"));
+
+    let mentee = store
+        .mentees()
+        .change_mentee(
+            "dan page",
+            MenteeChanges {
+                status: Some(Status::Cold),
+                reason: Some("Paused for the summer".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(mentee.status, Status::Cold);
+    assert_eq!(
+        store
+            .mentees()
+            .get_status_history("dan page".to_string())
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_lists_are_filtered_sorted_and_paged() {
+    let store = Store::open(setup_test_dir("lists").join("mentees.db")).unwrap();
+
+    for (name, status) in [("dan page", Status::Hot), ("amy", Status::Cold)] {
+        store
+            .mentees()
+            .create_mentee(NewMentee {
+                name: name.to_string(),
+                calls: 2,
+                gross: 200,
+                net: 180,
+                status,
+                payment_day: 5,
+                notes: None,
+                source: None,
+                referrer: None,
+            })
+            .unwrap();
+    }
+
+    for (name, date, amount) in [
+        ("dan page", "2026-01-05", 200),
+        ("dan page", "2026-02-05", 250),
+        ("amy", "2026-02-10", 300),
+        ("dan page", "2026-03-05", 150),
+    ] {
+        store
+            .payments()
+            .create_payment(
+                name,
+                PaymentInput {
+                    date: date.to_string(),
+                    amount,
+                    currency: None,
+                    fee: 0,
+                    source: None,
+                },
+            )
+            .unwrap();
+    }
+
+    let options = ListOptions {
+        since: NaiveDate::from_ymd_opt(2026, 2, 1),
+        sort: PaymentSort::Amount,
+        desc: true,
+        limit: Some(2),
+        ..ListOptions::default()
+    };
+    let amounts = |options: &ListOptions<PaymentSort>, status| -> Vec<u32> {
+        store
+            .payments()
+            .list_payments(None, status, options)
+            .unwrap()
+            .into_iter()
+            .map(|payment| payment.amount)
+            .collect()
+    };
+
+    assert_eq!(amounts(&options, None), vec![300, 250]);
+    assert_eq!(amounts(&options, Some(Status::Hot)), vec![250, 150]);
+    let next_page = ListOptions {
+        offset: 2,
+        ..options.clone()
+    };
+    assert_eq!(amounts(&next_page, None), vec![150]);
+
+    let names: Vec<String> = store
+        .mentees()
+        .get_mentees_summaries(
+            false,
+            None,
+            None,
+            &ListOptions {
+                sort: MenteeSort::Name,
+                limit: Some(1),
+                ..ListOptions::default()
+            },
+        )
+        .unwrap()
+        .into_iter()
+        .map(|mentee| mentee.name)
+        .collect();
+    assert_eq!(names, vec!["amy"]);
+}
+
+#[test]
+fn test_reports_mentees_by_source() {
+    let home = setup_test_dir("sources");
+    fs::create_dir_all(home.join(".mentees")).unwrap();
+    let store = Store::open(home.join(".mentees").join("mentees_dev.db")).unwrap();
+    let mentees = store.mentees();
+
+    let new_mentee = |name: &str, source: Option<Source>, referrer: Option<&str>| NewMentee {
+        name: name.to_string(),
+        calls: 2,
+        gross: 200,
+        net: 180,
+        status: Status::Hot,
+        payment_day: 5,
+        notes: None,
+        source,
+        referrer: referrer.map(str::to_string),
+    };
+
+    mentees
+        .create_mentee(new_mentee("amy", Some(Source::MentorCruise), None))
+        .unwrap();
+    let dan = mentees
+        .create_mentee(new_mentee("dan", None, Some("amy")))
+        .unwrap();
+    assert_eq!(dan.source, Some(Source::Referral));
+    assert_eq!(dan.referrer.as_deref(), Some("amy"));
+
+    // the referrer is kept by id, so it follows a rename
+    mentees
+        .change_mentee(
+            "amy",
+            MenteeChanges {
+                name: Some("amy lee".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        mentees.get_mentee("dan").unwrap().referrer.as_deref(),
+        Some("amy lee")
+    );
+
+    store
+        .payments()
+        .create_payment(
+            "dan",
+            PaymentInput {
+                date: "2026-03-02".to_string(),
+                amount: 200,
+                currency: Some("USD".to_string()),
+                fee: 20,
+                source: None,
+            },
+        )
+        .unwrap();
+
+    Command::cargo_bin("mentees")
+        .unwrap()
+        .env("HOME", &home)
+        .args(["report", "sources", "--output", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("MentorCruise,1,0,0%,-,0.0"))
+        .stdout(predicate::str::contains("Referral,1,1,100%,USD 180,"));
+}
+
+#[test]
+fn test_store_rejects_invalid_input() {
+    let store = Store::open(setup_test_dir("invalid").join("mentees.db")).unwrap();
+
+    let missing = store.calls().create_call(
+        "nobody",
+        CallInput {
+            date: "2026-03-02".to_string(),
+            notes: None,
+            free_call: false,
+            duration: None,
+            goal_ids: vec![],
+        },
+    );
+    assert!(matches!(missing, Err(MenteeError::NotFound(_))));
+
+    let bad_day = store.mentees().create_mentee(NewMentee {
+        name: "amy".to_string(),
+        calls: 4,
+        gross: 400,
+        net: 300,
+        status: Status::Warm,
+        payment_day: 32,
+        notes: None,
+        source: None,
+        referrer: None,
+    });
+    assert!(matches!(bad_day, Err(MenteeError::InvalidInput(_))));
+}
+
+/// A fresh directory under the system temp dir for one test
+fn setup_test_dir(name: &str) -> PathBuf {
+    let mut dir = env::temp_dir();
+    dir.push(format!("mentees-test-{}-{}", name, std::process::id()));
 
-    // Ensure no leftover database from previous tests
-    if Path::new(&db_path).exists() {
-        fs::remove_file(&db_path).unwrap();
+    // Ensure nothing is left over from previous runs
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
     }
+    fs::create_dir_all(&dir).unwrap();
 
-    let _ = MenteeService::new(true);
+    dir
 }